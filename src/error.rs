@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+use crate::parse::ast::{AstLocation, FileId};
+use crate::types::context::Language;
+
 /// Top-level error type exposed by the engine.
 ///
 /// This is what bubbles out to API / CLI / LSP callers.
@@ -43,6 +46,48 @@ pub enum ParseError {
         #[source]
         source: anyhow::Error,
     },
+
+    /// No grammar is available for `language`; check [`crate::parse::is_supported`]
+    /// before parsing.
+    #[error("parsing is not supported for {language:?} file {file_path} (file id {file_id})")]
+    UnsupportedLanguage {
+        file_id: FileId,
+        file_path: String,
+        language: Language,
+    },
+
+    #[error(
+        "parsing {file_path} (file id {file_id}) timed out after {timeout_ms} ms; \
+         raise the timeout or exclude the file if it is generated or minified"
+    )]
+    Timeout {
+        file_id: FileId,
+        file_path: String,
+        timeout_ms: u64,
+    },
+
+    #[error("{file_path} has {} syntax error(s)", errors.len())]
+    SyntaxErrors {
+        file_path: String,
+        errors: Vec<AstLocation>,
+    },
+
+    #[error("tree was not parsed with the {language:?} grammar")]
+    LanguageMismatch { language: Language },
+
+    #[error("tree for {file_path} spans {tree_len} bytes but the content has only {content_len}")]
+    ContentMismatch {
+        file_path: String,
+        tree_len: usize,
+        content_len: usize,
+    },
+}
+
+/// Errors compiling a tree-sitter query.
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("invalid query: {0}")]
+    Invalid(#[from] tree_sitter::QueryError),
 }
 
 /// Errors building semantic models (symbol tables, HTTP call model, etc.).
@@ -50,6 +95,21 @@ pub enum ParseError {
 pub enum SemanticError {
     #[error("failed to build semantic model for {file_path}: {reason}")]
     File { file_path: String, reason: String },
+
+    /// The syntax tree has ERROR/MISSING nodes; `location` is the first one.
+    ///
+    /// Returned by [`crate::semantics::build_semantics_strict`].
+    #[error(
+        "{file_path} (file id {file_id}) has a syntax error at line {}, column {}; \
+         fix it or analyze the partial tree leniently",
+        location.range.start_line + 1,
+        location.range.start_char_col + 1
+    )]
+    IncompleteTree {
+        file_id: FileId,
+        file_path: String,
+        location: AstLocation,
+    },
 }
 
 /// Errors building or querying the CodeGraph.
@@ -60,6 +120,9 @@ pub enum GraphError {
 
     #[error("graph build failed: {0}")]
     Build(String),
+
+    #[error("import cycle detected: {}", cycle.join(" -> "))]
+    Cycle { cycle: Vec<String> },
 }
 
 /// Errors executing rules.
@@ -142,6 +205,14 @@ mod tests {
         assert!(engine_err.to_string().contains("graph error"));
     }
 
+    #[test]
+    fn test_graph_error_cycle_display() {
+        let err = GraphError::Cycle {
+            cycle: vec!["a.py".to_string(), "b.py".to_string()],
+        };
+        assert_eq!(err.to_string(), "import cycle detected: a.py -> b.py");
+    }
+
     #[test]
     fn test_engine_error_from_rule_error() {
         let rule_err = RuleError::RuleFailed {
@@ -191,6 +262,40 @@ mod tests {
         assert!(debug_str.contains("test.rs"));
     }
 
+    #[test]
+    fn test_parse_error_unsupported_language_display() {
+        let err = ParseError::UnsupportedLanguage {
+            file_id: FileId(3),
+            file_path: "app.js".to_string(),
+            language: Language::Javascript,
+        };
+        assert_eq!(
+            err.to_string(),
+            "parsing is not supported for Javascript file app.js (file id 3)"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_syntax_errors_display() {
+        use crate::parse::ast::{FileId, TextRange};
+
+        let err = ParseError::SyntaxErrors {
+            file_path: "broken.py".to_string(),
+            errors: vec![AstLocation {
+                file_id: FileId(1),
+                range: TextRange {
+                    start_line: 3,
+                    start_col: 10,
+                    end_line: 3,
+                    end_col: 11,
+                    start_char_col: 10,
+                    end_char_col: 11,
+                },
+            }],
+        };
+        assert_eq!(err.to_string(), "broken.py has 1 syntax error(s)");
+    }
+
     // ==================== SemanticError Tests ====================
 
     #[test]
@@ -216,6 +321,42 @@ mod tests {
         assert!(debug_str.contains("type mismatch"));
     }
 
+    #[test]
+    fn test_parse_error_timeout_display() {
+        let err = ParseError::Timeout {
+            file_id: FileId(12),
+            file_path: "bundle.ts".to_string(),
+            timeout_ms: 500,
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("bundle.ts (file id 12)"));
+        assert!(msg.contains("timed out after 500 ms"));
+    }
+
+    #[test]
+    fn test_semantic_error_incomplete_tree_display() {
+        use crate::parse::ast::TextRange;
+
+        let err = SemanticError::IncompleteTree {
+            file_id: FileId(4),
+            file_path: "views.py".to_string(),
+            location: AstLocation {
+                file_id: FileId(4),
+                range: TextRange {
+                    start_line: 9,
+                    start_col: 2,
+                    end_line: 9,
+                    end_col: 3,
+                    start_char_col: 2,
+                    end_char_col: 3,
+                },
+            },
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("views.py (file id 4)"));
+        assert!(msg.contains("line 10, column 3"));
+    }
+
     // ==================== GraphError Tests ====================
 
     #[test]
@@ -0,0 +1,425 @@
+//! Text and JSON exports of the CodeGraph for external visualization tools.
+
+use std::fmt::Write;
+
+use petgraph::visit::EdgeRef;
+use serde_json::{Value, json};
+
+use super::{CodeGraph, GraphEdgeKind, GraphNode};
+use crate::types::context::Language;
+
+impl CodeGraph {
+    /// Render the graph in Graphviz DOT format.
+    ///
+    /// Nodes are labeled with their display name (file path, qualified
+    /// function name, module name, ...). Import edges are drawn solid,
+    /// call edges dashed and containment edges dotted, so the output can
+    /// be fed straight to `dot -Tsvg`.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph CodeGraph {\n");
+        out.push_str("    rankdir=LR;\n");
+
+        for idx in self.graph.node_indices() {
+            let node = &self.graph[idx];
+            let _ = writeln!(
+                out,
+                "    n{} [label=\"{}\", shape={}];",
+                idx.index(),
+                escape_dot(&node.display_name()),
+                dot_shape(node)
+            );
+        }
+
+        for edge in self.graph.edge_references() {
+            let _ = writeln!(
+                out,
+                "    n{} -> n{} [style={}, label=\"{}\"];",
+                edge.source().index(),
+                edge.target().index(),
+                dot_style(edge.weight()),
+                escape_dot(edge_label(edge.weight()))
+            );
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render the graph as GraphML XML.
+    ///
+    /// Every node carries `label` and `kind` attributes; nodes that belong to
+    /// a file also carry that file's `path` and `language`, and file nodes
+    /// their `line_count`. Edges carry their `kind`. The output opens
+    /// directly in Gephi or yEd.
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (id, domain, name, ty) in [
+            ("label", "node", "label", "string"),
+            ("kind", "node", "kind", "string"),
+            ("path", "node", "path", "string"),
+            ("language", "node", "language", "string"),
+            ("line_count", "node", "line_count", "int"),
+            ("edge_kind", "edge", "kind", "string"),
+        ] {
+            let _ = writeln!(
+                out,
+                "  <key id=\"{id}\" for=\"{domain}\" attr.name=\"{name}\" attr.type=\"{ty}\"/>"
+            );
+        }
+        out.push_str("  <graph id=\"CodeGraph\" edgedefault=\"directed\">\n");
+
+        for idx in self.graph.node_indices() {
+            let node = &self.graph[idx];
+            let _ = writeln!(out, "    <node id=\"n{}\">", idx.index());
+            write_graphml_data(&mut out, "label", &node.display_name());
+            write_graphml_data(&mut out, "kind", node_kind(node));
+            if let Some(GraphNode::File {
+                path,
+                language,
+                line_count,
+                ..
+            }) = self.file_of(node)
+            {
+                write_graphml_data(&mut out, "path", path);
+                write_graphml_data(&mut out, "language", language_name(*language));
+                if node.is_file() {
+                    write_graphml_data(&mut out, "line_count", &line_count.to_string());
+                }
+            }
+            out.push_str("    </node>\n");
+        }
+
+        for edge in self.graph.edge_references() {
+            let _ = writeln!(
+                out,
+                "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">",
+                edge.id().index(),
+                edge.source().index(),
+                edge.target().index()
+            );
+            write_graphml_data(&mut out, "edge_kind", edge_label(edge.weight()));
+            out.push_str("    </edge>\n");
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// Render the graph in the JSON Graph shape `{ "nodes": [...], "edges": [...] }`.
+    ///
+    /// Nodes have an `id`, `label`, `kind` and, when they belong to a file,
+    /// that file's `language`; edges reference node ids through `source`
+    /// and `target` and carry their `kind`. d3 and cytoscape consume this
+    /// shape directly.
+    pub fn to_json_graph(&self) -> Value {
+        let nodes: Vec<Value> = self
+            .graph
+            .node_indices()
+            .map(|idx| {
+                let node = &self.graph[idx];
+                let mut value = json!({
+                    "id": format!("n{}", idx.index()),
+                    "label": node.display_name(),
+                    "kind": node_kind(node),
+                });
+                if let Some(GraphNode::File { language, .. }) = self.file_of(node) {
+                    value["language"] = json!(language_name(*language));
+                }
+                value
+            })
+            .collect();
+
+        let edges: Vec<Value> = self
+            .graph
+            .edge_references()
+            .map(|edge| {
+                json!({
+                    "id": format!("e{}", edge.id().index()),
+                    "source": format!("n{}", edge.source().index()),
+                    "target": format!("n{}", edge.target().index()),
+                    "kind": edge_label(edge.weight()),
+                })
+            })
+            .collect();
+
+        json!({ "nodes": nodes, "edges": edges })
+    }
+
+    /// The file node a node belongs to (itself for file nodes).
+    fn file_of<'a>(&'a self, node: &'a GraphNode) -> Option<&'a GraphNode> {
+        if node.is_file() {
+            return Some(node);
+        }
+        node.file_id()
+            .and_then(|file_id| self.file_nodes.get(&file_id))
+            .map(|&idx| &self.graph[idx])
+    }
+}
+
+fn write_graphml_data(out: &mut String, key: &str, value: &str) {
+    let _ = writeln!(
+        out,
+        "      <data key=\"{}\">{}</data>",
+        key,
+        escape_xml(value)
+    );
+}
+
+/// Short label describing a node kind
+fn node_kind(node: &GraphNode) -> &'static str {
+    match node {
+        GraphNode::File { .. } => "file",
+        GraphNode::Function { .. } => "function",
+        GraphNode::Class { .. } => "class",
+        GraphNode::ExternalModule { .. } => "external_module",
+        GraphNode::FastApiApp { .. } => "fastapi_app",
+        GraphNode::FastApiRoute { .. } => "fastapi_route",
+        GraphNode::FastApiMiddleware { .. } => "fastapi_middleware",
+        GraphNode::Slo { .. } => "slo",
+    }
+}
+
+/// Lowercase language name, matching its serialized form
+fn language_name(language: Language) -> &'static str {
+    match language {
+        Language::Python => "python",
+        Language::Rust => "rust",
+        Language::Go => "go",
+        Language::Java => "java",
+        Language::Typescript => "typescript",
+        Language::Javascript => "javascript",
+    }
+}
+
+/// Short label describing an edge kind
+fn edge_label(kind: &GraphEdgeKind) -> &'static str {
+    match kind {
+        GraphEdgeKind::Contains => "contains",
+        GraphEdgeKind::Imports => "imports",
+        GraphEdgeKind::ImportsFrom { .. } => "imports_from",
+        GraphEdgeKind::Calls => "calls",
+        GraphEdgeKind::Inherits => "inherits",
+        GraphEdgeKind::UsesLibrary => "uses_library",
+        GraphEdgeKind::DependencyInjection => "dependency_injection",
+        GraphEdgeKind::FastApiAppOwnsRoute => "owns_route",
+        GraphEdgeKind::FastApiAppHasMiddleware => "has_middleware",
+        GraphEdgeKind::FastApiAppLifespan => "lifespan",
+        GraphEdgeKind::MonitoredBy => "monitored_by",
+    }
+}
+
+fn dot_style(kind: &GraphEdgeKind) -> &'static str {
+    match kind {
+        GraphEdgeKind::Calls => "dashed",
+        GraphEdgeKind::Contains => "dotted",
+        _ => "solid",
+    }
+}
+
+fn dot_shape(node: &GraphNode) -> &'static str {
+    match node {
+        GraphNode::File { .. } => "box",
+        GraphNode::Function { .. } => "ellipse",
+        GraphNode::Class { .. } => "component",
+        GraphNode::ExternalModule { .. } => "folder",
+        GraphNode::Slo { .. } => "diamond",
+        GraphNode::FastApiApp { .. }
+        | GraphNode::FastApiRoute { .. }
+        | GraphNode::FastApiMiddleware { .. } => "hexagon",
+    }
+}
+
+/// Escape a string for use inside a double-quoted DOT identifier.
+fn escape_dot(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape a string for use as XML character data or an attribute value.
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ModuleCategory;
+    use crate::parse::ast::FileId;
+
+    fn sample_graph() -> CodeGraph {
+        let mut cg = CodeGraph::new();
+        let main = cg.graph.add_node(GraphNode::File {
+            file_id: FileId(1),
+            path: "src/main.py".to_string(),
+            language: Language::Python,
+            line_count: 0,
+            function_count: 0,
+        });
+        let utils = cg.graph.add_node(GraphNode::File {
+            file_id: FileId(2),
+            path: "src/utils.py".to_string(),
+            language: Language::Python,
+            line_count: 0,
+            function_count: 0,
+        });
+        let run = cg.graph.add_node(GraphNode::Function {
+            file_id: FileId(1),
+            name: "run".to_string(),
+            qualified_name: "run".to_string(),
+            is_async: false,
+            is_handler: false,
+            http_method: None,
+            http_path: None,
+            start_line: Some(1),
+            end_line: Some(3),
+        });
+        let helper = cg.graph.add_node(GraphNode::Function {
+            file_id: FileId(2),
+            name: "helper".to_string(),
+            qualified_name: "helper".to_string(),
+            is_async: false,
+            is_handler: false,
+            http_method: None,
+            http_path: None,
+            start_line: Some(1),
+            end_line: Some(2),
+        });
+        cg.graph.add_edge(main, utils, GraphEdgeKind::Imports);
+        cg.graph.add_edge(main, run, GraphEdgeKind::Contains);
+        cg.graph.add_edge(utils, helper, GraphEdgeKind::Contains);
+        cg.graph.add_edge(run, helper, GraphEdgeKind::Calls);
+        cg
+    }
+
+    #[test]
+    fn to_dot_has_digraph_header() {
+        let dot = sample_graph().to_dot();
+        assert!(dot.starts_with("digraph CodeGraph {"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn to_dot_one_line_per_edge() {
+        let cg = sample_graph();
+        let dot = cg.to_dot();
+        let edge_lines = dot.lines().filter(|l| l.contains(" -> ")).count();
+        assert_eq!(edge_lines, cg.graph.edge_count());
+    }
+
+    #[test]
+    fn to_dot_labels_and_styles() {
+        let dot = sample_graph().to_dot();
+        assert!(dot.contains("label=\"src/main.py\""));
+        assert!(dot.contains("label=\"helper\""));
+        assert!(dot.contains("n0 -> n1 [style=solid, label=\"imports\"]"));
+        assert!(dot.contains("n2 -> n3 [style=dashed, label=\"calls\"]"));
+    }
+
+    #[test]
+    fn to_dot_empty_graph() {
+        let dot = CodeGraph::new().to_dot();
+        assert!(dot.starts_with("digraph"));
+        assert!(!dot.contains("->"));
+    }
+
+    #[test]
+    fn escape_dot_special_characters() {
+        assert_eq!(escape_dot(r#"say "hi""#), r#"say \"hi\""#);
+        assert_eq!(escape_dot(r"C:\path"), r"C:\\path");
+        assert_eq!(escape_dot("a\nb"), r"a\nb");
+    }
+
+    /// Check that every opened tag is closed in order, returning the tag names seen.
+    fn xml_tags(xml: &str) -> Vec<String> {
+        let mut open = Vec::new();
+        let mut seen = Vec::new();
+        for tag in xml.split('<').skip(1).map(|t| t.split('>').next().unwrap()) {
+            if tag.starts_with('?') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(name), "mismatched </{name}>");
+                continue;
+            }
+            let name = tag.split_whitespace().next().unwrap().to_string();
+            seen.push(name.clone());
+            if !tag.ends_with('/') {
+                open.push(name);
+            }
+        }
+        assert!(open.is_empty(), "unclosed tags: {open:?}");
+        seen
+    }
+
+    #[test]
+    fn to_graphml_is_well_formed_with_all_nodes_and_edges() {
+        let cg = sample_graph();
+        let xml = cg.to_graphml();
+        assert!(xml.starts_with("<?xml"));
+
+        let tags = xml_tags(&xml);
+        let count = |name: &str| tags.iter().filter(|t| *t == name).count();
+        assert_eq!(count("graphml"), 1);
+        assert_eq!(count("node"), cg.graph.node_count());
+        assert_eq!(count("edge"), cg.graph.edge_count());
+
+        assert!(xml.contains("<data key=\"path\">src/main.py</data>"));
+        assert!(xml.contains("<data key=\"language\">python</data>"));
+        assert!(xml.contains("<data key=\"edge_kind\">calls</data>"));
+    }
+
+    #[test]
+    fn to_graphml_escapes_labels() {
+        let mut cg = CodeGraph::new();
+        cg.graph.add_node(GraphNode::ExternalModule {
+            name: "<a & \"b\">".to_string(),
+            category: ModuleCategory::Other,
+        });
+        let xml = cg.to_graphml();
+        assert!(xml.contains("<data key=\"label\">&lt;a &amp; &quot;b&quot;&gt;</data>"));
+        xml_tags(&xml);
+    }
+
+    #[test]
+    fn to_json_graph_edges_reference_existing_nodes() {
+        let cg = sample_graph();
+        let graph = cg.to_json_graph();
+        let nodes = graph["nodes"].as_array().unwrap();
+        let edges = graph["edges"].as_array().unwrap();
+        assert_eq!(nodes.len(), cg.graph.node_count());
+        assert_eq!(edges.len(), cg.graph.edge_count());
+
+        let ids: Vec<&str> = nodes.iter().map(|n| n["id"].as_str().unwrap()).collect();
+        for edge in edges {
+            assert!(ids.contains(&edge["source"].as_str().unwrap()));
+            assert!(ids.contains(&edge["target"].as_str().unwrap()));
+        }
+
+        assert_eq!(nodes[0]["label"], "src/main.py");
+        assert_eq!(nodes[0]["kind"], "file");
+        assert_eq!(nodes[0]["language"], "python");
+        assert_eq!(edges[0]["kind"], "imports");
+        assert_eq!(edges[3]["kind"], "calls");
+    }
+}
@@ -15,16 +15,22 @@
 //! - UsesLibrary: File/function uses external library
 //! - Framework-specific edges (FastAPI routes, middlewares)
 
+mod export;
+
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use petgraph::Direction;
+use petgraph::algo::tarjan_scc;
 use petgraph::graph::{DiGraph, NodeIndex};
-use petgraph::visit::EdgeRef;
+use petgraph::visit::{EdgeFiltered, EdgeRef};
 use serde::{Deserialize, Serialize};
 
+use crate::error::GraphError;
 use crate::parse::ast::FileId;
 use crate::semantics::common::CommonSemantics;
+use crate::semantics::common::functions::FunctionDef;
+use crate::semantics::common::imports::ImportSource;
 use crate::semantics::go::frameworks::GoFrameworkSummary;
 use crate::semantics::go::model::GoFileSemantics;
 use crate::semantics::python::fastapi::FastApiFileSummary;
@@ -81,6 +87,12 @@ pub enum GraphNode {
         file_id: FileId,
         path: String,
         language: Language,
+        /// Number of lines in the file
+        #[serde(default)]
+        line_count: usize,
+        /// Number of functions and methods defined in the file
+        #[serde(default)]
+        function_count: usize,
     },
 
     /// A function or method definition
@@ -219,6 +231,60 @@ impl GraphNode {
     pub fn is_file(&self) -> bool {
         matches!(self, GraphNode::File { .. })
     }
+
+    /// The fields that identify this node within a graph.
+    ///
+    /// Descriptive data such as line counts or SLO percentages is left out,
+    /// so two snapshots of the same function or file compare equal.
+    fn identity(&self) -> NodeIdentity<'_> {
+        match self {
+            GraphNode::File { file_id, .. } => NodeIdentity::File(*file_id),
+            GraphNode::Function { file_id, name, .. } => NodeIdentity::Function(*file_id, name),
+            GraphNode::Class { file_id, name } => NodeIdentity::Class(*file_id, name),
+            GraphNode::ExternalModule { name, .. } => NodeIdentity::ExternalModule(name),
+            GraphNode::FastApiApp { file_id, var_name } => {
+                NodeIdentity::FastApiApp(*file_id, var_name)
+            }
+            GraphNode::FastApiRoute {
+                file_id,
+                http_method,
+                path,
+            } => NodeIdentity::FastApiRoute(*file_id, http_method, path),
+            GraphNode::FastApiMiddleware {
+                file_id,
+                app_var_name,
+                middleware_type,
+            } => NodeIdentity::FastApiMiddleware(*file_id, app_var_name, middleware_type),
+            GraphNode::Slo { id, .. } => NodeIdentity::Slo(id),
+        }
+    }
+}
+
+impl PartialEq for GraphNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for GraphNode {}
+
+impl std::hash::Hash for GraphNode {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
+}
+
+/// Borrowed identity of a [`GraphNode`], used for equality and hashing.
+#[derive(PartialEq, Eq, Hash)]
+enum NodeIdentity<'a> {
+    File(FileId),
+    Function(FileId, &'a str),
+    Class(FileId, &'a str),
+    ExternalModule(&'a str),
+    FastApiApp(FileId, &'a str),
+    FastApiRoute(FileId, &'a str, &'a str),
+    FastApiMiddleware(FileId, &'a str, &'a str),
+    Slo(&'a str),
 }
 
 /// Edge kinds between nodes.
@@ -361,7 +427,7 @@ impl CodeGraph {
         if path.contains('.') {
             let file_path = path.replace('.', "/");
             // Try with common extensions
-            for ext in &[".py", ".ts", ".tsx", ".js", ".go", ".rs"] {
+            for ext in &[".py", ".ts", ".tsx", ".js", ".go", ".rs", ".java"] {
                 let full_path = format!("{}{}", file_path, ext);
                 if let Some(&idx) = self.suffix_to_file.get(&full_path) {
                     return Some(idx);
@@ -474,6 +540,333 @@ impl CodeGraph {
         result
     }
 
+    /// Files that directly import `node` ("who imports me").
+    ///
+    /// `node` may be a file or an external module; for an external module
+    /// the dependents are the files using that library.
+    pub fn dependents(&self, node: &GraphNode) -> Vec<GraphNode> {
+        let Some(idx) = self.dependency_target(node) else {
+            return vec![];
+        };
+        self.direct_dependents(idx)
+            .into_iter()
+            .map(|idx| self.graph[idx].clone())
+            .collect()
+    }
+
+    /// Files that import `node` directly or through other files, nearest first.
+    ///
+    /// Walks the reverse import edges breadth-first, so deleting `node`
+    /// may affect every file returned.
+    pub fn transitive_dependents(&self, node: &GraphNode) -> Vec<GraphNode> {
+        let Some(start) = self.dependency_target(node) else {
+            return vec![];
+        };
+
+        let mut result = Vec::new();
+        let mut visited = std::collections::HashSet::from([start]);
+        let mut queue = std::collections::VecDeque::from([start]);
+
+        while let Some(current) = queue.pop_front() {
+            for importer in self.direct_dependents(current) {
+                if visited.insert(importer) {
+                    result.push(self.graph[importer].clone());
+                    queue.push_back(importer);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Shortest chain of imports leading from `from` to `to`, both included.
+    ///
+    /// Explains why one module depends on another: e.g. `[api.py, service.py,
+    /// models.py]` when `api` imports `service`, which imports `models`.
+    /// `to` may be an external module. Returns `None` when `to` is not
+    /// reachable, and `Some(vec![from])` when both are the same node.
+    pub fn shortest_path(&self, from: &GraphNode, to: &GraphNode) -> Option<Vec<GraphNode>> {
+        let start = self.dependency_target(from)?;
+        let goal = self.dependency_target(to)?;
+
+        let mut previous: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut visited = std::collections::HashSet::from([start]);
+        let mut queue = std::collections::VecDeque::from([start]);
+
+        while let Some(current) = queue.pop_front() {
+            if current == goal {
+                let mut path = vec![goal];
+                while let Some(&prev) = previous.get(path.last()?) {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some(
+                    path.into_iter()
+                        .map(|idx| self.graph[idx].clone())
+                        .collect(),
+                );
+            }
+
+            let mut next: Vec<NodeIndex> = self
+                .graph
+                .edges_directed(current, Direction::Outgoing)
+                .filter(|e| is_import_edge(e.weight()))
+                .map(|e| e.target())
+                .collect();
+            next.sort();
+            for target in next {
+                if visited.insert(target) {
+                    previous.insert(target, current);
+                    queue.push_back(target);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Files that neither import nor are imported by any other file.
+    ///
+    /// Structural edges (a file containing its functions) and external
+    /// library usage are ignored, so a script that only uses third-party
+    /// packages is still reported. Entry points, test files and
+    /// `__init__.py` may show up here on purpose; they are reported, not
+    /// filtered.
+    pub fn orphans(&self) -> Vec<GraphNode> {
+        self.graph
+            .node_indices()
+            .filter(|&idx| self.graph[idx].is_file())
+            .filter(|&idx| {
+                self.graph
+                    .edges_directed(idx, Direction::Incoming)
+                    .chain(self.graph.edges_directed(idx, Direction::Outgoing))
+                    .all(|e| {
+                        !is_import_edge(e.weight())
+                            || !self.graph[e.source()].is_file()
+                            || !self.graph[e.target()].is_file()
+                            || e.source() == e.target()
+                    })
+            })
+            .map(|idx| self.graph[idx].clone())
+            .collect()
+    }
+
+    /// Files that cannot be reached by following imports from any of `roots`.
+    ///
+    /// `roots` are the entry points (e.g. `main.py`, route modules); they
+    /// count as reachable themselves. Unknown roots are ignored.
+    pub fn unreachable_from(&self, roots: &[GraphNode]) -> Vec<GraphNode> {
+        let mut visited: std::collections::HashSet<NodeIndex> = roots
+            .iter()
+            .filter_map(|root| self.dependency_target(root))
+            .collect();
+        let mut queue: std::collections::VecDeque<NodeIndex> = visited.iter().copied().collect();
+
+        while let Some(current) = queue.pop_front() {
+            for edge in self.graph.edges_directed(current, Direction::Outgoing) {
+                if is_import_edge(edge.weight()) && visited.insert(edge.target()) {
+                    queue.push_back(edge.target());
+                }
+            }
+        }
+
+        self.graph
+            .node_indices()
+            .filter(|idx| self.graph[*idx].is_file() && !visited.contains(idx))
+            .map(|idx| self.graph[idx].clone())
+            .collect()
+    }
+
+    /// Node index of a file or external module node in this graph.
+    fn dependency_target(&self, node: &GraphNode) -> Option<NodeIndex> {
+        match node {
+            GraphNode::File { file_id, .. } => self.file_nodes.get(file_id).copied(),
+            GraphNode::ExternalModule { name, .. } => self.external_modules.get(name).copied(),
+            _ => None,
+        }
+    }
+
+    /// Distinct file nodes with an import edge into `idx`.
+    fn direct_dependents(&self, idx: NodeIndex) -> Vec<NodeIndex> {
+        let mut importers = Vec::new();
+        for edge in self.graph.edges_directed(idx, Direction::Incoming) {
+            if is_import_edge(edge.weight())
+                && self.graph[edge.source()].is_file()
+                && !importers.contains(&edge.source())
+            {
+                importers.push(edge.source());
+            }
+        }
+        importers.sort();
+        importers
+    }
+
+    /// Find circular imports between files.
+    ///
+    /// Runs Tarjan's strongly connected components algorithm over the
+    /// file import edges (`Imports` and `ImportsFrom`) and returns every
+    /// component with more than one file, as well as files importing
+    /// themselves. Each cycle lists its files in import order, starting
+    /// from the file that was added to the graph first.
+    pub fn find_cycles(&self) -> Vec<Vec<GraphNode>> {
+        self.import_cycles()
+            .into_iter()
+            .map(|cycle| {
+                cycle
+                    .into_iter()
+                    .map(|idx| self.graph[idx].clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Order files so that every file comes after the files it imports.
+    ///
+    /// Uses Kahn's algorithm over the file import edges (`Imports` and
+    /// `ImportsFrom`), treating "A imports B" as "A depends on B". Files
+    /// without ordering constraints keep their insertion order.
+    ///
+    /// Returns `GraphError::Cycle` with the paths of one offending cycle
+    /// if the import graph is not a DAG.
+    pub fn topological_order(&self) -> Result<Vec<GraphNode>, GraphError> {
+        let files: Vec<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&idx| self.graph[idx].is_file())
+            .collect();
+
+        // Number of distinct files each file still waits on
+        let mut pending: HashMap<NodeIndex, usize> = HashMap::new();
+        for &idx in &files {
+            let deps: std::collections::HashSet<NodeIndex> = self
+                .graph
+                .edges_directed(idx, Direction::Outgoing)
+                .filter(|e| {
+                    matches!(
+                        e.weight(),
+                        GraphEdgeKind::Imports | GraphEdgeKind::ImportsFrom { .. }
+                    )
+                })
+                .map(|e| e.target())
+                .collect();
+            pending.insert(idx, deps.len());
+        }
+
+        let mut queue: std::collections::VecDeque<NodeIndex> = files
+            .iter()
+            .copied()
+            .filter(|idx| pending[idx] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(files.len());
+
+        while let Some(idx) = queue.pop_front() {
+            order.push(self.graph[idx].clone());
+
+            let importers: std::collections::HashSet<NodeIndex> = self
+                .graph
+                .edges_directed(idx, Direction::Incoming)
+                .filter(|e| {
+                    matches!(
+                        e.weight(),
+                        GraphEdgeKind::Imports | GraphEdgeKind::ImportsFrom { .. }
+                    )
+                })
+                .map(|e| e.source())
+                .collect();
+            let mut importers: Vec<NodeIndex> = importers.into_iter().collect();
+            importers.sort_unstable();
+
+            for importer in importers {
+                if let Some(count) = pending.get_mut(&importer) {
+                    *count -= 1;
+                    if *count == 0 {
+                        queue.push_back(importer);
+                    }
+                }
+            }
+        }
+
+        if order.len() < files.len() {
+            let cycle = self
+                .import_cycles()
+                .into_iter()
+                .next()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|idx| self.graph[idx].display_name())
+                .collect();
+            return Err(GraphError::Cycle { cycle });
+        }
+
+        Ok(order)
+    }
+
+    /// Node indexes of each import cycle, ordered as in `find_cycles`.
+    fn import_cycles(&self) -> Vec<Vec<NodeIndex>> {
+        let imports_only = EdgeFiltered::from_fn(&self.graph, |e| {
+            matches!(
+                e.weight(),
+                GraphEdgeKind::Imports | GraphEdgeKind::ImportsFrom { .. }
+            )
+        });
+
+        let mut cycles: Vec<Vec<NodeIndex>> = tarjan_scc(&imports_only)
+            .into_iter()
+            .filter(|scc| scc.len() > 1 || self.has_import_edge(scc[0], scc[0]))
+            .map(|scc| self.order_import_cycle(&scc))
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Order the members of a strongly connected component by walking import
+    /// edges depth-first from its lowest node index.
+    fn order_import_cycle(&self, scc: &[NodeIndex]) -> Vec<NodeIndex> {
+        let members: std::collections::HashSet<NodeIndex> = scc.iter().copied().collect();
+        let Some(&start) = scc.iter().min() else {
+            return vec![];
+        };
+
+        let mut ordered = Vec::with_capacity(scc.len());
+        let mut visited = std::collections::HashSet::new();
+        let mut stack = vec![start];
+
+        while let Some(idx) = stack.pop() {
+            if !visited.insert(idx) {
+                continue;
+            }
+            ordered.push(idx);
+
+            let mut next: Vec<NodeIndex> = self
+                .graph
+                .edges_directed(idx, Direction::Outgoing)
+                .filter(|e| {
+                    matches!(
+                        e.weight(),
+                        GraphEdgeKind::Imports | GraphEdgeKind::ImportsFrom { .. }
+                    )
+                })
+                .map(|e| e.target())
+                .filter(|t| members.contains(t) && !visited.contains(t))
+                .collect();
+            // Push in reverse so the lowest index is visited next
+            next.sort_unstable_by(|a, b| b.cmp(a));
+            stack.extend(next);
+        }
+
+        ordered
+    }
+
+    /// Check whether `from` has an import edge to `to`
+    fn has_import_edge(&self, from: NodeIndex, to: NodeIndex) -> bool {
+        self.graph.edges_connecting(from, to).any(|e| {
+            matches!(
+                e.weight(),
+                GraphEdgeKind::Imports | GraphEdgeKind::ImportsFrom { .. }
+            )
+        })
+    }
+
     /// Get external libraries used by a file
     pub fn get_external_dependencies(&self, file_id: FileId) -> Vec<String> {
         let Some(&file_idx) = self.file_nodes.get(&file_id) else {
@@ -548,6 +941,18 @@ impl CodeGraph {
             }
         }
 
+        let degrees = self.node_degrees();
+        let max_fan_in = degrees
+            .values()
+            .map(|(fan_in, _)| *fan_in)
+            .max()
+            .unwrap_or(0);
+        let max_fan_out = degrees
+            .values()
+            .map(|(_, fan_out)| *fan_out)
+            .max()
+            .unwrap_or(0);
+
         GraphStats {
             file_count,
             function_count,
@@ -557,11 +962,81 @@ impl CodeGraph {
             contains_edge_count,
             uses_library_edge_count,
             calls_edge_count,
+            max_fan_in,
+            max_fan_out,
             total_nodes: self.graph.node_count(),
             total_edges: self.graph.edge_count(),
         }
     }
 
+    /// Get the (fan_in, fan_out) of every node in the graph.
+    ///
+    /// Fan-in is the number of incoming edges and fan-out the number of
+    /// outgoing edges, across all edge kinds. Nodes sharing an identity
+    /// (see the `GraphNode` equality impl) have their degrees summed.
+    pub fn node_degrees(&self) -> HashMap<GraphNode, (usize, usize)> {
+        let mut degrees: HashMap<GraphNode, (usize, usize)> = HashMap::new();
+        for idx in self.graph.node_indices() {
+            let fan_in = self.graph.edges_directed(idx, Direction::Incoming).count();
+            let fan_out = self.graph.edges_directed(idx, Direction::Outgoing).count();
+            let entry = degrees.entry(self.graph[idx].clone()).or_default();
+            entry.0 += fan_in;
+            entry.1 += fan_out;
+        }
+        degrees
+    }
+
+    /// Extract the nodes matching `predicate` into a new graph.
+    ///
+    /// Edges are kept only when both endpoints match, so relationships that
+    /// cross the slice boundary are dropped. Lookup indexes are rebuilt for
+    /// the new graph.
+    pub fn subgraph(&self, predicate: impl Fn(&GraphNode) -> bool) -> CodeGraph {
+        let graph = self.graph.filter_map(
+            |_, node| predicate(node).then(|| node.clone()),
+            |_, edge| Some(edge.clone()),
+        );
+        let mut sub = CodeGraph {
+            graph,
+            ..CodeGraph::new()
+        };
+        sub.rebuild_indexes();
+        sub
+    }
+
+    /// Extract the nodes that belong to files of the given language.
+    ///
+    /// Functions, classes and framework nodes follow their file; nodes
+    /// without a file (external modules, SLOs) are dropped.
+    pub fn subgraph_by_language(&self, language: Language) -> CodeGraph {
+        self.subgraph(|node| {
+            matches!(
+                self.owning_file(node),
+                Some(GraphNode::File { language: l, .. }) if *l == language
+            )
+        })
+    }
+
+    /// Extract the nodes that belong to files under the given path prefix
+    /// (e.g. `"services/billing/"`).
+    ///
+    /// Functions, classes and framework nodes follow their file; nodes
+    /// without a file (external modules, SLOs) are dropped.
+    pub fn subgraph_by_path_prefix(&self, prefix: &str) -> CodeGraph {
+        self.subgraph(|node| {
+            matches!(
+                self.owning_file(node),
+                Some(GraphNode::File { path, .. }) if path.starts_with(prefix)
+            )
+        })
+    }
+
+    /// The file node a node belongs to, if any.
+    fn owning_file(&self, node: &GraphNode) -> Option<&GraphNode> {
+        let file_id = node.file_id()?;
+        self.file_nodes.get(&file_id).map(|&idx| &self.graph[idx])
+    }
+
     /// Rebuild all lookup indexes from the graph.
     ///
     /// This must be called after deserializing a CodeGraph to restore
@@ -749,6 +1224,7 @@ impl CodeGraph {
             .or_else(|| path.strip_suffix(".js"))
             .or_else(|| path.strip_suffix(".go"))
             .or_else(|| path.strip_suffix(".rs"))
+            .or_else(|| path.strip_suffix(".java"))
         {
             let module_path = without_ext.replace('/', ".");
             module_to_file
@@ -791,30 +1267,71 @@ pub struct GraphStats {
     pub uses_library_edge_count: usize,
     /// Number of function-to-function call edges
     pub calls_edge_count: usize,
+    /// Highest number of incoming edges on any single node
+    pub max_fan_in: usize,
+    /// Highest number of outgoing edges on any single node
+    pub max_fan_out: usize,
     pub total_nodes: usize,
     pub total_edges: usize,
 }
 
+/// Options controlling which relationships `build_code_graph_with_calls` adds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphBuildOptions {
+    /// Resolve function calls (intra-file and through imports) into `Calls` edges.
+    pub include_calls: bool,
+}
+
+impl Default for GraphBuildOptions {
+    fn default() -> Self {
+        Self {
+            include_calls: true,
+        }
+    }
+}
+
 /// Build a CodeGraph from all file semantics.
 ///
 /// `sem_entries` is typically a snapshot of the session's semantics map:
 /// (FileId, Arc<SourceSemantics>).
+///
+/// This is equivalent to `build_code_graph_with_calls` with default options,
+/// i.e. call edges are included.
 pub fn build_code_graph(sem_entries: &[(FileId, Arc<SourceSemantics>)]) -> CodeGraph {
+    build_code_graph_with_calls(sem_entries, GraphBuildOptions::default())
+}
+
+/// Build a CodeGraph from all file semantics with explicit build options.
+///
+/// When `options.include_calls` is set, each `FunctionDef`'s call sites are
+/// resolved against function nodes in the same file first, then across files
+/// using the caller's imported symbols, producing `GraphEdgeKind::Calls` edges
+/// between `GraphNode::Function` nodes. Each nested function or closure also
+/// gets a `Contains` edge from the function that defines it, so its calls
+/// stay reachable from there without being reported as call sites.
+pub fn build_code_graph_with_calls(
+    sem_entries: &[(FileId, Arc<SourceSemantics>)],
+    options: GraphBuildOptions,
+) -> CodeGraph {
     let mut cg = CodeGraph::new();
 
     // First pass: create file nodes and collect path mappings with suffix indexes
     for (file_id, sem) in sem_entries {
-        let (path, language) = match sem.as_ref() {
-            SourceSemantics::Python(py) => (py.path.clone(), Language::Python),
-            SourceSemantics::Go(go) => (go.path.clone(), Language::Go),
-            SourceSemantics::Rust(rs) => (rs.path.clone(), Language::Rust),
-            SourceSemantics::Typescript(ts) => (ts.path.clone(), Language::Typescript),
+        let common: &dyn CommonSemantics = match sem.as_ref() {
+            SourceSemantics::Python(py) => py,
+            SourceSemantics::Go(go) => go,
+            SourceSemantics::Rust(rs) => rs,
+            SourceSemantics::Typescript(ts) => ts,
+            SourceSemantics::Java(java) => java,
         };
+        let path = common.file_path().to_string();
 
         let node_index = cg.graph.add_node(GraphNode::File {
             file_id: *file_id,
             path: path.clone(),
-            language,
+            language: common.language(),
+            line_count: common.line_count(),
+            function_count: common.functions().len(),
         });
 
         cg.file_nodes.insert(*file_id, node_index);
@@ -875,9 +1392,16 @@ pub fn build_code_graph(sem_entries: &[(FileId, Arc<SourceSemantics>)]) -> CodeG
                     add_express_nodes(&mut cg, file_node, *file_id, ts, express);
                 }
             }
+            SourceSemantics::Java(_java) => {
+                // Java doesn't have framework-specific nodes yet
+            }
         }
     }
 
+    if !options.include_calls {
+        return cg;
+    }
+
     // Third pass: add Calls edges between functions
     // First resolve intra-file calls (callee within the same file)
     for (file_id, sem) in sem_entries {
@@ -886,15 +1410,28 @@ pub fn build_code_graph(sem_entries: &[(FileId, Arc<SourceSemantics>)]) -> CodeG
             SourceSemantics::Go(go) => go.functions(),
             SourceSemantics::Rust(rs) => rs.functions(),
             SourceSemantics::Typescript(ts) => ts.functions(),
+            SourceSemantics::Java(java) => java.functions(),
         };
 
-        for func in functions {
+        let keys = function_keys(&functions);
+        for (func, key) in functions.iter().zip(&keys) {
             // Get the caller node
-            let caller_key = (*file_id, func.name.clone());
-            let Some(&caller_node) = cg.function_nodes.get(&caller_key) else {
+            let Some(&caller_node) = cg.function_nodes.get(&(*file_id, key.clone())) else {
                 continue;
             };
 
+            // A nested function or closure is defined by its parent rather
+            // than called from it, so link the two with a `Contains` edge
+            if func.parent_function.is_some()
+                && let Some((parent_key, _)) = key.rsplit_once('.')
+                && let Some(&parent_node) =
+                    cg.function_nodes.get(&(*file_id, parent_key.to_string()))
+                && cg.graph.find_edge(parent_node, caller_node).is_none()
+            {
+                cg.graph
+                    .add_edge(parent_node, caller_node, GraphEdgeKind::Contains);
+            }
+
             // Process each call site
             for call in &func.calls {
                 // Skip method calls on objects (e.g., db.add(), obj.method())
@@ -912,8 +1449,7 @@ pub fn build_code_graph(sem_entries: &[(FileId, Arc<SourceSemantics>)]) -> CodeG
                 }
 
                 // Try to find callee in the same file (intra-file call resolution)
-                let callee_key = (*file_id, call.callee.clone());
-                if let Some(&callee_node) = cg.function_nodes.get(&callee_key) {
+                if let Some(callee_node) = resolve_local_callee(&cg, *file_id, key, &call.callee) {
                     // Add Calls edge: caller -> callee
                     cg.graph
                         .add_edge(caller_node, callee_node, GraphEdgeKind::Calls);
@@ -945,6 +1481,7 @@ fn add_cross_file_call_edges(cg: &mut CodeGraph, sem_entries: &[(FileId, Arc<Sou
                 SourceSemantics::Go(go) => (go.path.clone(), go.imports()),
                 SourceSemantics::Rust(rs) => (rs.path.clone(), rs.imports()),
                 SourceSemantics::Typescript(ts) => (ts.path.clone(), ts.imports()),
+                SourceSemantics::Java(java) => (java.path.clone(), java.imports()),
             };
             (*file_id, (path, imports))
         })
@@ -957,6 +1494,7 @@ fn add_cross_file_call_edges(cg: &mut CodeGraph, sem_entries: &[(FileId, Arc<Sou
             SourceSemantics::Go(go) => go.functions(),
             SourceSemantics::Rust(rs) => rs.functions(),
             SourceSemantics::Typescript(ts) => ts.functions(),
+            SourceSemantics::Java(java) => java.functions(),
         };
 
         let empty_path = String::new();
@@ -966,10 +1504,10 @@ fn add_cross_file_call_edges(cg: &mut CodeGraph, sem_entries: &[(FileId, Arc<Sou
             .map(|(p, i)| (p.as_str(), i.as_slice()))
             .unwrap_or((empty_path.as_str(), empty_imports.as_slice()));
 
-        for func in functions {
+        let keys = function_keys(&functions);
+        for (func, key) in functions.iter().zip(&keys) {
             // Get the caller node
-            let caller_key = (*file_id, func.name.clone());
-            let Some(&caller_node) = cg.function_nodes.get(&caller_key) else {
+            let Some(&caller_node) = cg.function_nodes.get(&(*file_id, key.clone())) else {
                 continue;
             };
 
@@ -980,8 +1518,8 @@ fn add_cross_file_call_edges(cg: &mut CodeGraph, sem_entries: &[(FileId, Arc<Sou
                 // 1. It's a simple call (no receiver) AND the callee exists in this file
                 // 2. It's a self/this method call AND the callee exists in this file
                 let was_resolved_intra_file = {
-                    let callee_key = (*file_id, call.callee.clone());
-                    let callee_exists_in_file = cg.function_nodes.contains_key(&callee_key);
+                    let callee_exists_in_file =
+                        resolve_local_callee(cg, *file_id, key, &call.callee).is_some();
 
                     if let Some(ref receiver) = call.receiver {
                         // Method call - only resolved intra-file if self/this
@@ -1048,6 +1586,7 @@ fn resolve_call_through_imports(
                 if let Some(source_file_idx) = find_import_source_file_with_context(
                     cg,
                     &import.module_path,
+                    &import.source,
                     importing_file_path,
                 ) {
                     // Get the file_id from the source file node
@@ -1094,6 +1633,7 @@ fn resolve_call_through_imports(
                     if let Some(source_file_idx) = find_import_source_file_with_context(
                         cg,
                         &import.module_path,
+                        &import.source,
                         importing_file_path,
                     ) {
                         if let GraphNode::File { file_id, .. } = &cg.graph[source_file_idx] {
@@ -1129,6 +1669,7 @@ fn resolve_call_through_imports(
                     if let Some(source_file_idx) = find_import_source_file_with_context(
                         cg,
                         &submodule_path,
+                        &import.source,
                         importing_file_path,
                     ) {
                         if let GraphNode::File { file_id, .. } = &cg.graph[source_file_idx] {
@@ -1154,26 +1695,26 @@ fn resolve_call_through_imports(
 ///
 /// * `cg` - The code graph containing file nodes
 /// * `module_path` - The module path from the import (e.g., ".utils", "pkg.models")
+/// * `source` - How the import was classified (stdlib, external, local)
 /// * `importing_file_path` - The path of the file doing the import (for relative resolution)
 fn find_import_source_file_with_context(
     cg: &CodeGraph,
     module_path: &str,
+    source: &ImportSource,
     importing_file_path: &str,
 ) -> Option<NodeIndex> {
-    // Handle relative imports
-    if module_path.starts_with('.') {
-        // Use the same resolution logic as add_import_edges
-        let possible_paths = resolve_relative_import(importing_file_path, module_path);
-        for path in &possible_paths {
-            if let Some(idx) = cg.find_file_by_path(path) {
-                return Some(idx);
-            }
-        }
-        return None;
-    }
-
-    // For absolute imports, delegate to the existing function
-    find_import_source_file(cg, module_path)
+    // Use the same resolution logic as add_import_edges
+    let language = match cg
+        .path_to_file
+        .get(importing_file_path)
+        .map(|&idx| &cg.graph[idx])
+    {
+        Some(GraphNode::File { language, .. }) => *language,
+        _ => Language::Python,
+    };
+    resolve_import_targets(cg, language, importing_file_path, module_path, source)
+        .into_iter()
+        .next()
 }
 
 /// Find the file node that corresponds to an import module path.
@@ -1198,6 +1739,7 @@ fn find_import_source_file(cg: &CodeGraph, module_path: &str) -> Option<NodeInde
         format!("{}.js", module_as_file),
         format!("{}.go", module_as_file),
         format!("{}.rs", module_as_file),
+        format!("{}.java", module_as_file),
         module_as_file.clone(),
     ];
 
@@ -1217,12 +1759,13 @@ fn add_import_edges(
     _file_id: FileId,
     sem: &Arc<SourceSemantics>,
 ) {
-    // Get the file path to resolve relative imports
-    let file_path = match sem.as_ref() {
-        SourceSemantics::Python(py) => py.path.clone(),
-        SourceSemantics::Go(go) => go.path.clone(),
-        SourceSemantics::Rust(rs) => rs.path.clone(),
-        SourceSemantics::Typescript(ts) => ts.path.clone(),
+    // Get the file path and language to resolve relative imports
+    let (file_path, language) = match sem.as_ref() {
+        SourceSemantics::Python(py) => (py.path.clone(), Language::Python),
+        SourceSemantics::Go(go) => (go.path.clone(), Language::Go),
+        SourceSemantics::Rust(rs) => (rs.path.clone(), Language::Rust),
+        SourceSemantics::Typescript(ts) => (ts.path.clone(), ts.language),
+        SourceSemantics::Java(java) => (java.path.clone(), Language::Java),
     };
 
     // Get imports via CommonSemantics trait
@@ -1231,50 +1774,30 @@ fn add_import_edges(
         SourceSemantics::Go(go) => go.imports(),
         SourceSemantics::Rust(rs) => rs.imports(),
         SourceSemantics::Typescript(ts) => ts.imports(),
+        SourceSemantics::Java(java) => java.imports(),
     };
 
     for import in imports {
-        // First, try to find the imported module as a file in our graph
-        // This handles both:
-        // - Explicit local/relative imports (import.is_local() == true)
-        // - Absolute imports to local packages (e.g., "from myapp.task import Task")
-        //
-        // We try multiple path patterns to find the file:
-        // 1. Exact module path (e.g., "reliably_app.task" -> "reliably_app/task.py")
-        // 2. Module path as directory init (e.g., "reliably_app" -> "reliably_app/__init__.py")
-        // 3. Relative imports (e.g., ".utils" resolved relative to the importing file's directory)
-
-        let possible_paths = if import.module_path.starts_with('.') {
-            // Handle relative imports (Python-style: from .utils import foo)
-            // The module path starts with one or more dots indicating relative level
-            resolve_relative_import(&file_path, &import.module_path)
-        } else {
-            // Absolute imports
-            let module_as_file = import.module_path.replace('.', "/");
-            vec![
-                format!("{}.py", module_as_file),
-                format!("{}/__init__.py", module_as_file),
-                format!("{}.ts", module_as_file),
-                format!("{}.tsx", module_as_file),
-                format!("{}.js", module_as_file),
-                module_as_file.clone(),
-            ]
-        };
-
-        let mut found_local_file = false;
-        for path in &possible_paths {
-            if let Some(target_idx) = cg.find_file_by_path(path) {
-                // Found as a local file - create import edge
-                if import.items.is_empty() {
-                    cg.graph
-                        .add_edge(file_node, target_idx, GraphEdgeKind::Imports);
-                } else {
-                    let items: Vec<String> = import.items.iter().map(|i| i.name.clone()).collect();
-                    cg.graph
-                        .add_edge(file_node, target_idx, GraphEdgeKind::ImportsFrom { items });
-                }
-                found_local_file = true;
-                break;
+        // First, try to find the imported module as a file (or, for Go, the
+        // files of a package directory) in our graph. This handles both
+        // explicit relative imports and absolute imports of local packages
+        // (e.g., "from myapp.task import Task", "crate::db::Pool").
+        let targets = resolve_import_targets(
+            cg,
+            language,
+            &file_path,
+            &import.module_path,
+            &import.source,
+        );
+        let found_local_file = !targets.is_empty();
+        for target_idx in targets {
+            if import.items.is_empty() {
+                cg.graph
+                    .add_edge(file_node, target_idx, GraphEdgeKind::Imports);
+            } else {
+                let items: Vec<String> = import.items.iter().map(|i| i.name.clone()).collect();
+                cg.graph
+                    .add_edge(file_node, target_idx, GraphEdgeKind::ImportsFrom { items });
             }
         }
 
@@ -1352,6 +1875,208 @@ fn resolve_relative_import(importing_file: &str, module_path: &str) -> Vec<Strin
     ]
 }
 
+/// Whether an edge records a dependency on another file or library.
+fn is_import_edge(kind: &GraphEdgeKind) -> bool {
+    matches!(
+        kind,
+        GraphEdgeKind::Imports | GraphEdgeKind::ImportsFrom { .. } | GraphEdgeKind::UsesLibrary
+    )
+}
+
+/// Resolve an import to the file nodes it refers to.
+///
+/// Resolution follows each language's module rules:
+/// - Python: dotted paths map to `.py` files or package `__init__.py`, with
+///   leading dots resolved relative to the importing file
+/// - TypeScript/JavaScript: `./` and `../` paths are resolved against the
+///   importing file's directory, and local paths resolved from a path alias
+///   against the project root, trying the usual extensions and `index` files
+/// - Go: a package path under the project's own module (classified local from
+///   `go.mod`) maps to every file in the matching directory; standard-library
+///   and third-party packages never resolve to project files
+/// - Rust: `crate::`, `self::` and `super::` paths map to `foo.rs` or `foo/mod.rs`
+///
+/// An empty result means the import is external (or simply not in the graph).
+fn resolve_import_targets(
+    cg: &CodeGraph,
+    language: Language,
+    importing_file: &str,
+    module_path: &str,
+    source: &ImportSource,
+) -> Vec<NodeIndex> {
+    let possible_paths = match language {
+        Language::Go if *source == ImportSource::Local => return go_package_files(cg, module_path),
+        Language::Go => return Vec::new(),
+        Language::Rust => rust_module_paths(importing_file, module_path),
+        Language::Typescript | Language::Javascript
+            if module_path.starts_with("./")
+                || module_path.starts_with("../")
+                || *source == ImportSource::Local =>
+        {
+            typescript_module_paths(importing_file, module_path)
+        }
+        _ if module_path.starts_with('.') => resolve_relative_import(importing_file, module_path),
+        _ => {
+            return find_import_source_file(cg, module_path)
+                .into_iter()
+                .collect();
+        }
+    };
+
+    possible_paths
+        .iter()
+        .find_map(|path| cg.find_file_by_path(path))
+        .into_iter()
+        .collect()
+}
+
+/// Join a relative path onto a directory, resolving `.` and `..` segments.
+fn join_relative_path(dir: &str, relative: &str) -> String {
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for segment in relative.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(segment),
+        }
+    }
+    parts.join("/")
+}
+
+/// Candidate files for a TypeScript/JavaScript import.
+///
+/// `./` and `../` paths are relative to the importing file. Any other local
+/// path is one a `tsconfig.json` alias resolved, relative to the project root.
+fn typescript_module_paths(importing_file: &str, module_path: &str) -> Vec<String> {
+    let base = if module_path.starts_with("./") || module_path.starts_with("../") {
+        let importing_dir = importing_file.rsplit_once('/').map_or("", |(dir, _)| dir);
+        join_relative_path(importing_dir, module_path)
+    } else {
+        join_relative_path("", module_path)
+    };
+
+    let mut paths = vec![base.clone()];
+    for ext in [".ts", ".tsx", ".js", ".jsx"] {
+        paths.push(format!("{}{}", base, ext));
+    }
+    for index in ["index.ts", "index.tsx", "index.js"] {
+        paths.push(format!("{}/{}", base, index));
+    }
+    paths
+}
+
+/// Candidate files for a crate-local Rust `use` path.
+///
+/// Trailing segments may name items rather than modules (`crate::db::Pool`),
+/// so progressively shorter module paths are tried, longest first.
+fn rust_module_paths(importing_file: &str, module_path: &str) -> Vec<String> {
+    let module_path = module_path.split("::{").next().unwrap_or(module_path);
+    let module_path = module_path.split(" as ").next().unwrap_or(module_path);
+    let mut segments: Vec<&str> = module_path.split("::").map(str::trim).collect();
+
+    let (dir, file) = importing_file
+        .rsplit_once('/')
+        .unwrap_or(("", importing_file));
+    let crate_root = match importing_file.rfind("src/") {
+        Some(idx) => importing_file[..idx + 3].to_string(),
+        None => dir.to_string(),
+    };
+    // Directory holding the child modules of the importing file's module
+    let mut base = if matches!(file, "mod.rs" | "lib.rs" | "main.rs") {
+        dir.to_string()
+    } else {
+        importing_file.trim_end_matches(".rs").to_string()
+    };
+
+    match segments.first().copied() {
+        Some("crate") => {
+            segments.remove(0);
+            base = crate_root.clone();
+        }
+        Some("self") => {
+            segments.remove(0);
+        }
+        Some("super") => {
+            while segments.first() == Some(&"super") {
+                segments.remove(0);
+                base = base
+                    .rsplit_once('/')
+                    .map_or(String::new(), |(p, _)| p.to_string());
+            }
+        }
+        _ => return Vec::new(),
+    }
+
+    let join = |rest: &[&str]| {
+        std::iter::once(base.as_str())
+            .chain(rest.iter().copied())
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+
+    let mut paths = Vec::new();
+    for len in (1..=segments.len()).rev() {
+        let module = join(&segments[..len]);
+        paths.push(format!("{}.rs", module));
+        paths.push(format!("{}/mod.rs", module));
+    }
+    if base == crate_root {
+        let root = join(&[]);
+        for entry in ["lib.rs", "main.rs"] {
+            paths.push(if root.is_empty() {
+                entry.to_string()
+            } else {
+                format!("{}/{}", root, entry)
+            });
+        }
+    } else {
+        paths.push(format!("{}.rs", base));
+        paths.push(format!("{}/mod.rs", base));
+    }
+    paths
+}
+
+/// Files of the Go package an import path refers to.
+///
+/// A package is a directory; the import path matches when it ends with the
+/// directory path on a segment boundary (e.g. `github.com/org/app/internal/db`
+/// matches `internal/db`). The longest matching directory wins.
+fn go_package_files(cg: &CodeGraph, import_path: &str) -> Vec<NodeIndex> {
+    let mut best: Option<(usize, Vec<NodeIndex>)> = None;
+    for &idx in cg.file_nodes.values() {
+        let GraphNode::File {
+            path,
+            language: Language::Go,
+            ..
+        } = &cg.graph[idx]
+        else {
+            continue;
+        };
+        let Some((dir, _)) = path.rsplit_once('/') else {
+            continue;
+        };
+        let matches = import_path == dir
+            || import_path
+                .strip_suffix(dir)
+                .is_some_and(|prefix| prefix.ends_with('/'));
+        if !matches {
+            continue;
+        }
+        match &mut best {
+            Some((len, files)) if *len == dir.len() => files.push(idx),
+            Some((len, _)) if *len > dir.len() => {}
+            _ => best = Some((dir.len(), vec![idx])),
+        }
+    }
+
+    let mut files = best.map(|(_, files)| files).unwrap_or_default();
+    files.sort();
+    files
+}
+
 /// Add function nodes from a file
 ///
 /// For Python files with FastAPI and TypeScript files with Express.js, route handlers
@@ -1369,6 +2094,7 @@ fn add_function_nodes(
         SourceSemantics::Go(go) => go.functions(),
         SourceSemantics::Rust(rs) => rs.functions(),
         SourceSemantics::Typescript(ts) => ts.functions(),
+        SourceSemantics::Java(java) => java.functions(),
     };
 
     // Collect framework route handler names to skip
@@ -1414,22 +2140,27 @@ fn add_function_nodes(
             // Rust doesn't have framework route handlers yet
             std::collections::HashSet::new()
         }
+        SourceSemantics::Java(_java) => {
+            // Java doesn't have framework route handlers yet
+            std::collections::HashSet::new()
+        }
     };
 
-    for func in functions {
+    let keys = function_keys(&functions);
+    for (func, key) in functions.iter().zip(keys) {
         // Skip framework route handlers - they're added by framework-specific functions with HTTP metadata
-        if handler_names_to_skip.contains(func.name.as_str()) {
+        if func.parent_function.is_none() && handler_names_to_skip.contains(func.name.as_str()) {
             continue;
         }
 
         let qualified_name = match &func.class_name {
-            Some(class) => format!("{}.{}", class, func.name),
-            None => func.name.clone(),
+            Some(class) => format!("{}.{}", class, key),
+            None => key.clone(),
         };
 
         let func_node = cg.graph.add_node(GraphNode::Function {
             file_id,
-            name: func.name.clone(),
+            name: key.clone(),
             qualified_name: qualified_name.clone(),
             is_async: func.is_async,
             is_handler: func.is_route_handler(),
@@ -1444,11 +2175,74 @@ fn add_function_nodes(
             .add_edge(file_node, func_node, GraphEdgeKind::Contains);
 
         // Store for lookup
-        cg.function_nodes
-            .insert((file_id, func.name.clone()), func_node);
+        cg.function_nodes.insert((file_id, key), func_node);
     }
 }
 
+/// Graph keys of a file's functions, in the order of `functions`.
+///
+/// Top-level functions and methods are keyed by name. Nested functions are
+/// scoped under the key of their enclosing function (`outer.inner`), and
+/// anonymous closures also carry their start byte (`outer.<lambda>@57`), so
+/// every inner `def wrapper` and every callback gets its own node.
+fn function_keys(functions: &[FunctionDef]) -> Vec<String> {
+    let mut order: Vec<usize> = (0..functions.len()).collect();
+    // Enclosing functions start earlier, or at the same byte and end later
+    order.sort_by_key(|&i| {
+        (
+            functions[i].start_byte,
+            std::cmp::Reverse(functions[i].end_byte),
+        )
+    });
+
+    let mut keys = vec![String::new(); functions.len()];
+    for (pos, &i) in order.iter().enumerate() {
+        let func = &functions[i];
+        if func.parent_function.is_none() {
+            keys[i] = func.name.clone();
+            continue;
+        }
+        let parent = order[..pos].iter().rev().find(|&&j| {
+            functions[j].start_byte <= func.start_byte && func.end_byte <= functions[j].end_byte
+        });
+        let name = if func.name.starts_with('<') {
+            format!("{}@{}", func.name, func.start_byte)
+        } else {
+            func.name.clone()
+        };
+        keys[i] = match parent {
+            Some(&j) => format!("{}.{}", keys[j], name),
+            None => name,
+        };
+    }
+    keys
+}
+
+/// Find the function a call by bare name refers to, from the caller's scope outwards.
+///
+/// A call inside `outer.<lambda>@57` to `helper` tries `outer.<lambda>@57.helper`,
+/// then `outer.helper`, then the top-level `helper`.
+fn resolve_local_callee(
+    cg: &CodeGraph,
+    file_id: FileId,
+    caller_key: &str,
+    callee: &str,
+) -> Option<NodeIndex> {
+    let mut scope = Some(caller_key);
+    while let Some(current) = scope {
+        if let Some(&node) = cg
+            .function_nodes
+            .get(&(file_id, format!("{current}.{callee}")))
+        {
+            return Some(node);
+        }
+        scope = current.rsplit_once('.').map(|(parent, _)| parent);
+    }
+    cg.function_nodes
+        .get(&(file_id, callee.to_string()))
+        .copied()
+}
+
 /// Categorize a module based on its name
 fn categorize_module(module_path: &str) -> ModuleCategory {
     let path_lower = module_path.to_lowercase();
@@ -1947,6 +2741,8 @@ mod tests {
             file_id: FileId(1),
             path: "test.py".to_string(),
             language: Language::Python,
+            line_count: 0,
+            function_count: 0,
         };
         let debug_str = format!("{:?}", node);
         assert!(debug_str.contains("File"));
@@ -2034,6 +2830,8 @@ mod tests {
             file_id: FileId(1),
             path: "src/main.py".to_string(),
             language: Language::Python,
+            line_count: 0,
+            function_count: 0,
         };
         assert_eq!(file.display_name(), "src/main.py");
 
@@ -2063,6 +2861,8 @@ mod tests {
             file_id: FileId(1),
             path: "test.py".to_string(),
             language: Language::Python,
+            line_count: 0,
+            function_count: 0,
         };
         assert_eq!(file.file_id(), Some(FileId(1)));
 
@@ -2079,6 +2879,8 @@ mod tests {
             file_id: FileId(1),
             path: "test.py".to_string(),
             language: Language::Python,
+            line_count: 0,
+            function_count: 0,
         };
         assert!(file.is_file());
 
@@ -2705,6 +3507,8 @@ def bar():
             file_id,
             path: "test.py".to_string(),
             language: Language::Python,
+            line_count: 0,
+            function_count: 0,
         });
 
         let func_a = cg.graph.add_node(GraphNode::Function {
@@ -2771,6 +3575,8 @@ def bar():
             file_id,
             path: "test.py".to_string(),
             language: Language::Python,
+            line_count: 0,
+            function_count: 0,
         });
         cg.file_nodes.insert(file_id, file_node);
         cg.path_to_file.insert("test.py".to_string(), file_node);
@@ -2848,6 +3654,8 @@ def bar():
             file_id,
             path: "real.py".to_string(),
             language: Language::Python,
+            line_count: 0,
+            function_count: 0,
         });
 
         // Rebuild should clear stale data and add real data
@@ -2947,6 +3755,242 @@ def main():
         assert!(stats.import_edge_count >= 1);
     }
 
+    fn cross_file_call_entries() -> Vec<(FileId, Arc<SourceSemantics>)> {
+        let b_src = r#"
+def compute(x):
+    return x * 2
+"#;
+        let a_src = r#"
+from b import compute
+
+def run():
+    return compute(21)
+"#;
+        vec![
+            parse_python_with_id("b.py", b_src, 1),
+            parse_python_with_id("a.py", a_src, 2),
+        ]
+    }
+
+    #[test]
+    fn build_code_graph_with_calls_adds_cross_file_call_edge() {
+        let sem_entries = cross_file_call_entries();
+        let cg = build_code_graph_with_calls(&sem_entries, GraphBuildOptions::default());
+
+        let run = cg.function_nodes[&(FileId(2), "run".to_string())];
+        let compute = cg.function_nodes[&(FileId(1), "compute".to_string())];
+
+        assert!(
+            cg.graph
+                .edges_connecting(run, compute)
+                .any(|e| *e.weight() == GraphEdgeKind::Calls),
+            "a.run should have a Calls edge to b.compute"
+        );
+    }
+
+    #[test]
+    fn build_code_graph_with_calls_disabled_skips_call_edges() {
+        let sem_entries = cross_file_call_entries();
+        let options = GraphBuildOptions {
+            include_calls: false,
+        };
+        let cg = build_code_graph_with_calls(&sem_entries, options);
+
+        let stats = cg.stats();
+        assert_eq!(stats.calls_edge_count, 0);
+        assert!(stats.import_edge_count >= 1);
+        assert_eq!(stats.function_count, 2);
+    }
+
+    #[test]
+    fn build_code_graph_matches_default_options() {
+        let sem_entries = cross_file_call_entries();
+        let default_cg = build_code_graph(&sem_entries);
+        let explicit_cg = build_code_graph_with_calls(&sem_entries, GraphBuildOptions::default());
+
+        assert_eq!(
+            default_cg.stats().calls_edge_count,
+            explicit_cg.stats().calls_edge_count
+        );
+        assert_eq!(
+            default_cg.stats().total_edges,
+            explicit_cg.stats().total_edges
+        );
+    }
+
+    // ==================== Degree Tests ====================
+
+    fn add_test_file(cg: &mut CodeGraph, id: u64, path: &str) -> NodeIndex {
+        cg.graph.add_node(GraphNode::File {
+            file_id: FileId(id),
+            path: path.to_string(),
+            language: Language::Python,
+            line_count: 0,
+            function_count: 0,
+        })
+    }
+
+    #[test]
+    fn node_degrees_hand_built_graph() {
+        // a -> core, b -> core, c -> core, core -> util
+        let mut cg = CodeGraph::new();
+        let a = add_test_file(&mut cg, 1, "a.py");
+        let b = add_test_file(&mut cg, 2, "b.py");
+        let c = add_test_file(&mut cg, 3, "c.py");
+        let core = add_test_file(&mut cg, 4, "core.py");
+        let util = add_test_file(&mut cg, 5, "util.py");
+        for importer in [a, b, c] {
+            cg.graph.add_edge(importer, core, GraphEdgeKind::Imports);
+        }
+        cg.graph.add_edge(core, util, GraphEdgeKind::Imports);
+
+        let degrees = cg.node_degrees();
+        assert_eq!(degrees.len(), 5);
+        assert_eq!(degrees[&cg.graph[core]], (3, 1));
+        assert_eq!(degrees[&cg.graph[a]], (0, 1));
+        assert_eq!(degrees[&cg.graph[util]], (1, 0));
+
+        // Lookups match on identity, not on descriptive fields.
+        let core_again = GraphNode::File {
+            file_id: FileId(4),
+            path: "core.py".to_string(),
+            language: Language::Python,
+            line_count: 120,
+            function_count: 7,
+        };
+        assert_eq!(degrees[&core_again], (3, 1));
+
+        let stats = cg.stats();
+        assert_eq!(stats.max_fan_in, 3);
+        assert_eq!(stats.max_fan_out, 1);
+    }
+
+    #[test]
+    fn node_degrees_empty_graph() {
+        let cg = CodeGraph::new();
+        assert!(cg.node_degrees().is_empty());
+
+        let stats = cg.stats();
+        assert_eq!(stats.max_fan_in, 0);
+        assert_eq!(stats.max_fan_out, 0);
+    }
+
+    // ==================== Cycle Detection Tests ====================
+
+    fn cycle_paths(cycle: &[GraphNode]) -> Vec<String> {
+        cycle.iter().map(|n| n.display_name()).collect()
+    }
+
+    #[test]
+    fn find_cycles_detects_three_file_import_cycle() {
+        let sem_entries = vec![
+            parse_python_with_id("a.py", "import b\n", 1),
+            parse_python_with_id("b.py", "import c\n", 2),
+            parse_python_with_id("c.py", "import a\n", 3),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        let cycles = cg.find_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycle_paths(&cycles[0]), vec!["a.py", "b.py", "c.py"]);
+        assert!(cycles[0].iter().all(|n| n.is_file()));
+    }
+
+    #[test]
+    fn find_cycles_returns_none_for_dag() {
+        let sem_entries = vec![
+            parse_python_with_id("a.py", "import b\nimport c\n", 1),
+            parse_python_with_id("b.py", "import c\n", 2),
+            parse_python_with_id("c.py", "import os\n", 3),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        assert!(cg.find_cycles().is_empty());
+    }
+
+    #[test]
+    fn find_cycles_reports_self_import() {
+        let mut cg = CodeGraph::new();
+        let file = cg.graph.add_node(GraphNode::File {
+            file_id: FileId(1),
+            path: "loop.py".to_string(),
+            language: Language::Python,
+            line_count: 0,
+            function_count: 0,
+        });
+        cg.graph.add_edge(file, file, GraphEdgeKind::Imports);
+
+        let cycles = cg.find_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycle_paths(&cycles[0]), vec!["loop.py"]);
+    }
+
+    #[test]
+    fn find_cycles_ignores_call_recursion() {
+        let src = r#"
+def ping():
+    pong()
+
+def pong():
+    ping()
+"#;
+        let sem_entries = vec![parse_python_with_id("rec.py", src, 1)];
+        let cg = build_code_graph(&sem_entries);
+
+        assert!(cg.stats().calls_edge_count >= 2);
+        assert!(cg.find_cycles().is_empty());
+    }
+
+    // ==================== Topological Order Tests ====================
+
+    #[test]
+    fn topological_order_linear_chain() {
+        // app imports service, service imports db
+        let sem_entries = vec![
+            parse_python_with_id("app.py", "import service\n", 1),
+            parse_python_with_id("service.py", "import db\n", 2),
+            parse_python_with_id("db.py", "x = 1\n", 3),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        let order = cg.topological_order().expect("chain should be a DAG");
+        let paths: Vec<String> = order.iter().map(|n| n.display_name()).collect();
+        assert_eq!(paths, vec!["db.py", "service.py", "app.py"]);
+    }
+
+    #[test]
+    fn topological_order_only_contains_files() {
+        let src = r#"
+import requests
+
+def handler():
+    pass
+"#;
+        let sem_entries = vec![parse_python_with_id("main.py", src, 1)];
+        let cg = build_code_graph(&sem_entries);
+
+        let order = cg.topological_order().unwrap();
+        assert_eq!(order.len(), 1);
+        assert!(order[0].is_file());
+    }
+
+    #[test]
+    fn topological_order_cycle_returns_error() {
+        let sem_entries = vec![
+            parse_python_with_id("a.py", "import b\n", 1),
+            parse_python_with_id("b.py", "import a\n", 2),
+            parse_python_with_id("c.py", "import a\n", 3),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        match cg.topological_order() {
+            Err(GraphError::Cycle { cycle }) => {
+                assert_eq!(cycle, vec!["a.py", "b.py"]);
+            }
+            other => panic!("expected cycle error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn find_import_source_file_returns_none_for_external() {
         let src = "x = 1";
@@ -3251,6 +4295,91 @@ def main():
         assert!(found_calls_edge, "Expected Calls edge from main() to add()");
     }
 
+    #[test]
+    fn closures_get_their_own_function_nodes() {
+        let source = "\
+def outer(items):
+    items.sort(key=lambda i: helper())
+    return list(map(lambda j: other(), items))
+
+def helper():
+    pass
+
+def other():
+    pass
+
+def retry(fn):
+    def wrapper():
+        return fn()
+    return wrapper
+
+def timed(fn):
+    def wrapper():
+        return fn()
+    return wrapper
+";
+        let (file_id, sem) = parse_python_with_id("app.py", source, 1);
+        let cg = build_code_graph(&[(file_id, sem)]);
+
+        let first = format!("outer.<lambda>@{}", source.find("lambda i").unwrap());
+        let second = format!("outer.<lambda>@{}", source.find("lambda j").unwrap());
+        let node = |key: &str| {
+            *cg.function_nodes
+                .get(&(file_id, key.to_string()))
+                .unwrap_or_else(|| panic!("missing function node {key}"))
+        };
+        let calls = |from: NodeIndex, to: NodeIndex| {
+            cg.graph
+                .edges_connecting(from, to)
+                .any(|e| *e.weight() == GraphEdgeKind::Calls)
+        };
+
+        assert_ne!(node(&first), node(&second));
+        assert!(calls(node(&first), node("helper")));
+        assert!(!calls(node(&first), node("other")));
+        assert!(calls(node(&second), node("other")));
+        assert!(!calls(node(&second), node("helper")));
+        assert_ne!(node("retry.wrapper"), node("timed.wrapper"));
+    }
+
+    #[test]
+    fn closure_calls_stay_reachable_from_parent() {
+        let source = "\
+def outer(items):
+    items.sort(key=lambda i: helper())
+    return list(map(lambda j: other(), items))
+
+def helper():
+    pass
+
+def other():
+    pass
+";
+        let (file_id, sem) = parse_python_with_id("app.py", source, 1);
+        let cg = build_code_graph(&[(file_id, sem)]);
+
+        let node = |key: &str| cg.function_nodes[&(file_id, key.to_string())];
+        // Function-to-function edges only; file Contains edges are skipped
+        let between_functions = EdgeFiltered::from_fn(&cg.graph, |e| {
+            matches!(cg.graph[e.source()], GraphNode::Function { .. })
+                && matches!(e.weight(), GraphEdgeKind::Calls | GraphEdgeKind::Contains)
+        });
+        let reaches = |from: &str, to: &str| {
+            petgraph::algo::has_path_connecting(&between_functions, node(from), node(to), None)
+        };
+
+        let lambda = format!("outer.<lambda>@{}", source.find("lambda i").unwrap());
+        let parent_edges: Vec<_> = cg
+            .graph
+            .edges_connecting(node("outer"), node(&lambda))
+            .map(|e| e.weight().clone())
+            .collect();
+        assert_eq!(parent_edges, vec![GraphEdgeKind::Contains]);
+        assert!(reaches("outer", "helper"));
+        assert!(reaches("outer", "other"));
+        assert!(!reaches("helper", "other"));
+    }
+
     #[test]
     fn find_import_source_file_with_context_resolves_relative() {
         // Set up a graph with a utils.py file
@@ -3262,7 +4391,8 @@ def main():
         assert!(find_import_source_file(&cg, ".utils").is_none());
 
         // With context, relative import should succeed
-        let result = find_import_source_file_with_context(&cg, ".utils", "app.py");
+        let result =
+            find_import_source_file_with_context(&cg, ".utils", &ImportSource::Local, "app.py");
         assert!(
             result.is_some(),
             "Expected to find utils.py via relative import from app.py"
@@ -3388,4 +4518,361 @@ def process(db):
             calls_count
         );
     }
+
+    // ==================== Import Resolution Tests ====================
+
+    fn build_with_id(
+        language: Language,
+        path: &str,
+        source: &str,
+        id: u64,
+    ) -> (FileId, Arc<SourceSemantics>) {
+        let sf = SourceFile {
+            path: path.to_string(),
+            language,
+            content: source.to_string(),
+        };
+        let parsed = crate::parse::parse_source(FileId(id), &sf).expect("parsing should succeed");
+        let sem = crate::semantics::build_source_semantics(&parsed)
+            .expect("semantics building should succeed")
+            .expect("language should be supported");
+        (FileId(id), Arc::new(sem))
+    }
+
+    #[test]
+    fn python_package_import_resolves_to_file() {
+        let sem_entries = vec![
+            parse_python_with_id(
+                "pkg/a.py",
+                "from pkg.b import x\nfrom .b import y\nimport requests\n",
+                1,
+            ),
+            parse_python_with_id("pkg/b.py", "x = 1\ny = 2\n", 2),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        assert_eq!(cg.get_imports(FileId(1)), vec![FileId(2), FileId(2)]);
+        assert_eq!(cg.get_external_dependencies(FileId(1)), vec!["requests"]);
+    }
+
+    #[test]
+    fn typescript_relative_import_resolves_with_extension_and_index() {
+        let sem_entries = vec![
+            build_with_id(
+                Language::Typescript,
+                "src/app/main.ts",
+                "import { util } from '../lib/util';\nimport * as ui from './ui';\nimport express from 'express';\n",
+                1,
+            ),
+            build_with_id(
+                Language::Typescript,
+                "src/lib/util.ts",
+                "export const util = 1;\n",
+                2,
+            ),
+            build_with_id(
+                Language::Typescript,
+                "src/app/ui/index.tsx",
+                "export const x = 1;\n",
+                3,
+            ),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        let mut imports = cg.get_imports(FileId(1));
+        imports.sort_by_key(|id| id.0);
+        assert_eq!(imports, vec![FileId(2), FileId(3)]);
+        assert_eq!(cg.get_external_dependencies(FileId(1)), vec!["express"]);
+    }
+
+    #[test]
+    fn typescript_relative_import_does_not_fall_back_to_project_root() {
+        let sem_entries = vec![
+            build_with_id(
+                Language::Typescript,
+                "src/app/main.ts",
+                "import { util } from './util';\n",
+                1,
+            ),
+            build_with_id(
+                Language::Typescript,
+                "util.ts",
+                "export const util = 1;\n",
+                2,
+            ),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        assert!(cg.get_imports(FileId(1)).is_empty());
+    }
+
+    #[test]
+    fn typescript_path_alias_resolves_against_project_root() {
+        let sf = SourceFile {
+            path: "src/app/main.ts".to_string(),
+            language: Language::Typescript,
+            content: "import { util } from '@lib/util';\n".to_string(),
+        };
+        let parsed = parse_typescript_file(FileId(1), &sf).expect("parsing should succeed");
+        let aliases =
+            std::collections::BTreeMap::from([("@lib/*".to_string(), "./src/lib/*".to_string())]);
+        let sem = crate::semantics::typescript::build_typescript_semantics_with_aliases(
+            &parsed, &aliases,
+        )
+        .expect("semantics building should succeed");
+
+        let sem_entries = vec![
+            (FileId(1), Arc::new(SourceSemantics::Typescript(sem))),
+            build_with_id(
+                Language::Typescript,
+                "src/lib/util.ts",
+                "export const util = 1;\n",
+                2,
+            ),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        assert_eq!(cg.get_imports(FileId(1)), vec![FileId(2)]);
+    }
+
+    /// Build a Go file belonging to the `github.com/org/app` module.
+    fn build_go_in_module(path: &str, source: &str, id: u64) -> (FileId, Arc<SourceSemantics>) {
+        let sf = SourceFile {
+            path: path.to_string(),
+            language: Language::Go,
+            content: source.to_string(),
+        };
+        let parsed = crate::parse::parse_source(FileId(id), &sf).expect("parsing should succeed");
+        let sem =
+            crate::semantics::go::build_go_semantics_with_module(&parsed, "github.com/org/app")
+                .expect("semantics building should succeed");
+        (FileId(id), Arc::new(SourceSemantics::Go(sem)))
+    }
+
+    #[test]
+    fn go_package_import_resolves_to_directory_files() {
+        let sem_entries = vec![
+            build_go_in_module(
+                "cmd/main.go",
+                "package main\n\nimport (\n\t\"fmt\"\n\t\"github.com/org/app/internal/db\"\n)\n",
+                1,
+            ),
+            build_go_in_module("internal/db/conn.go", "package db\n", 2),
+            build_go_in_module("internal/db/query.go", "package db\n", 3),
+            build_go_in_module("internal/api/api.go", "package api\n", 4),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        let mut imports = cg.get_imports(FileId(1));
+        imports.sort_by_key(|id| id.0);
+        assert_eq!(imports, vec![FileId(2), FileId(3)]);
+    }
+
+    #[test]
+    fn go_stdlib_and_third_party_imports_never_resolve_to_local_files() {
+        let sem_entries = vec![
+            build_go_in_module(
+                "cmd/main.go",
+                "package main\n\nimport (\n\t\"log\"\n\t\"github.com/other/lib/db\"\n)\n",
+                1,
+            ),
+            build_go_in_module("log/log.go", "package log\n", 2),
+            build_go_in_module("db/db.go", "package db\n", 3),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        assert!(cg.get_imports(FileId(1)).is_empty());
+        let external = cg.get_external_dependencies(FileId(1));
+        assert_eq!(external.len(), 2);
+        assert!(external.contains(&"log".to_string()));
+    }
+
+    #[test]
+    fn rust_crate_paths_resolve_to_module_files() {
+        let sem_entries = vec![
+            build_with_id(
+                Language::Rust,
+                "src/handlers/user.rs",
+                "use crate::db::Pool;\nuse super::auth;\nuse tokio::sync::Mutex;\n",
+                1,
+            ),
+            build_with_id(Language::Rust, "src/db/mod.rs", "pub struct Pool;\n", 2),
+            build_with_id(
+                Language::Rust,
+                "src/handlers/auth.rs",
+                "pub fn check() {}\n",
+                3,
+            ),
+        ];
+        let cg = build_code_graph(&sem_entries);
+
+        assert_eq!(cg.get_imports(FileId(1)), vec![FileId(3), FileId(2)]);
+        assert_eq!(cg.get_external_dependencies(FileId(1)).len(), 1);
+    }
+
+    #[test]
+    fn file_nodes_carry_language_and_size_metadata() {
+        let sem_entries = vec![parse_python_with_id(
+            "app.py",
+            "import os\n\ndef a():\n    pass\n\ndef b():\n    pass\n",
+            1,
+        )];
+        let cg = build_code_graph(&sem_entries);
+
+        let node = &cg.graph[cg.file_nodes[&FileId(1)]];
+        let GraphNode::File {
+            language,
+            line_count,
+            function_count,
+            ..
+        } = node
+        else {
+            panic!("expected a file node");
+        };
+        assert_eq!(*language, Language::Python);
+        assert_eq!(*line_count, 7);
+        assert_eq!(*function_count, 2);
+
+        let json = serde_json::to_value(node).unwrap();
+        assert_eq!(json["File"]["language"], "python");
+        assert_eq!(json["File"]["line_count"], 7);
+        assert_eq!(json["File"]["function_count"], 2);
+    }
+
+    #[test]
+    fn subgraph_by_language_drops_cross_language_edges() {
+        let sem_entries = vec![
+            parse_python_with_id(
+                "api/app.py",
+                "from api.util import f\n\ndef g():\n    f()\n",
+                1,
+            ),
+            parse_python_with_id("api/util.py", "def f():\n    pass\n", 2),
+            build_with_id(
+                Language::Typescript,
+                "web/main.ts",
+                "import express from 'express';\n",
+                3,
+            ),
+        ];
+        let mut cg = build_code_graph(&sem_entries);
+        // A cross-language dependency (e.g. a generated client of the Python API)
+        let (web, util) = (cg.file_nodes[&FileId(3)], cg.file_nodes[&FileId(2)]);
+        cg.graph.add_edge(web, util, GraphEdgeKind::Imports);
+        assert_eq!(cg.get_imports(FileId(3)), vec![FileId(2)]);
+
+        let py = cg.subgraph_by_language(Language::Python);
+        assert_eq!(py.file_nodes.len(), 2);
+        assert!(!py.file_nodes.contains_key(&FileId(3)));
+        assert!(py.graph.node_weights().all(|n| n.file_id().is_some()));
+        assert_eq!(py.get_imports(FileId(1)), vec![FileId(2)]);
+        assert!(py.get_importers(FileId(2)).contains(&FileId(1)));
+        assert!(!py.get_importers(FileId(2)).contains(&FileId(3)));
+        assert!(
+            py.function_nodes
+                .contains_key(&(FileId(2), "f".to_string()))
+        );
+
+        let web = cg.subgraph_by_path_prefix("web/");
+        assert_eq!(web.file_nodes.len(), 1);
+        assert!(web.get_imports(FileId(3)).is_empty());
+        assert_eq!(web.graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn dependents_of_leaf_module() {
+        // models <- repo <- service
+        //        <- audit
+        let sem_entries = vec![
+            parse_python_with_id("models.py", "import requests\n", 1),
+            parse_python_with_id("repo.py", "import models\n", 2),
+            parse_python_with_id("audit.py", "from models import User\n", 3),
+            parse_python_with_id("service.py", "import repo\n", 4),
+            parse_python_with_id("cli.py", "import requests\n", 5),
+        ];
+        let cg = build_code_graph(&sem_entries);
+        let models = cg.graph[cg.file_nodes[&FileId(1)]].clone();
+        let ids = |nodes: Vec<GraphNode>| -> Vec<u64> {
+            let mut ids: Vec<u64> = nodes
+                .iter()
+                .filter_map(|n| n.file_id())
+                .map(|f| f.0)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(ids(cg.dependents(&models)), vec![2, 3]);
+        assert_eq!(ids(cg.transitive_dependents(&models)), vec![2, 3, 4]);
+
+        let service = cg.graph[cg.file_nodes[&FileId(4)]].clone();
+        assert!(cg.dependents(&service).is_empty());
+        assert!(cg.transitive_dependents(&service).is_empty());
+
+        let requests = cg.graph[cg.external_modules["requests"]].clone();
+        assert_eq!(ids(cg.dependents(&requests)), vec![1, 5]);
+        assert_eq!(
+            ids(cg.transitive_dependents(&requests)),
+            vec![1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn shortest_path_follows_imports() {
+        let sem_entries = vec![
+            parse_python_with_id("api.py", "import service\nimport util\n", 1),
+            parse_python_with_id("service.py", "import models\n", 2),
+            parse_python_with_id("models.py", "import requests\n", 3),
+            parse_python_with_id("util.py", "x = 1\n", 4),
+        ];
+        let cg = build_code_graph(&sem_entries);
+        let file = |id: u64| cg.graph[cg.file_nodes[&FileId(id)]].clone();
+
+        let path = cg
+            .shortest_path(&file(1), &file(3))
+            .expect("models is reachable");
+        let names: Vec<String> = path.iter().map(GraphNode::display_name).collect();
+        assert_eq!(names, vec!["api.py", "service.py", "models.py"]);
+
+        let requests = cg.graph[cg.external_modules["requests"]].clone();
+        assert_eq!(cg.shortest_path(&file(1), &requests).unwrap().len(), 4);
+
+        assert!(cg.shortest_path(&file(3), &file(1)).is_none());
+        assert!(cg.shortest_path(&file(4), &file(3)).is_none());
+        assert_eq!(cg.shortest_path(&file(4), &file(4)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn orphans_and_unreachable_files() {
+        let sem_entries = vec![
+            parse_python_with_id("main.py", "import service\n", 1),
+            parse_python_with_id("service.py", "import requests\n\ndef run():\n    pass\n", 2),
+            parse_python_with_id("legacy.py", "import helpers\n", 3),
+            parse_python_with_id("helpers.py", "x = 1\n", 4),
+            parse_python_with_id("scratch.py", "import os\n\ndef f():\n    pass\n", 5),
+        ];
+        let cg = build_code_graph(&sem_entries);
+        let names = |nodes: Vec<GraphNode>| -> Vec<String> {
+            nodes.iter().map(GraphNode::display_name).collect()
+        };
+
+        assert_eq!(names(cg.orphans()), vec!["scratch.py"]);
+
+        let main = cg.graph[cg.file_nodes[&FileId(1)]].clone();
+        assert_eq!(
+            names(cg.unreachable_from(&[main])),
+            vec!["legacy.py", "helpers.py", "scratch.py"]
+        );
+
+        // helpers.py was only reachable through legacy.py
+        let legacy = cg.graph[cg.file_nodes[&FileId(3)]].clone();
+        assert!(!names(cg.unreachable_from(&[legacy])).contains(&"helpers.py".to_string()));
+    }
+
+    #[test]
+    fn join_relative_path_normalizes_segments() {
+        assert_eq!(join_relative_path("src/app", "../lib/util"), "src/lib/util");
+        assert_eq!(join_relative_path("src/app", "./ui"), "src/app/ui");
+        assert_eq!(join_relative_path("", "./src/util"), "src/util");
+    }
 }
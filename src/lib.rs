@@ -7,6 +7,7 @@
 //! - **Parsing**: Tree-sitter based parsing for Python, Go, Rust, TypeScript, etc.
 //! - **Semantics**: Extract semantic information (functions, imports, classes, etc.)
 //! - **Graph**: Build code dependency graphs with import/call relationships
+//! - **Report**: Render locations as code-frame snippets for display
 //! - **Types**: Common types for language identification and source files
 //!
 //! ## Example
@@ -33,15 +34,35 @@ use serde::{Deserialize, Serialize};
 pub mod error;
 pub mod graph;
 pub mod parse;
+pub mod report;
 pub mod semantics;
 pub mod types;
 
 // Re-export commonly used types for convenience
-pub use graph::{CodeGraph, GraphEdgeKind, GraphNode, GraphStats, build_code_graph};
+pub use graph::{
+    CodeGraph, GraphBuildOptions, GraphEdgeKind, GraphNode, GraphStats, build_code_graph,
+    build_code_graph_with_calls,
+};
 pub use parse::ast::{FileId, ParsedFile};
 pub use semantics::SourceSemantics;
 pub use types::context::{Language, SourceFile};
 
+use error::EngineError;
+use semantics::CommonSemantics;
+
+/// Parse a source file and build its semantics in one call.
+///
+/// Combines [`parse::parse_source_strict`] and [`semantics::build_semantics`]. Files
+/// whose syntax tree contains errors are rejected with [`error::ParseError::SyntaxErrors`]
+/// rather than producing silently degraded semantics.
+pub fn analyze_file(
+    file_id: FileId,
+    source: &SourceFile,
+) -> Result<Box<dyn CommonSemantics>, EngineError> {
+    let parsed = parse::parse_source_strict(file_id, source)?;
+    Ok(semantics::build_semantics(&parsed)?)
+}
+
 /// Intermediate Representation for client-side parsing.
 ///
 /// This struct contains all the data needed for the server to run rules
@@ -106,3 +127,58 @@ impl IntermediateRepresentation {
         self.graph.rebuild_indexes();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+
+    fn make_source_file(path: &str, language: Language, content: &str) -> SourceFile {
+        SourceFile {
+            path: path.to_string(),
+            language,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn analyze_file_returns_working_semantics() {
+        let sf = make_source_file(
+            "app.py",
+            Language::Python,
+            "import requests\n\ndef fetch():\n    return requests.get('https://example.com')\n",
+        );
+        let sem = analyze_file(FileId(1), &sf).expect("analysis should succeed");
+        assert_eq!(sem.language(), Language::Python);
+        assert!(sem.has_import("requests"));
+        assert!(sem.find_function("fetch").is_some());
+        assert_eq!(sem.http_calls_without_timeout().len(), 1);
+    }
+
+    #[test]
+    fn analyze_file_malformed_source_returns_parse_error() {
+        let sf = make_source_file("broken.py", Language::Python, "def broken(:\n    return\n");
+        let err = analyze_file(FileId(1), &sf).err().expect("should fail");
+        assert!(matches!(
+            err,
+            EngineError::Parse(ParseError::SyntaxErrors { .. })
+        ));
+    }
+
+    #[test]
+    fn analyze_file_javascript_end_to_end() {
+        let sf = make_source_file(
+            "api.js",
+            Language::Javascript,
+            "const axios = require('axios');\n\nasync function load(id, retries = 3) {\n  return axios.get(`/users/${id}`);\n}\n",
+        );
+        let sem = analyze_file(FileId(1), &sf).expect("javascript should be analyzed");
+        assert_eq!(sem.language(), Language::Javascript);
+        let functions = sem.functions();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "load");
+        assert!(functions[0].is_async);
+        assert_eq!(functions[0].params.len(), 2);
+        assert!(functions[0].params[1].is_optional);
+    }
+}
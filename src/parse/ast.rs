@@ -1,20 +1,37 @@
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tree_sitter::{Node, Tree};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+use tree_sitter::{InputEdit, Node, Parser, Query, QueryCursor, StreamingIterator, Tree};
 
+use crate::error::{ParseError, QueryError};
+use crate::semantics::common::CommonLocation;
 use crate::types::context::Language;
 
 /// Engine-internal identifier for a file in a session.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct FileId(pub u64);
 
+impl std::fmt::Display for FileId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Text range in (line, col) space; 0-based.
+///
+/// `start_col`/`end_col` count bytes from the start of the line, as tree-sitter
+/// does. The `*_char_col` variants count Unicode scalar values instead, which
+/// is what editors show for lines with multibyte characters.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TextRange {
     pub start_line: u32,
     pub start_col: u32,
     pub end_line: u32,
     pub end_col: u32,
+    #[serde(default)]
+    pub start_char_col: u32,
+    #[serde(default)]
+    pub end_char_col: u32,
 }
 
 /// Lightweight handle to "where in the AST" something lives.
@@ -24,6 +41,49 @@ pub struct AstLocation {
     pub range: TextRange,
 }
 
+impl AstLocation {
+    /// 0-based start column in bytes
+    pub fn byte_column(&self) -> u32 {
+        self.range.start_col
+    }
+
+    /// 0-based start column in characters
+    pub fn char_column(&self) -> u32 {
+        self.range.start_char_col
+    }
+}
+
+/// A node captured by a [`ParsedFile::query`] pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryCapture {
+    /// Capture name without the `@`
+    pub name: String,
+    /// Source text of the captured node
+    pub text: String,
+    pub location: AstLocation,
+}
+
+/// One match of a [`ParsedFile::query`] pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryMatch {
+    /// Index of the matching pattern within the query source
+    pub pattern_index: usize,
+    /// Captured nodes, in the order tree-sitter reports them
+    pub captures: Vec<QueryCapture>,
+}
+
+impl QueryMatch {
+    /// The first capture with the given name
+    pub fn capture(&self, name: &str) -> Option<&QueryCapture> {
+        self.captures.iter().find(|c| c.name == name)
+    }
+}
+
+/// Compiled queries, keyed by grammar and query source.
+type QueryCache = HashMap<(tree_sitter::Language, String), Arc<Query>>;
+
+static QUERY_CACHE: LazyLock<Mutex<QueryCache>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// A fully parsed source file: source + tree-sitter AST + language tag.
 #[derive(Debug)]
 pub struct ParsedFile {
@@ -32,9 +92,50 @@ pub struct ParsedFile {
     pub language: Language,
     pub source: Arc<String>,
     pub tree: Tree,
+    /// Byte offset of the start of each line of `source`, built on first lookup
+    pub(crate) line_starts: OnceLock<Arc<[usize]>>,
 }
 
 impl ParsedFile {
+    /// Wrap a tree parsed elsewhere (e.g. by an editor) without re-parsing.
+    ///
+    /// `content` must be the text `tree` was parsed from. Fails if the tree
+    /// came from a different grammar than `language` uses, or if it spans
+    /// more bytes than `content` holds.
+    pub fn from_tree(
+        file_id: FileId,
+        path: impl Into<String>,
+        language: Language,
+        content: impl Into<String>,
+        tree: Tree,
+    ) -> Result<Self, ParseError> {
+        let path = path.into();
+        let content = content.into();
+        if !crate::parse::grammars(language)
+            .iter()
+            .any(|g| *g == *tree.language())
+        {
+            return Err(ParseError::LanguageMismatch { language });
+        }
+        let tree_len = tree.root_node().end_byte();
+        if tree_len > content.len() {
+            return Err(ParseError::ContentMismatch {
+                file_path: path,
+                tree_len,
+                content_len: content.len(),
+            });
+        }
+
+        Ok(Self {
+            file_id,
+            path,
+            language,
+            source: Arc::new(content),
+            tree,
+            line_starts: OnceLock::new(),
+        })
+    }
+
     /// Convert a tree-sitter node range into a TextRange.
     pub fn location_for_node(&self, node: &Node) -> AstLocation {
         let range = node.range();
@@ -48,15 +149,206 @@ impl ParsedFile {
                 start_col: start.column as u32,
                 end_line: end.row as u32,
                 end_col: end.column as u32,
+                start_char_col: self.char_column(range.start_byte, start.column),
+                end_char_col: self.char_column(range.end_byte, end.column),
             },
         }
     }
 
+    /// Character column of `byte`, given its byte column on the same line.
+    fn char_column(&self, byte: usize, byte_column: usize) -> u32 {
+        self.source
+            .get(byte - byte_column..byte)
+            .map_or(byte_column, |prefix| prefix.chars().count()) as u32
+    }
+
     /// Get the exact source text for a node.
     pub fn text_for_node(&self, node: &Node) -> String {
         let byte_range = node.byte_range();
         self.source[byte_range.start..byte_range.end].to_string()
     }
+
+    /// Exact source text covered by `loc`.
+    ///
+    /// Returns an empty string if the byte range runs past the end of the
+    /// source or does not fall on character boundaries.
+    pub fn slice(&self, loc: &CommonLocation) -> &str {
+        self.source
+            .get(loc.start_byte..loc.end_byte)
+            .unwrap_or_default()
+    }
+
+    /// Run a tree-sitter S-expression query against this file's tree.
+    ///
+    /// The query is compiled for the file's grammar on first use and cached,
+    /// so repeated queries across files cost one compilation per grammar.
+    /// Text predicates such as `#eq?` and `#match?` are honoured.
+    pub fn query(&self, query_src: &str) -> Result<Vec<QueryMatch>, QueryError> {
+        let query = compiled_query(self.tree.language().to_owned(), query_src)?;
+        let names = query.capture_names();
+
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&query, self.tree.root_node(), self.source.as_bytes());
+        let mut results = Vec::new();
+        while let Some(m) = matches.next() {
+            results.push(QueryMatch {
+                pattern_index: m.pattern_index,
+                captures: m
+                    .captures
+                    .iter()
+                    .map(|capture| QueryCapture {
+                        name: names[capture.index as usize].to_string(),
+                        text: self.text_for_node(&capture.node),
+                        location: self.location_for_node(&capture.node),
+                    })
+                    .collect(),
+            });
+        }
+        Ok(results)
+    }
+
+    /// Apply an edit and incrementally re-parse, reusing unchanged subtrees.
+    ///
+    /// `edit` describes the change from the current source to `new_content`, in
+    /// tree-sitter terms (byte offsets and row/column points). On success the
+    /// source and tree both reflect `new_content`; on failure they are untouched.
+    pub fn reparse_with_edit(
+        &mut self,
+        edit: InputEdit,
+        new_content: &str,
+    ) -> Result<(), ParseError> {
+        let to_parse_error = |source: anyhow::Error| ParseError::File {
+            file_path: self.path.clone(),
+            source,
+        };
+
+        // The tree remembers its grammar, so no parser needs to be stored.
+        let mut parser = Parser::new();
+        parser
+            .set_language(&self.tree.language())
+            .map_err(|err| to_parse_error(err.into()))?;
+
+        let mut old_tree = self.tree.clone();
+        old_tree.edit(&edit);
+        let tree = parser
+            .parse(new_content, Some(&old_tree))
+            .ok_or_else(|| to_parse_error(anyhow::anyhow!("incremental re-parse failed")))?;
+
+        self.tree = tree;
+        self.source = Arc::new(new_content.to_string());
+        self.line_starts = OnceLock::new();
+        Ok(())
+    }
+
+    /// Convert a byte offset into a 0-based (line, column) position.
+    ///
+    /// Columns count bytes, like tree-sitter points and [`TextRange`]. Offsets
+    /// past the end of the source are clamped to it. The line index is built
+    /// on first lookup and kept until the next [`ParsedFile::reparse_with_edit`].
+    pub fn line_col_at(&self, byte: usize) -> (usize, usize) {
+        let byte = byte.min(self.source.len());
+        let starts = self.line_starts();
+        let line = starts.partition_point(|&start| start <= byte) - 1;
+        (line, byte - starts[line])
+    }
+
+    /// Convert a 0-based (line, column) position into a byte offset.
+    ///
+    /// Returns `None` if the line does not exist, the column runs past the end
+    /// of the line, or the position falls inside a multibyte character.
+    pub fn byte_at(&self, line: usize, col: usize) -> Option<usize> {
+        let starts = self.line_starts();
+        let start = *starts.get(line)?;
+        let line_end = starts
+            .get(line + 1)
+            .map_or(self.source.len(), |&next| next - 1);
+        let byte = start + col;
+        (byte <= line_end && self.source.is_char_boundary(byte)).then_some(byte)
+    }
+
+    /// Byte offset of the start of each line, built once per source.
+    fn line_starts(&self) -> &[usize] {
+        self.line_starts.get_or_init(|| {
+            std::iter::once(0)
+                .chain(self.source.match_indices('\n').map(|(idx, _)| idx + 1))
+                .collect()
+        })
+    }
+
+    /// Visit every node in source order (pre-order), with its depth below the root.
+    pub fn walk<'t>(&'t self, visitor: &mut impl FnMut(Node<'t>, usize)) {
+        let mut cursor = self.tree.walk();
+        let mut depth = 0;
+        loop {
+            visitor(cursor.node(), depth);
+            if cursor.goto_first_child() {
+                depth += 1;
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    return;
+                }
+                depth -= 1;
+            }
+        }
+    }
+
+    /// Visit every node of the given kind, in source order.
+    pub fn walk_kind<'t>(&'t self, kind: &str, mut f: impl FnMut(Node<'t>)) {
+        self.walk(&mut |node, _| {
+            if node.kind() == kind {
+                f(node);
+            }
+        });
+    }
+
+    /// Whether tree-sitter had to recover from syntax errors in this file.
+    pub fn has_errors(&self) -> bool {
+        self.tree.root_node().has_error()
+    }
+
+    /// Locations of ERROR and MISSING nodes, in source order.
+    ///
+    /// Nested errors are reported once, at the outermost ERROR node.
+    pub fn error_ranges(&self) -> Vec<AstLocation> {
+        let mut ranges = Vec::new();
+        let mut stack = vec![self.tree.root_node()];
+
+        while let Some(node) = stack.pop() {
+            if node.is_error() || node.is_missing() {
+                ranges.push(self.location_for_node(&node));
+                continue;
+            }
+            if !node.has_error() {
+                continue;
+            }
+
+            let mut cursor = node.walk();
+            let children: Vec<Node> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+
+        ranges
+    }
+}
+
+/// Compile `query_src` for `language`, reusing a previous compilation.
+fn compiled_query(
+    language: tree_sitter::Language,
+    query_src: &str,
+) -> Result<Arc<Query>, QueryError> {
+    let key = (language, query_src.to_string());
+    if let Some(query) = QUERY_CACHE.lock().expect("query cache poisoned").get(&key) {
+        return Ok(Arc::clone(query));
+    }
+
+    let query = Arc::new(Query::new(&key.0, query_src)?);
+    QUERY_CACHE
+        .lock()
+        .expect("query cache poisoned")
+        .insert(key, Arc::clone(&query));
+    Ok(query)
 }
 
 #[cfg(test)]
@@ -133,6 +425,8 @@ mod tests {
             start_col: 0,
             end_line: 10,
             end_col: 5,
+            start_char_col: 0,
+            end_char_col: 5,
         };
         assert_eq!(range.start_line, 0);
         assert_eq!(range.start_col, 0);
@@ -147,18 +441,24 @@ mod tests {
             start_col: 2,
             end_line: 3,
             end_col: 4,
+            start_char_col: 2,
+            end_char_col: 4,
         };
         let range2 = TextRange {
             start_line: 1,
             start_col: 2,
             end_line: 3,
             end_col: 4,
+            start_char_col: 2,
+            end_char_col: 4,
         };
         let range3 = TextRange {
             start_line: 1,
             start_col: 2,
             end_line: 3,
-            end_col: 5, // different
+            end_col: 5,
+            start_char_col: 2,
+            end_char_col: 5, // different
         };
 
         assert_eq!(range1, range2);
@@ -172,6 +472,8 @@ mod tests {
             start_col: 10,
             end_line: 15,
             end_col: 20,
+            start_char_col: 10,
+            end_char_col: 20,
         };
         let range2 = range1;
         assert_eq!(range1, range2);
@@ -184,6 +486,8 @@ mod tests {
             start_col: 2,
             end_line: 3,
             end_col: 4,
+            start_char_col: 2,
+            end_char_col: 4,
         };
         let debug_str = format!("{:?}", range);
         assert!(debug_str.contains("TextRange"));
@@ -197,6 +501,8 @@ mod tests {
             start_col: 20,
             end_line: 30,
             end_col: 40,
+            start_char_col: 20,
+            end_char_col: 40,
         };
         let json = serde_json::to_string(&range).unwrap();
         let deserialized: TextRange = serde_json::from_str(&json).unwrap();
@@ -210,6 +516,8 @@ mod tests {
             start_col: 10,
             end_line: 5,
             end_col: 11,
+            start_char_col: 10,
+            end_char_col: 11,
         };
         assert_eq!(range.start_line, range.end_line);
         assert_eq!(range.end_col - range.start_col, 1);
@@ -222,6 +530,8 @@ mod tests {
             start_col: 5,
             end_line: 100,
             end_col: 0,
+            start_char_col: 5,
+            end_char_col: 0,
         };
         assert!(range.end_line > range.start_line);
     }
@@ -237,6 +547,8 @@ mod tests {
                 start_col: 0,
                 end_line: 1,
                 end_col: 10,
+                start_char_col: 0,
+                end_char_col: 10,
             },
         };
         assert_eq!(loc.file_id, FileId(1));
@@ -252,6 +564,8 @@ mod tests {
                 start_col: 0,
                 end_line: 1,
                 end_col: 10,
+                start_char_col: 0,
+                end_char_col: 10,
             },
         };
         let loc2 = AstLocation {
@@ -261,6 +575,8 @@ mod tests {
                 start_col: 0,
                 end_line: 1,
                 end_col: 10,
+                start_char_col: 0,
+                end_char_col: 10,
             },
         };
         let loc3 = AstLocation {
@@ -270,6 +586,8 @@ mod tests {
                 start_col: 0,
                 end_line: 1,
                 end_col: 10,
+                start_char_col: 0,
+                end_char_col: 10,
             },
         };
 
@@ -286,6 +604,8 @@ mod tests {
                 start_col: 2,
                 end_line: 3,
                 end_col: 4,
+                start_char_col: 2,
+                end_char_col: 4,
             },
         };
         let loc2 = loc1.clone();
@@ -301,6 +621,8 @@ mod tests {
                 start_col: 0,
                 end_line: 1,
                 end_col: 10,
+                start_char_col: 0,
+                end_char_col: 10,
             },
         };
         let debug_str = format!("{:?}", loc);
@@ -318,6 +640,8 @@ mod tests {
                 start_col: 20,
                 end_line: 30,
                 end_col: 40,
+                start_char_col: 20,
+                end_char_col: 40,
             },
         };
         let json = serde_json::to_string(&loc).unwrap();
@@ -534,6 +858,20 @@ mod tests {
         assert_eq!(loc.range.start_col, 4);
     }
 
+    #[test]
+    fn test_location_for_node_counts_char_columns_after_emoji() {
+        let parsed = create_test_parsed_file("s = \"🎉é\"; run()\n");
+        let mut call = None;
+        parsed.walk_kind("call", |node| call = Some(node));
+        let loc = parsed.location_for_node(&call.unwrap());
+
+        // The emoji is 4 bytes and `é` is 2, but each is one character
+        assert_eq!(loc.byte_column(), 14);
+        assert_eq!(loc.char_column(), 10);
+        assert_eq!(loc.range.end_col, 19);
+        assert_eq!(loc.range.end_char_col, 15);
+    }
+
     #[test]
     fn test_text_for_node_preserves_whitespace() {
         let code = "x   =   1";
@@ -566,4 +904,268 @@ mod tests {
         let text = parsed.text_for_node(&root);
         assert_eq!(text, "");
     }
+
+    #[test]
+    fn test_walk_visits_nodes_in_order_with_depth() {
+        let parsed = create_test_parsed_file("x = 1\n");
+        let mut visited = Vec::new();
+        parsed.walk(&mut |node, depth| visited.push((node.kind(), depth)));
+
+        assert_eq!(visited[0], ("module", 0));
+        assert_eq!(visited[1], ("expression_statement", 1));
+        assert_eq!(visited[2], ("assignment", 2));
+        assert_eq!(visited[3], ("identifier", 3));
+        assert_eq!(visited.len(), parsed.tree.root_node().descendant_count());
+    }
+
+    #[test]
+    fn test_walk_kind_counts_rust_calls() {
+        use crate::types::context::SourceFile;
+
+        let sf = SourceFile {
+            path: "lib.rs".to_string(),
+            language: Language::Rust,
+            content: "fn main() {\n    let v = load(1);\n    save(v.clone());\n    println!(\"{}\", v);\n}\n"
+                .to_string(),
+        };
+        let parsed = crate::parse::rust::parse_rust_file(FileId(1), &sf).unwrap();
+
+        let mut callees = Vec::new();
+        parsed.walk_kind("call_expression", |node| {
+            let function = node.child_by_field_name("function").unwrap();
+            callees.push(parsed.text_for_node(&function));
+        });
+        assert_eq!(callees, vec!["load", "save", "v.clone"]);
+    }
+
+    #[test]
+    fn test_line_col_at_and_byte_at_round_trip() {
+        let code = "x = 1\nname = \"é\"\ny = 2\n";
+        let parsed = create_test_parsed_file(code);
+
+        assert_eq!(parsed.line_col_at(0), (0, 0));
+        assert_eq!(parsed.line_col_at(5), (0, 5));
+        assert_eq!(parsed.line_col_at(6), (1, 0));
+
+        // `é` is two bytes, so the closing quote sits at byte column 10
+        let quote = code.rfind("\"\n").unwrap();
+        assert_eq!(parsed.line_col_at(quote), (1, 10));
+        assert_eq!(parsed.line_col_at(quote + 2), (2, 0));
+        assert_eq!(parsed.line_col_at(code.len() + 10), (3, 0));
+
+        for byte in (0..=code.len()).filter(|&b| code.is_char_boundary(b)) {
+            let (line, col) = parsed.line_col_at(byte);
+            assert_eq!(parsed.byte_at(line, col), Some(byte));
+        }
+    }
+
+    #[test]
+    fn test_line_index_is_rebuilt_after_reparse() {
+        use tree_sitter::Point;
+
+        let mut parsed = create_test_parsed_file("a = 1\nb = 2\n");
+        assert_eq!(parsed.line_col_at(6), (1, 0));
+
+        // Rename `a` to `abc`, pushing the second line two bytes later
+        let edit = InputEdit {
+            start_byte: 1,
+            old_end_byte: 1,
+            new_end_byte: 3,
+            start_position: Point::new(0, 1),
+            old_end_position: Point::new(0, 1),
+            new_end_position: Point::new(0, 3),
+        };
+        parsed
+            .reparse_with_edit(edit, "abc = 1\nb = 2\n")
+            .expect("re-parse should succeed");
+
+        assert_eq!(parsed.line_col_at(6), (0, 6));
+        assert_eq!(parsed.line_col_at(8), (1, 0));
+        assert_eq!(parsed.byte_at(1, 0), Some(8));
+    }
+
+    #[test]
+    fn test_byte_at_rejects_invalid_positions() {
+        let parsed = create_test_parsed_file("a = \"é\"\nb = 2");
+
+        assert_eq!(parsed.byte_at(1, 5), Some(14));
+        assert_eq!(parsed.byte_at(1, 6), None);
+        assert_eq!(parsed.byte_at(2, 0), None);
+        // Column 6 on line 0 is the middle of `é`
+        assert_eq!(parsed.byte_at(0, 6), None);
+        assert_eq!(parsed.byte_at(0, 5), Some(5));
+    }
+
+    #[test]
+    fn test_line_index_matches_tree_sitter_points() {
+        let parsed = create_test_parsed_file("def f():\n    return \"ü\" + g()\n");
+        parsed.walk(&mut |node, _| {
+            let start = node.start_position();
+            assert_eq!(
+                parsed.line_col_at(node.start_byte()),
+                (start.row, start.column)
+            );
+        });
+    }
+
+    #[test]
+    fn test_has_errors_false_for_valid_python() {
+        let parsed = create_test_parsed_file("def ok():\n    return 1\n");
+        assert!(!parsed.has_errors());
+        assert!(parsed.error_ranges().is_empty());
+    }
+
+    #[test]
+    fn test_has_errors_true_for_broken_python() {
+        let code = "def ok():\n    return 1\n\ndef broken(:\n    return\n";
+        let parsed = create_test_parsed_file(code);
+        assert!(parsed.has_errors());
+
+        let ranges = parsed.error_ranges();
+        assert!(!ranges.is_empty());
+        assert!(ranges.iter().all(|loc| loc.range.start_line >= 3));
+    }
+
+    #[test]
+    fn test_reparse_with_edit_updates_tree_and_offsets() {
+        use tree_sitter::Point;
+
+        let mut parsed = create_test_parsed_file("x = 1\ny = 2\n");
+        let second = parsed.tree.root_node().named_child(1).unwrap();
+        assert_eq!(second.start_byte(), 6);
+
+        // Rename `x` to `xyz`: insert two bytes after the first character.
+        let edit = InputEdit {
+            start_byte: 1,
+            old_end_byte: 1,
+            new_end_byte: 3,
+            start_position: Point::new(0, 1),
+            old_end_position: Point::new(0, 1),
+            new_end_position: Point::new(0, 3),
+        };
+        parsed
+            .reparse_with_edit(edit, "xyz = 1\ny = 2\n")
+            .expect("re-parse should succeed");
+
+        let root = parsed.tree.root_node();
+        assert!(!parsed.has_errors());
+        assert_eq!(parsed.source.as_str(), "xyz = 1\ny = 2\n");
+
+        let first = root.named_child(0).unwrap();
+        assert_eq!(parsed.text_for_node(&first), "xyz = 1");
+
+        let second = root.named_child(1).unwrap();
+        assert_eq!(second.start_byte(), 8);
+        assert_eq!(parsed.text_for_node(&second), "y = 2");
+
+        let fresh = create_test_parsed_file("xyz = 1\ny = 2\n");
+        assert_eq!(root.to_sexp(), fresh.tree.root_node().to_sexp());
+    }
+
+    #[test]
+    fn slice_returns_source_of_function_and_import() {
+        use crate::semantics::common::CommonSemantics;
+        use crate::semantics::python::model::PyFileSemantics;
+
+        let code = "import os\n\ndef handler(event):\n    return os.getcwd()\n";
+        let parsed = create_test_parsed_file(code);
+        let sem = PyFileSemantics::from_parsed(&parsed);
+
+        let import = &sem.imports()[0];
+        assert_eq!(parsed.slice(&import.location), "import os");
+
+        let function = &sem.functions()[0];
+        assert_eq!(
+            parsed.slice(&function.location),
+            "def handler(event):\n    return os.getcwd()"
+        );
+
+        let out_of_bounds = CommonLocation {
+            start_byte: 40,
+            end_byte: 400,
+            ..function.location.clone()
+        };
+        assert_eq!(parsed.slice(&out_of_bounds), "");
+    }
+
+    #[test]
+    fn query_captures_rust_function_names() {
+        use crate::types::context::SourceFile;
+
+        let sf = SourceFile {
+            path: "lib.rs".to_string(),
+            language: Language::Rust,
+            content: "fn connect() {}\n\nimpl Client {\n    fn send(&self) {}\n}\n".to_string(),
+        };
+        let parsed = crate::parse::rust::parse_rust_file(FileId(1), &sf).unwrap();
+
+        let query = "(function_item name: (identifier) @name)";
+        let matches = parsed.query(query).expect("query should compile");
+        let names: Vec<&str> = matches
+            .iter()
+            .filter_map(|m| m.capture("name"))
+            .map(|c| c.text.as_str())
+            .collect();
+        assert_eq!(names, ["connect", "send"]);
+        assert_eq!(matches[1].captures[0].location.range.start_line, 3);
+
+        // Cached query gives the same result
+        assert_eq!(parsed.query(query).unwrap(), matches);
+
+        let filtered = parsed
+            .query(r#"((function_item name: (identifier) @name) (#eq? @name "send"))"#)
+            .unwrap();
+        assert_eq!(filtered.len(), 1);
+
+        assert!(matches!(
+            parsed.query("(function_item"),
+            Err(QueryError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn from_tree_reuses_an_externally_parsed_tree() {
+        let content = "def handler(event):\n    return event\n";
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(content, None).unwrap();
+
+        let parsed = ParsedFile::from_tree(
+            FileId(7),
+            "handler.py",
+            Language::Python,
+            content,
+            tree.clone(),
+        )
+        .expect("python tree should be accepted");
+        assert_eq!(parsed.path, "handler.py");
+        let functions = crate::semantics::build_source_semantics(&parsed)
+            .unwrap()
+            .unwrap()
+            .into_common()
+            .functions();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "handler");
+        assert_eq!(functions[0].location.file_id, FileId(7));
+
+        assert!(matches!(
+            ParsedFile::from_tree(FileId(7), "handler.py", Language::Go, content, tree.clone()),
+            Err(ParseError::LanguageMismatch {
+                language: Language::Go
+            })
+        ));
+
+        // Content shorter than the tree would make node text lookups panic
+        let truncated = &content[..10];
+        assert!(matches!(
+            ParsedFile::from_tree(FileId(7), "handler.py", Language::Python, truncated, tree),
+            Err(ParseError::ContentMismatch {
+                tree_len,
+                content_len: 10,
+                ..
+            }) if tree_len == content.len()
+        ));
+    }
 }
@@ -0,0 +1,227 @@
+//! Reuse of parsed trees across re-scans.
+//!
+//! Watch mode re-analyzes every file on each change even though most are
+//! untouched. [`ParseCache`] keys trees by language and a hash of the content,
+//! so unchanged content skips tree-sitter, including identical files at
+//! different paths. Each path holds a reference to the tree of its latest
+//! content; a tree no path refers to any more is evicted, so edits do not
+//! pile up stale trees.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tree_sitter::Tree;
+
+use super::ast::{FileId, ParsedFile};
+use crate::types::context::{Language, SourceFile};
+
+/// Cache key: the language and content hash a tree was parsed from
+type Key = (Language, u64);
+
+/// Parsed trees keyed by language and content hash
+#[derive(Debug, Default)]
+pub struct ParseCache {
+    entries: HashMap<Key, Entry>,
+    /// Key of the latest content seen at each path
+    paths: HashMap<String, Key>,
+    parses: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    source: Arc<String>,
+    tree: Tree,
+    /// Number of paths whose latest content is this entry
+    users: usize,
+}
+
+impl ParseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached trees
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the cache holds no trees
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of parses performed because the content was not cached
+    pub fn parses(&self) -> usize {
+        self.parses
+    }
+
+    /// Drop every cached tree
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.paths.clear();
+    }
+
+    /// A `ParsedFile` for `sf` built from a cached tree of the same language
+    /// and content, if there is one.
+    ///
+    /// The hash is a cheap first check; the content itself and the grammar
+    /// are compared, so a collision never returns a tree for different source
+    /// and a `.ts` tree is never reused for a `.tsx` file.
+    pub(crate) fn get(&mut self, file_id: FileId, sf: &SourceFile) -> Option<ParsedFile> {
+        let key = (sf.language, content_hash(&sf.content));
+        let entry = self.entries.get(&key)?;
+        if *entry.source != sf.content || *entry.tree.language() != super::grammar_for(sf) {
+            return None;
+        }
+        let parsed = ParsedFile {
+            file_id,
+            path: sf.path.clone(),
+            language: sf.language,
+            source: Arc::clone(&entry.source),
+            tree: entry.tree.clone(),
+            line_starts: Default::default(),
+        };
+        self.track(&sf.path, key);
+        Some(parsed)
+    }
+
+    /// Remember the tree of a freshly parsed file.
+    ///
+    /// The path's previous content is released, and its tree evicted if no
+    /// other path shares it.
+    pub(crate) fn insert(&mut self, parsed: &ParsedFile) {
+        self.parses += 1;
+        let key = (parsed.language, content_hash(&parsed.source));
+        match self.entries.get_mut(&key) {
+            Some(entry) => {
+                entry.source = Arc::clone(&parsed.source);
+                entry.tree = parsed.tree.clone();
+            }
+            None => {
+                self.entries.insert(
+                    key,
+                    Entry {
+                        source: Arc::clone(&parsed.source),
+                        tree: parsed.tree.clone(),
+                        users: 0,
+                    },
+                );
+            }
+        }
+        self.track(&parsed.path, key);
+    }
+
+    /// Drop the reference of a file that no longer exists, evicting its tree
+    /// if no other path shares it.
+    pub fn remove(&mut self, path: &str) {
+        if let Some(key) = self.paths.remove(path) {
+            self.release(key);
+        }
+    }
+
+    /// Point `path` at `key`, releasing the entry it pointed at before.
+    fn track(&mut self, path: &str, key: Key) {
+        match self.paths.insert(path.to_string(), key) {
+            Some(previous) if previous == key => return,
+            Some(previous) => self.release(previous),
+            None => {}
+        }
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.users += 1;
+        }
+    }
+
+    /// Drop one reference to `key`, evicting the entry once none are left.
+    fn release(&mut self, key: Key) {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.users = entry.users.saturating_sub(1);
+            if entry.users == 0 {
+                self.entries.remove(&key);
+            }
+        }
+    }
+}
+
+/// FxHash over the content bytes: fast and non-cryptographic.
+fn content_hash(content: &str) -> u64 {
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    let mut hash = content.len() as u64;
+    let mut chunks = content.as_bytes().chunks_exact(8);
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().expect("chunk is 8 bytes"));
+        hash = (hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+    for &byte in chunks.remainder() {
+        hash = (hash.rotate_left(5) ^ u64::from(byte)).wrapping_mul(SEED);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_distinguishes_small_edits() {
+        assert_eq!(content_hash("x = 1\n"), content_hash("x = 1\n"));
+        assert_ne!(content_hash("x = 1\n"), content_hash("x = 2\n"));
+        assert_ne!(content_hash(""), content_hash("\0"));
+    }
+
+    fn parse(path: &str, content: &str) -> ParsedFile {
+        let sf = SourceFile {
+            path: path.to_string(),
+            language: Language::Python,
+            content: content.to_string(),
+        };
+        crate::parse::parse_source(FileId(1), &sf).unwrap()
+    }
+
+    fn source(path: &str, content: &str) -> SourceFile {
+        SourceFile {
+            path: path.to_string(),
+            language: Language::Python,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn edits_evict_trees_no_path_still_uses() {
+        let mut cache = ParseCache::new();
+        for i in 0..10 {
+            cache.insert(&parse("app.py", &format!("x = {i}\n")));
+        }
+        cache.insert(&parse("lib.py", "y = 1\n"));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.parses(), 11);
+        assert!(cache.get(FileId(1), &source("app.py", "x = 0\n")).is_none());
+
+        cache.remove("lib.py");
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn identical_content_shares_one_tree_across_paths() {
+        let mut cache = ParseCache::new();
+        cache.insert(&parse("pkg/__init__.py", ""));
+        let hit = cache.get(FileId(2), &source("other/__init__.py", ""));
+        assert_eq!(
+            hit.map(|parsed| parsed.path),
+            Some("other/__init__.py".to_string())
+        );
+        assert_eq!(cache.len(), 1);
+
+        // The tree survives until the last path using it moves on
+        cache.insert(&parse("pkg/__init__.py", "x = 1\n"));
+        assert!(
+            cache
+                .get(FileId(2), &source("third/__init__.py", ""))
+                .is_some()
+        );
+        cache.remove("other/__init__.py");
+        assert_eq!(cache.len(), 2);
+        cache.remove("third/__init__.py");
+        assert_eq!(cache.len(), 1);
+    }
+}
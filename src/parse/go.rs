@@ -6,7 +6,7 @@ use tree_sitter::{Language as TsLanguage, Parser};
 use crate::parse::ast::{FileId, ParsedFile};
 use crate::types::context::{Language, SourceFile};
 
-fn go_language() -> TsLanguage {
+pub(crate) fn go_language() -> TsLanguage {
     tree_sitter_go::LANGUAGE.into()
 }
 
@@ -26,6 +26,7 @@ pub fn parse_go_file(file_id: FileId, sf: &SourceFile) -> Result<ParsedFile> {
         language: Language::Go,
         source,
         tree,
+        line_starts: Default::default(),
     })
 }
 
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tree_sitter::{Language as TsLanguage, Parser};
+
+use crate::parse::ast::{FileId, ParsedFile};
+use crate::types::context::{Language, SourceFile};
+
+pub(crate) fn java_language() -> TsLanguage {
+    tree_sitter_java::LANGUAGE.into()
+}
+
+/// Parse a Java source file into a `ParsedFile`.
+pub fn parse_java_file(file_id: FileId, sf: &SourceFile) -> Result<ParsedFile> {
+    let mut parser = Parser::new();
+    parser.set_language(&java_language())?;
+
+    let source = Arc::new(sf.content.clone());
+    let tree = parser
+        .parse(&*source, None)
+        .ok_or_else(|| anyhow::anyhow!("failed to parse Java source"))?;
+
+    Ok(ParsedFile {
+        file_id,
+        path: sf.path.clone(),
+        language: Language::Java,
+        source,
+        tree,
+        line_starts: Default::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_java_source_file(path: &str, content: &str) -> SourceFile {
+        SourceFile {
+            path: path.to_string(),
+            language: Language::Java,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_java_language_returns_valid_language() {
+        let lang = java_language();
+        assert!(lang.abi_version() > 0);
+    }
+
+    #[test]
+    fn test_parse_simple_class() {
+        let sf = make_java_source_file("Test.java", "public class Test {}");
+        let result = parse_java_file(FileId(1), &sf);
+        assert!(result.is_ok());
+        let parsed = result.unwrap();
+        assert_eq!(parsed.language, Language::Java);
+        assert_eq!(parsed.tree.root_node().kind(), "program");
+        assert!(!parsed.tree.root_node().has_error());
+    }
+
+    #[test]
+    fn test_parse_methods_and_imports() {
+        let code = r#"
+package com.example;
+
+import java.util.List;
+import com.example.repo.UserRepository;
+
+public class UserService {
+    public List<String> names() {
+        return List.of("a", "b");
+    }
+
+    private void log(String msg) {
+        System.out.println(msg);
+    }
+}
+"#;
+        let sf = make_java_source_file("UserService.java", code);
+        let parsed = parse_java_file(FileId(2), &sf).expect("parsing should succeed");
+        let root = parsed.tree.root_node();
+        assert!(!root.has_error());
+
+        let mut cursor = root.walk();
+        let import_count = root
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "import_declaration")
+            .count();
+        assert_eq!(import_count, 2);
+    }
+
+    #[test]
+    fn test_parse_preserves_path_and_file_id() {
+        let sf = make_java_source_file("src/main/java/App.java", "class App {}");
+        let parsed = parse_java_file(FileId(42), &sf).expect("parsing should succeed");
+        assert_eq!(parsed.file_id, FileId(42));
+        assert_eq!(parsed.path, "src/main/java/App.java");
+    }
+}
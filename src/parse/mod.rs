@@ -1,42 +1,207 @@
 pub mod ast;
+mod cache; // Parsed-tree reuse across re-scans
 pub mod go;
+pub mod java;
+pub mod javascript;
 pub mod python;
 pub mod rust;
 pub mod typescript;
 
-use crate::error::ParseError;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tree_sitter::{ParseOptions as TsParseOptions, ParseState, Parser};
+
+use crate::error::{EngineError, ParseError};
+use crate::semantics::common::CommonSemantics;
 use crate::types::context::{Language, SourceFile};
 use ast::{FileId, ParsedFile};
+pub use cache::ParseCache;
 
-/// Generic entry point: parse a `SourceFile` into a `ParsedFile`.
+/// Whether `language` has a tree-sitter grammar, i.e. [`parse_source`] accepts it.
+pub fn is_supported(language: Language) -> bool {
+    matches!(
+        language,
+        Language::Python
+            | Language::Go
+            | Language::Rust
+            | Language::Java
+            | Language::Typescript
+            | Language::Javascript
+    )
+}
 
-pub fn parse_source_file(file_id: FileId, sf: &SourceFile) -> Result<ParsedFile, ParseError> {
+/// Tree-sitter grammars that parse `language`; TypeScript has both the `.ts`
+/// and `.tsx` dialects.
+pub(crate) fn grammars(language: Language) -> Vec<tree_sitter::Language> {
+    match language {
+        Language::Python => vec![python::python_language()],
+        Language::Go => vec![go::go_language()],
+        Language::Rust => vec![rust::rust_language()],
+        Language::Java => vec![java::java_language()],
+        Language::Typescript => vec![
+            typescript::typescript_language(),
+            typescript::tsx_language(),
+        ],
+        Language::Javascript => vec![javascript::javascript_language()],
+    }
+}
+
+/// The grammar `sf` is parsed with; `.tsx` files use the TSX dialect.
+pub(crate) fn grammar_for(sf: &SourceFile) -> tree_sitter::Language {
     match sf.language {
-        Language::Python => {
-            python::parse_python_file(file_id, sf).map_err(|source| ParseError::File {
-                file_path: sf.path.clone(),
-                source,
-            })
-        }
-        Language::Go => go::parse_go_file(file_id, sf).map_err(|source| ParseError::File {
+        Language::Typescript if sf.path.ends_with(".tsx") => typescript::tsx_language(),
+        language => grammars(language).swap_remove(0),
+    }
+}
+
+/// Generic entry point: parse a `SourceFile` into a `ParsedFile`.
+///
+/// Dispatches on `sf.language` to the matching tree-sitter parser.
+pub fn parse_source(file_id: FileId, sf: &SourceFile) -> Result<ParsedFile, ParseError> {
+    let parsed = match sf.language {
+        Language::Python => python::parse_python_file(file_id, sf),
+        Language::Go => go::parse_go_file(file_id, sf),
+        Language::Rust => rust::parse_rust_file(file_id, sf),
+        Language::Java => java::parse_java_file(file_id, sf),
+        Language::Typescript => typescript::parse_typescript_file(file_id, sf),
+        Language::Javascript => javascript::parse_javascript_file(file_id, sf),
+    };
+
+    parsed.map_err(|source| ParseError::File {
+        file_path: sf.path.clone(),
+        source,
+    })
+}
+
+/// Options for [`parse_source_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Give up with [`ParseError::Timeout`] once parsing runs longer than this.
+    ///
+    /// Guards batch runs against pathological inputs such as huge generated
+    /// or minified files. `None` parses without a time budget.
+    pub timeout: Option<Duration>,
+}
+
+/// Parse a `SourceFile` like [`parse_source`], honoring `options`.
+pub fn parse_source_with_options(
+    file_id: FileId,
+    sf: &SourceFile,
+    options: &ParseOptions,
+) -> Result<ParsedFile, ParseError> {
+    match options.timeout {
+        Some(timeout) => parse_with_timeout(file_id, sf, timeout),
+        None => parse_source(file_id, sf),
+    }
+}
+
+/// Parse with a progress callback that cancels once `timeout` has elapsed.
+fn parse_with_timeout(
+    file_id: FileId,
+    sf: &SourceFile,
+    timeout: Duration,
+) -> Result<ParsedFile, ParseError> {
+    let grammar = grammar_for(sf);
+
+    let mut parser = Parser::new();
+    parser
+        .set_language(&grammar)
+        .map_err(|err| ParseError::File {
             file_path: sf.path.clone(),
-            source,
-        }),
-        Language::Rust => rust::parse_rust_file(file_id, sf).map_err(|source| ParseError::File {
+            source: err.into(),
+        })?;
+
+    let source = Arc::new(sf.content.clone());
+    let bytes = source.as_bytes();
+    let deadline = Instant::now() + timeout;
+    let mut past_deadline = |_: &ParseState| Instant::now() >= deadline;
+    let tree = parser
+        .parse_with_options(
+            &mut |offset, _| bytes.get(offset..).unwrap_or_default(),
+            None,
+            Some(TsParseOptions::new().progress_callback(&mut past_deadline)),
+        )
+        .ok_or_else(|| ParseError::Timeout {
+            file_id,
             file_path: sf.path.clone(),
-            source,
-        }),
-        Language::Typescript => {
-            typescript::parse_typescript_file(file_id, sf).map_err(|source| ParseError::File {
-                file_path: sf.path.clone(),
-                source,
-            })
-        }
-        _ => Err(ParseError::File {
+            timeout_ms: timeout.as_millis() as u64,
+        })?;
+
+    Ok(ParsedFile {
+        file_id,
+        path: sf.path.clone(),
+        language: sf.language,
+        source,
+        tree,
+        line_starts: Default::default(),
+    })
+}
+
+/// Parse a `SourceFile`, rejecting files that tree-sitter could only partially parse.
+///
+/// Returns [`ParseError::SyntaxErrors`] with the location of every ERROR/MISSING
+/// node instead of a silently degraded tree.
+pub fn parse_source_strict(file_id: FileId, sf: &SourceFile) -> Result<ParsedFile, ParseError> {
+    let parsed = parse_source(file_id, sf)?;
+    if parsed.has_errors() {
+        return Err(ParseError::SyntaxErrors {
             file_path: sf.path.clone(),
-            source: anyhow::anyhow!("parsing not yet implemented for this language"),
-        }),
+            errors: parsed.error_ranges(),
+        });
     }
+    Ok(parsed)
+}
+
+/// Parse a `SourceFile` into a `ParsedFile`.
+///
+/// Same as [`parse_source`]; kept for existing callers.
+pub fn parse_source_file(file_id: FileId, sf: &SourceFile) -> Result<ParsedFile, ParseError> {
+    parse_source(file_id, sf)
+}
+
+/// Parse a `SourceFile`, reusing the cached tree if the same content was
+/// parsed before in the same language.
+///
+/// On a miss the file is parsed with [`parse_source`] and its tree stored in
+/// `cache`. Errors are not cached.
+pub fn parse_source_cached(
+    cache: &mut ParseCache,
+    file_id: FileId,
+    sf: &SourceFile,
+) -> Result<ParsedFile, ParseError> {
+    if let Some(parsed) = cache.get(file_id, sf) {
+        return Ok(parsed);
+    }
+    let parsed = parse_source(file_id, sf)?;
+    cache.insert(&parsed);
+    Ok(parsed)
+}
+
+/// Lazily read, parse and analyze files, one per iteration step.
+///
+/// Nothing is read until the iterator is advanced, so callers scanning a large
+/// repository can process each file's semantics and drop it before the next.
+/// Paths whose extension maps to no supported language are skipped. Each
+/// file goes through [`crate::analyze_file`], so files with syntax errors
+/// yield [`ParseError::SyntaxErrors`]. Each file's `FileId` is its position
+/// in `paths`.
+pub fn iter_sources(
+    paths: impl IntoIterator<Item = PathBuf>,
+) -> impl Iterator<Item = Result<Box<dyn CommonSemantics>, EngineError>> {
+    paths.into_iter().enumerate().filter_map(|(idx, path)| {
+        let language = Language::from_path(&path.to_string_lossy())?;
+        is_supported(language).then(|| analyze_path(FileId(idx as u64), &path))
+    })
+}
+
+fn analyze_path(file_id: FileId, path: &Path) -> Result<Box<dyn CommonSemantics>, EngineError> {
+    let sf = SourceFile::from_path(path).map_err(|err| ParseError::File {
+        file_path: path.to_string_lossy().into_owned(),
+        source: err.into(),
+    })?;
+    crate::analyze_file(file_id, &sf)
 }
 
 #[cfg(test)]
@@ -134,10 +299,14 @@ if __name__ == "__main__":
     }
 
     #[test]
-    fn test_parse_unsupported_language_java() {
+    fn test_parse_java_file_success() {
         let sf = make_source_file("Test.java", Language::Java, "public class Test {}");
         let result = parse_source_file(FileId(6), &sf);
-        assert!(result.is_err());
+        assert!(result.is_ok());
+        let parsed = result.unwrap();
+        assert_eq!(parsed.file_id, FileId(6));
+        assert_eq!(parsed.path, "Test.java");
+        assert_eq!(parsed.language, Language::Java);
     }
 
     #[test]
@@ -152,10 +321,11 @@ if __name__ == "__main__":
     }
 
     #[test]
-    fn test_parse_unsupported_language_javascript() {
+    fn test_parse_javascript_file_success() {
         let sf = make_source_file("test.js", Language::Javascript, "const x = 1;");
-        let result = parse_source_file(FileId(8), &sf);
-        assert!(result.is_err());
+        let parsed = parse_source_file(FileId(8), &sf).expect("javascript should parse");
+        assert_eq!(parsed.language, Language::Javascript);
+        assert!(!parsed.has_errors());
     }
 
     #[test]
@@ -202,4 +372,142 @@ emoji = "🎉🚀"
         assert!(result.is_ok());
         assert_eq!(result.unwrap().path, path);
     }
+
+    // ==================== parse_source dispatch ====================
+
+    #[test]
+    fn test_parse_source_dispatches_supported_languages() {
+        let cases = [
+            (
+                "main.py",
+                Language::Python,
+                "def main():\n    pass\n",
+                "module",
+            ),
+            ("main.go", Language::Go, "package main\n", "source_file"),
+            ("main.rs", Language::Rust, "fn main() {}", "source_file"),
+            ("Main.java", Language::Java, "class Main {}", "program"),
+            ("main.ts", Language::Typescript, "const x = 1;", "program"),
+            ("main.js", Language::Javascript, "const x = 1;", "program"),
+        ];
+
+        for (path, language, content, root_kind) in cases {
+            let sf = make_source_file(path, language, content);
+            let parsed = parse_source(FileId(1), &sf).expect("parsing should succeed");
+            assert_eq!(parsed.language, language);
+            assert_eq!(parsed.path, path);
+            assert_eq!(parsed.tree.root_node().kind(), root_kind);
+        }
+    }
+
+    #[test]
+    fn test_parse_source_strict_rejects_syntax_errors() {
+        let sf = make_source_file("broken.py", Language::Python, "def broken(:\n    return\n");
+        assert!(parse_source(FileId(1), &sf).is_ok());
+
+        match parse_source_strict(FileId(1), &sf) {
+            Err(ParseError::SyntaxErrors { file_path, errors }) => {
+                assert_eq!(file_path, "broken.py");
+                assert!(!errors.is_empty());
+            }
+            other => panic!("expected SyntaxErrors, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_source_strict_accepts_valid_source() {
+        let sf = make_source_file("main.go", Language::Go, "package main\n\nfunc main() {}\n");
+        let parsed = parse_source_strict(FileId(1), &sf).expect("valid source should parse");
+        assert!(!parsed.has_errors());
+    }
+
+    #[test]
+    fn test_parse_source_with_timeout() {
+        let options = |timeout| ParseOptions {
+            timeout: Some(timeout),
+        };
+
+        let sf = make_source_file("main.go", Language::Go, "package main\n\nfunc main() {}\n");
+        let parsed = parse_source_with_options(FileId(1), &sf, &options(Duration::from_secs(10)))
+            .expect("small file should parse in time");
+        assert_eq!(parsed.tree.root_node().kind(), "source_file");
+
+        let huge = "x = [1, 2, 3]\n".repeat(50_000);
+        let sf = make_source_file("generated.py", Language::Python, &huge);
+        match parse_source_with_options(FileId(2), &sf, &options(Duration::ZERO)) {
+            Err(err @ ParseError::Timeout { .. }) => {
+                assert!(err.to_string().contains("generated.py (file id 2)"));
+            }
+            other => panic!("expected Timeout, got {other:?}"),
+        }
+        let parsed = parse_source_with_options(FileId(2), &sf, &ParseOptions::default())
+            .expect("no timeout by default");
+        assert!(!parsed.has_errors());
+    }
+
+    #[test]
+    fn iter_sources_analyzes_supported_files_lazily() {
+        let dir = std::env::temp_dir().join(format!("unfault-core-iter-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = [
+            ("app.py", "def handler():\n    pass\n"),
+            ("main.go", "package main\n\nfunc main() {}\n"),
+            ("lib.rs", "pub fn run() {}\n"),
+            ("index.ts", "export function start() {}\n"),
+            ("app.js", "export function render() {}\n"),
+            ("README.md", "# docs\n"),
+            ("app.rb", "def hello; end\n"),
+            ("broken.py", "def broken(:\n    pass\n"),
+        ];
+        let mut paths: Vec<PathBuf> = files
+            .iter()
+            .map(|(name, content)| {
+                let path = dir.join(name);
+                std::fs::write(&path, content).unwrap();
+                path
+            })
+            .collect();
+        paths.push(dir.join("missing.py"));
+
+        let results: Vec<_> = iter_sources(paths).collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // README.md and app.rb are skipped; broken.py has a syntax error and
+        // missing.py fails to read
+        assert_eq!(results.len(), 7);
+        let analyzed: Vec<_> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+        assert_eq!(analyzed.len(), 5);
+        assert_eq!(analyzed[1].language(), Language::Go);
+        assert_eq!(analyzed[1].file_id(), FileId(1));
+        assert_eq!(analyzed[4].language(), Language::Javascript);
+        assert_eq!(analyzed[4].functions()[0].name, "render");
+        assert!(matches!(
+            results[5],
+            Err(EngineError::Parse(ParseError::SyntaxErrors { .. }))
+        ));
+        assert!(matches!(
+            results.last(),
+            Some(Err(EngineError::Parse(ParseError::File { .. })))
+        ));
+    }
+
+    #[test]
+    fn parse_source_cached_reuses_tree_for_unchanged_content() {
+        let mut cache = ParseCache::new();
+        let sf = make_source_file("app.py", Language::Python, "def f():\n    return 1\n");
+
+        let first = parse_source_cached(&mut cache, FileId(1), &sf).unwrap();
+        let second = parse_source_cached(&mut cache, FileId(2), &sf).unwrap();
+        assert_eq!(cache.parses(), 1);
+        assert_eq!(second.file_id, FileId(2));
+        assert_eq!(
+            first.tree.root_node().to_sexp(),
+            second.tree.root_node().to_sexp()
+        );
+
+        let edited = make_source_file("app.py", Language::Python, "def f():\n    return 2\n");
+        parse_source_cached(&mut cache, FileId(1), &edited).unwrap();
+        assert_eq!(cache.parses(), 2);
+        assert_eq!(cache.len(), 1);
+    }
 }
@@ -6,7 +6,7 @@ use tree_sitter::{Language as TsLanguage, Parser};
 use crate::parse::ast::{FileId, ParsedFile};
 use crate::types::context::{Language, SourceFile};
 
-fn python_language() -> TsLanguage {
+pub(crate) fn python_language() -> TsLanguage {
     // Modern tree-sitter crate exposes LANGUAGE directly
     tree_sitter_python::LANGUAGE.into()
 }
@@ -27,6 +27,7 @@ pub fn parse_python_file(file_id: FileId, sf: &SourceFile) -> Result<ParsedFile>
         language: Language::Python,
         source,
         tree,
+        line_starts: Default::default(),
     })
 }
 
@@ -11,7 +11,7 @@ use tree_sitter::{Language as TsLanguage, Parser};
 use crate::parse::ast::{FileId, ParsedFile};
 use crate::types::context::{Language, SourceFile};
 
-fn rust_language() -> TsLanguage {
+pub(crate) fn rust_language() -> TsLanguage {
     tree_sitter_rust::LANGUAGE.into()
 }
 
@@ -31,6 +31,7 @@ pub fn parse_rust_file(file_id: FileId, sf: &SourceFile) -> Result<ParsedFile> {
         language: Language::Rust,
         source,
         tree,
+        line_starts: Default::default(),
     })
 }
 
@@ -6,12 +6,12 @@ use tree_sitter::{Language as TsLanguage, Parser};
 use crate::parse::ast::{FileId, ParsedFile};
 use crate::types::context::{Language, SourceFile};
 
-fn typescript_language() -> TsLanguage {
+pub(crate) fn typescript_language() -> TsLanguage {
     // Modern tree-sitter crate exposes LANGUAGE directly
     tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()
 }
 
-fn tsx_language() -> TsLanguage {
+pub(crate) fn tsx_language() -> TsLanguage {
     tree_sitter_typescript::LANGUAGE_TSX.into()
 }
 
@@ -39,6 +39,7 @@ pub fn parse_typescript_file(file_id: FileId, sf: &SourceFile) -> Result<ParsedF
         language: Language::Typescript,
         source,
         tree,
+        line_starts: Default::default(),
     })
 }
 
@@ -0,0 +1,120 @@
+//! Rendering helpers for displaying findings.
+//!
+//! Findings carry a [`CommonLocation`]; these helpers turn one back into text
+//! a human can read next to the message, in the style of compiler diagnostics.
+
+use crate::semantics::common::CommonLocation;
+
+/// Render a compiler-style snippet of `source` around `loc`.
+///
+/// Shows the lines spanned by `loc` plus `context_lines` on either side, each
+/// prefixed with its 1-based line number, and underlines the location with
+/// carets. A span covering several lines is underlined to the end of its
+/// first line and marked with `...`.
+///
+/// ```text
+///   |
+/// 3 | def fetch():
+/// 4 |     return requests.get(url)
+///   |            ^^^^^^^^^^^^^^^^^
+/// 5 |
+/// ```
+pub fn code_frame(source: &str, loc: &CommonLocation, context_lines: usize) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+
+    let start = (loc.line.max(1) as usize - 1).min(lines.len() - 1);
+    let span = source.get(loc.start_byte..loc.end_byte).unwrap_or_default();
+    let end = (start + span.matches('\n').count()).min(lines.len() - 1);
+    let first = start.saturating_sub(context_lines);
+    let last = (end + context_lines).min(lines.len() - 1);
+    let width = (last + 1).to_string().len();
+
+    let start_line = lines[start];
+    let line_chars = start_line.chars().count();
+    let col = (loc.column.max(1) as usize - 1).min(line_chars);
+    // Keep tabs so the carets line up with the code above them
+    let padding: String = start_line
+        .chars()
+        .take(col)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let underline_len = if end > start {
+        line_chars - col
+    } else {
+        span.chars().count()
+    };
+    let mut underline = format!("{}{}", padding, "^".repeat(underline_len.max(1)));
+    if end > start {
+        underline.push_str(" ...");
+    }
+
+    let mut frame = format!("{:>width$} |\n", "");
+    for (idx, text) in lines.iter().enumerate().take(last + 1).skip(first) {
+        let row = format!("{:>width$} | {}", idx + 1, text);
+        frame.push_str(row.trim_end());
+        frame.push('\n');
+        if idx == start {
+            frame.push_str(&format!("{:>width$} | {}\n", "", underline));
+        }
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ast::FileId;
+
+    /// Location of the first occurrence of `needle` in `source`.
+    fn location_of(source: &str, needle: &str) -> CommonLocation {
+        let start_byte = source.find(needle).expect("needle is in source");
+        let before = &source[..start_byte];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        CommonLocation {
+            file_id: FileId(1),
+            line: before.matches('\n').count() as u32 + 1,
+            column: source[line_start..start_byte].chars().count() as u32 + 1,
+            start_byte,
+            end_byte: start_byte + needle.len(),
+        }
+    }
+
+    #[test]
+    fn frame_shows_line_number_and_caret_under_location() {
+        let source =
+            "import requests\n\ndef fetch(url):\n    return requests.get(url)\n\nfetch('x')\n";
+        let loc = location_of(source, "requests.get(url)");
+
+        let frame = code_frame(source, &loc, 1);
+        assert_eq!(
+            frame,
+            "  |\n3 | def fetch(url):\n4 |     return requests.get(url)\n  |            ^^^^^^^^^^^^^^^^^\n5 |\n"
+        );
+    }
+
+    #[test]
+    fn multi_line_span_underlines_first_line_and_marks_continuation() {
+        let source = "x = 1\nresult = call(\n    a,\n    b,\n)\ny = 2\n";
+        let loc = location_of(source, "call(\n    a,\n    b,\n)");
+
+        let frame = code_frame(source, &loc, 0);
+        assert!(frame.contains("2 | result = call(\n  |          ^^^^^ ...\n"));
+        assert!(frame.contains("5 | )\n"));
+        assert!(!frame.contains("y = 2"));
+        assert!(!frame.contains("x = 1"));
+    }
+
+    #[test]
+    fn gutter_widens_for_multi_digit_line_numbers() {
+        let source: String = (1..=12).map(|i| format!("line{i}\n")).collect();
+        let loc = location_of(&source, "line10");
+
+        let frame = code_frame(&source, &loc, 2);
+        assert!(frame.starts_with("   |\n 8 | line8\n"));
+        assert!(frame.contains("10 | line10\n   | ^^^^^^\n"));
+        assert!(frame.ends_with("12 | line12\n"));
+    }
+}
@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::CommonLocation;
+use super::{CommonLocation, Confidence};
 
 /// Async runtime/framework classification
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -23,6 +23,9 @@ pub enum AsyncRuntime {
     Tokio,
     AsyncStd,
     Smol,
+    Glommio,
+    /// `futures::executor` (e.g. `block_on`, `ThreadPool`)
+    FuturesExecutor,
 
     // TypeScript/JavaScript
     PromiseNative,
@@ -49,6 +52,8 @@ impl AsyncRuntime {
             Self::Tokio => "tokio",
             Self::AsyncStd => "async-std",
             Self::Smol => "smol",
+            Self::Glommio => "glommio",
+            Self::FuturesExecutor => "futures::executor",
             Self::PromiseNative => "Promise",
             Self::BluesbirdPromise => "Bluebird",
             Self::CompletableFuture => "CompletableFuture",
@@ -68,6 +73,8 @@ impl AsyncRuntime {
             Self::Goroutine => "context.WithTimeout()",
             Self::Tokio => "tokio::time::timeout()",
             Self::AsyncStd => "async_std::future::timeout()",
+            Self::Smol => "smol::Timer with FutureExt::or()",
+            Self::Glommio => "glommio::timer::timeout()",
             Self::PromiseNative => "AbortController with signal",
             Self::CompletableFuture => ".orTimeout() or .completeOnTimeout()",
             Self::ProjectReactor => ".timeout()",
@@ -202,9 +209,20 @@ pub struct AsyncOperation {
     /// Bound/semaphore limit (if determinable)
     pub bound_limit: Option<u32>,
 
+    /// Whether this operation occurs inside a loop
+    pub in_loop: bool,
+
+    /// Whether this operation occurs inside a comprehension or generator expression
+    #[serde(default)]
+    pub in_comprehension: bool,
+
     /// Whether this operation is properly cleaned up on shutdown
     pub has_cleanup: bool,
 
+    /// How certain the detector is about this operation
+    #[serde(default)]
+    pub confidence: Confidence,
+
     /// Full text of the operation
     pub operation_text: String,
 
@@ -346,7 +364,10 @@ mod tests {
             cancellation_handling: None,
             is_bounded: false,
             bound_limit: None,
+            in_loop: false,
+            in_comprehension: false,
             has_cleanup: false,
+            confidence: Confidence::High,
             operation_text: "asyncio.create_task(coro())".into(),
             location: make_location(),
             enclosing_function: Some("main".into()),
@@ -371,7 +392,10 @@ mod tests {
             cancellation_handling: None,
             is_bounded: true,
             bound_limit: Some(10),
+            in_loop: true,
+            in_comprehension: false,
             has_cleanup: true,
+            confidence: Confidence::High,
             operation_text: "tokio::spawn(async { ... })".into(),
             location: make_location(),
             enclosing_function: Some("process".into()),
@@ -424,4 +448,31 @@ mod tests {
                 .contains("context.WithTimeout")
         );
     }
+
+    #[test]
+    fn rust_runtimes_have_first_class_variants() {
+        for runtime in [
+            AsyncRuntime::Smol,
+            AsyncRuntime::Glommio,
+            AsyncRuntime::FuturesExecutor,
+        ] {
+            let label = match &runtime {
+                AsyncRuntime::Smol => "smol",
+                AsyncRuntime::Glommio => "glommio",
+                AsyncRuntime::FuturesExecutor => "futures::executor",
+                other => panic!("unexpected runtime {other:?}"),
+            };
+            assert_eq!(runtime.as_str(), label);
+
+            let json = serde_json::to_string(&runtime).unwrap();
+            assert_eq!(
+                serde_json::from_str::<AsyncRuntime>(&json).unwrap(),
+                runtime
+            );
+        }
+
+        // Previously serialized custom runtimes still deserialize
+        let other: AsyncRuntime = serde_json::from_str(r#"{"Other":"embassy"}"#).unwrap();
+        assert_eq!(other, AsyncRuntime::Other("embassy".to_string()));
+    }
 }
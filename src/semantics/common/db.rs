@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::CommonLocation;
+use super::{CommonLocation, Confidence};
 
 /// Database/ORM library classification
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -44,6 +44,12 @@ pub enum DbLibrary {
     Mybatis,
     Jooq,
 
+    // NoSQL (any language)
+    /// MongoDB drivers and ODMs (PyMongo, Motor, Mongoose, mongo-go-driver)
+    Mongo,
+    /// Redis clients (redis-py, ioredis, go-redis)
+    Redis,
+
     // Generic
     Other(String),
 }
@@ -75,6 +81,8 @@ impl DbLibrary {
             Self::Jdbc => "JDBC",
             Self::Mybatis => "MyBatis",
             Self::Jooq => "jOOQ",
+            Self::Mongo => "MongoDB",
+            Self::Redis => "Redis",
             Self::Other(s) => s,
         }
     }
@@ -137,6 +145,14 @@ pub enum DbOperationType {
     RelationshipAccess,
     /// Raw SQL execution
     RawSql,
+    /// Document query (`find`, `findOne`)
+    Find,
+    /// Aggregation pipeline
+    Aggregate,
+    /// Cache/key-value read (`GET`, `HGET`)
+    CacheGet,
+    /// Cache/key-value write (`SET`, `HSET`, `INCR`)
+    CacheSet,
     /// Unknown operation
     Unknown,
 }
@@ -154,10 +170,65 @@ impl DbOperationType {
             Self::TransactionRollback => "ROLLBACK",
             Self::RelationshipAccess => "RELATIONSHIP",
             Self::RawSql => "RAW_SQL",
+            Self::Find => "FIND",
+            Self::Aggregate => "AGGREGATE",
+            Self::CacheGet => "CACHE_GET",
+            Self::CacheSet => "CACHE_SET",
             Self::Unknown => "UNKNOWN",
         }
     }
 
+    /// Infer the operation type from the leading keyword of a SQL statement.
+    ///
+    /// Statements that are not plain SELECT/INSERT/UPDATE/DELETE (DDL, CTEs,
+    /// stored procedure calls) are reported as raw SQL.
+    pub fn from_sql(sql: &str) -> Self {
+        let keyword = sql
+            .trim_start()
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .unwrap_or_default();
+        match keyword.to_ascii_uppercase().as_str() {
+            "SELECT" => Self::Select,
+            "INSERT" => Self::Insert,
+            "UPDATE" => Self::Update,
+            "DELETE" => Self::Delete,
+            _ => Self::RawSql,
+        }
+    }
+
+    /// Infer the operation type from a document-store driver method.
+    ///
+    /// Case and underscores are ignored, so `find_one`, `findOne` and
+    /// `FindOne` all map to [`DbOperationType::Find`].
+    pub fn from_document_method(method: &str) -> Option<Self> {
+        let method = method.replace('_', "").to_ascii_lowercase();
+        match method.as_str() {
+            "find" | "findone" | "findbyid" | "countdocuments" | "distinct" => Some(Self::Find),
+            "aggregate" => Some(Self::Aggregate),
+            "insertone" | "insertmany" => Some(Self::Insert),
+            "updateone" | "updatemany" | "replaceone" | "findoneandupdate"
+            | "findbyidandupdate" => Some(Self::Update),
+            "deleteone" | "deletemany" | "findoneanddelete" | "findbyidanddelete" => {
+                Some(Self::Delete)
+            }
+            _ => None,
+        }
+    }
+
+    /// Infer the operation type from a Redis client command method
+    /// (`get`, `hset`, `Del`, ...).
+    pub fn from_cache_method(method: &str) -> Option<Self> {
+        match method.to_ascii_lowercase().as_str() {
+            "get" | "mget" | "hget" | "hgetall" | "exists" | "ttl" => Some(Self::CacheGet),
+            "set" | "setex" | "setnx" | "mset" | "hset" | "incr" | "decr" | "expire" => {
+                Some(Self::CacheSet)
+            }
+            "del" | "delete" | "hdel" | "unlink" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+
     /// Check if this is a mutating operation
     pub fn is_mutating(&self) -> bool {
         matches!(self, Self::Insert | Self::Update | Self::Delete)
@@ -215,9 +286,20 @@ pub struct DbOperation {
     /// The model/table being operated on
     pub model_name: Option<String>,
 
+    /// Whether raw SQL is built by concatenating or interpolating non-literal values
+    pub has_string_interpolation: bool,
+
     /// The relationship field being accessed (for lazy loading)
     pub relationship_field: Option<String>,
 
+    /// Literal SQL passed to the query (if statically determinable)
+    #[serde(default)]
+    pub sql: Option<String>,
+
+    /// How certain the detector is that this is a database call
+    #[serde(default)]
+    pub confidence: Confidence,
+
     /// Full text of the operation
     pub operation_text: String,
 
@@ -239,8 +321,13 @@ impl DbOperation {
     pub fn is_potential_n_plus_one(&self) -> bool {
         // A query/relationship access inside a loop without eager loading
         (self.in_loop || self.in_iteration)
-            && (self.operation_type == DbOperationType::Select
-                || self.operation_type == DbOperationType::RelationshipAccess)
+            && matches!(
+                self.operation_type,
+                DbOperationType::Select
+                    | DbOperationType::Find
+                    | DbOperationType::CacheGet
+                    | DbOperationType::RelationshipAccess
+            )
             && self.eager_loading.is_none()
     }
 
@@ -255,12 +342,18 @@ impl DbOperation {
                     | DbOperationType::Delete
                     | DbOperationType::Connect
                     | DbOperationType::RawSql
+                    | DbOperationType::Find
+                    | DbOperationType::Aggregate
             )
     }
 
-    /// Check if this mutating operation should be in a transaction
+    /// Check if this mutating operation should be in a transaction.
+    ///
+    /// Single-document and single-key NoSQL writes are atomic on their own.
     pub fn needs_transaction(&self) -> bool {
-        self.operation_type.is_mutating() && !self.in_transaction
+        self.operation_type.is_mutating()
+            && !self.in_transaction
+            && !matches!(self.library, DbLibrary::Mongo | DbLibrary::Redis)
     }
 
     /// Get suggested timeout based on library
@@ -285,6 +378,8 @@ impl DbOperation {
             DbLibrary::TypeOrm => "connectTimeoutMS in options",
             DbLibrary::Jpa | DbLibrary::Hibernate => "@QueryHints with timeout",
             DbLibrary::Jdbc => "setQueryTimeout() on Statement",
+            DbLibrary::Mongo => "maxTimeMS on the query or socketTimeoutMS on the client",
+            DbLibrary::Redis => "socket_timeout / commandTimeout on the client",
             _ => "timeout parameter",
         }
     }
@@ -354,6 +449,79 @@ impl Default for ConnectionPoolConfig {
     }
 }
 
+/// Words that may follow `redis` in the name of a client variable.
+const CLIENT_SUFFIXES: &[&str] = &["client", "cli", "conn", "connection", "pool"];
+
+/// Check if a call receiver names a Redis client: `redis`, `self.redis`,
+/// `redis_client`, `redisConn` or go-redis's conventional `rdb`.
+///
+/// Only whole identifier words count, and nothing but a client suffix may
+/// follow `redis`, so `redis_config.get("host")` is a dict lookup.
+pub(crate) fn is_redis_receiver(receiver: &str) -> bool {
+    let words = identifier_words(last_component(receiver).0);
+    words == ["rdb"]
+        || words.iter().rposition(|w| w == "redis").is_some_and(|at| {
+            words[at + 1..]
+                .iter()
+                .all(|w| CLIENT_SUFFIXES.contains(&w.as_str()))
+        })
+}
+
+/// Check if a call receiver names a MongoDB collection: `users_collection`,
+/// `coll`, `db.Collection("users")`, `db.users` or `db["users"]`.
+///
+/// Only whole identifier words count, so `feedback.find` and
+/// `collector.Find` are not collections.
+pub(crate) fn is_mongo_collection_receiver(receiver: &str) -> bool {
+    let is_database = |ident: &str| {
+        identifier_words(ident)
+            .last()
+            .is_some_and(|w| matches!(w.as_str(), "db" | "database" | "mongo"))
+    };
+
+    let (last, indexed) = last_component(receiver);
+    let named_collection = identifier_words(last)
+        .last()
+        .is_some_and(|w| matches!(w.as_str(), "collection" | "coll"));
+    let database_attribute = receiver
+        .rsplit_once('.')
+        .is_some_and(|(parent, _)| is_database(last_component(parent).0));
+
+    named_collection || (indexed && is_database(last)) || database_attribute
+}
+
+/// Identifier of the last `.`-separated part of a receiver, without call
+/// arguments or subscripts, and whether it was subscripted.
+fn last_component(receiver: &str) -> (&str, bool) {
+    let last = receiver.rsplit('.').next().unwrap_or(receiver);
+    match last.find(['(', '[']) {
+        Some(at) => (&last[..at], last[at..].starts_with('[')),
+        None => (last, false),
+    }
+}
+
+/// Lowercased words of an identifier, split on `_` and camelCase boundaries.
+fn identifier_words(ident: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut prev_lower = false;
+    for c in ident.chars() {
+        if c == '_' {
+            prev_lower = false;
+            words.push(String::new());
+            continue;
+        }
+        if (c.is_ascii_uppercase() && prev_lower) || words.is_empty() {
+            words.push(String::new());
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        if let Some(word) = words.last_mut() {
+            word.push(c.to_ascii_lowercase());
+        }
+    }
+    words.retain(|w| !w.is_empty());
+    words
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -369,6 +537,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn operation_type_from_sql_keyword() {
+        assert_eq!(
+            DbOperationType::from_sql("  select * from users"),
+            DbOperationType::Select
+        );
+        assert_eq!(
+            DbOperationType::from_sql("INSERT INTO users VALUES ($1)"),
+            DbOperationType::Insert
+        );
+        assert_eq!(
+            DbOperationType::from_sql("CREATE TABLE t (id int)"),
+            DbOperationType::RawSql
+        );
+        assert_eq!(DbOperationType::from_sql(""), DbOperationType::RawSql);
+    }
+
+    #[test]
+    fn operation_type_from_nosql_methods() {
+        assert_eq!(
+            DbOperationType::from_document_method("find_one"),
+            Some(DbOperationType::Find)
+        );
+        assert_eq!(
+            DbOperationType::from_document_method("FindOne"),
+            Some(DbOperationType::Find)
+        );
+        assert_eq!(
+            DbOperationType::from_document_method("aggregate"),
+            Some(DbOperationType::Aggregate)
+        );
+        assert_eq!(DbOperationType::from_document_method("save"), None);
+        assert_eq!(
+            DbOperationType::from_cache_method("Get"),
+            Some(DbOperationType::CacheGet)
+        );
+        assert_eq!(
+            DbOperationType::from_cache_method("setex"),
+            Some(DbOperationType::CacheSet)
+        );
+        assert_eq!(DbOperationType::from_cache_method("ping"), None);
+    }
+
+    #[test]
+    fn nosql_receivers_match_whole_words() {
+        assert!(is_redis_receiver("redis"));
+        assert!(is_redis_receiver("self.redis"));
+        assert!(is_redis_receiver("redis_client"));
+        assert!(is_redis_receiver("redisConn"));
+        assert!(is_redis_receiver("rdb"));
+        assert!(!is_redis_receiver("redis_config"));
+        assert!(!is_redis_receiver("self.redisUrl"));
+        assert!(!is_redis_receiver("predis"));
+
+        assert!(is_mongo_collection_receiver("users_collection"));
+        assert!(is_mongo_collection_receiver("coll"));
+        assert!(is_mongo_collection_receiver("usersColl"));
+        assert!(is_mongo_collection_receiver("db.users"));
+        assert!(is_mongo_collection_receiver("self.db.orders"));
+        assert!(is_mongo_collection_receiver(r#"db["users"]"#));
+        assert!(is_mongo_collection_receiver(
+            r#"client.Database("app").Collection("users")"#
+        ));
+        assert!(!is_mongo_collection_receiver("feedback"));
+        assert!(!is_mongo_collection_receiver("collector"));
+        assert!(!is_mongo_collection_receiver("collection_name"));
+        assert!(!is_mongo_collection_receiver("db"));
+    }
+
     #[test]
     fn db_library_is_orm() {
         assert!(DbLibrary::SqlAlchemy.is_orm());
@@ -391,7 +628,10 @@ mod tests {
             in_loop: true,
             in_iteration: false,
             model_name: Some("User".into()),
+            has_string_interpolation: false,
             relationship_field: None,
+            sql: None,
+            confidence: Confidence::High,
             operation_text: "session.query(Post).filter_by(user_id=user.id)".into(),
             location: make_location(),
             enclosing_function: Some("get_posts".into()),
@@ -414,7 +654,10 @@ mod tests {
             in_loop: true,
             in_iteration: false,
             model_name: Some("User".into()),
+            has_string_interpolation: false,
             relationship_field: None,
+            sql: None,
+            confidence: Confidence::High,
             operation_text: "session.query(User).options(joinedload(User.posts))".into(),
             location: make_location(),
             enclosing_function: Some("get_users".into()),
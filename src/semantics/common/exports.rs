@@ -0,0 +1,93 @@
+//! Common abstractions for the public symbols a file exports.
+//!
+//! Each language has its own export rules (`export` in TypeScript, `pub` in
+//! Rust, capitalization in Go, `__all__` in Python); this module gives them a
+//! shared shape for API-surface analysis.
+
+use serde::{Deserialize, Serialize};
+
+use super::CommonLocation;
+use super::functions::Visibility;
+
+/// Kind of exported symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExportKind {
+    /// Free function
+    Function,
+    /// Class, struct or enum
+    Class,
+    /// Interface, trait or type alias
+    Type,
+    /// Constant
+    Const,
+    /// Mutable module-level variable
+    Variable,
+}
+
+impl ExportKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Function => "function",
+            Self::Class => "class",
+            Self::Type => "type",
+            Self::Const => "const",
+            Self::Variable => "variable",
+        }
+    }
+}
+
+/// A symbol visible to other modules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedSymbol {
+    /// Symbol name
+    pub name: String,
+    /// What kind of symbol this is
+    pub kind: ExportKind,
+    /// How widely the symbol is visible (e.g. `Package` for Rust `pub(crate)`)
+    pub visibility: Visibility,
+    /// Location of the declaration
+    pub location: CommonLocation,
+}
+
+impl ExportedSymbol {
+    pub fn new(
+        name: impl Into<String>,
+        kind: ExportKind,
+        visibility: Visibility,
+        location: CommonLocation,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            visibility,
+            location,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ast::FileId;
+
+    #[test]
+    fn exported_symbol_new_sets_fields() {
+        let location = CommonLocation {
+            file_id: FileId(1),
+            line: 3,
+            column: 1,
+            start_byte: 0,
+            end_byte: 0,
+        };
+        let sym = ExportedSymbol::new(
+            "handler",
+            ExportKind::Function,
+            Visibility::Public,
+            location,
+        );
+        assert_eq!(sym.name, "handler");
+        assert_eq!(sym.kind, ExportKind::Function);
+        assert_eq!(sym.kind.as_str(), "function");
+        assert_eq!(sym.visibility, Visibility::Public);
+    }
+}
@@ -0,0 +1,100 @@
+//! Numeric profile of a file.
+//!
+//! A cheap summary of what a file contains, computed from the
+//! `CommonSemantics` accessors, for dashboards and quick comparisons.
+
+use serde::{Deserialize, Serialize};
+
+use super::CommonSemantics;
+
+/// Counts and averages describing a single file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileMetrics {
+    /// Function and method definitions
+    pub functions: usize,
+    /// Class, struct and enum definitions
+    pub classes: usize,
+    /// Imports and dependencies
+    pub imports: usize,
+    /// HTTP client calls
+    pub http_calls: usize,
+    /// Database operations
+    pub db_operations: usize,
+    /// Async/concurrent operations
+    pub async_operations: usize,
+    /// Total lines of code
+    pub lines_of_code: usize,
+    /// Mean cyclomatic complexity over all functions (0.0 without functions)
+    pub avg_function_complexity: f64,
+}
+
+impl FileMetrics {
+    /// Compute the metrics of a file.
+    pub fn from_semantics<S: CommonSemantics + ?Sized>(sem: &S) -> Self {
+        let functions = sem.functions();
+        let avg_function_complexity = if functions.is_empty() {
+            0.0
+        } else {
+            let total: u32 = functions.iter().map(|f| f.cyclomatic_complexity).sum();
+            f64::from(total) / functions.len() as f64
+        };
+
+        Self {
+            functions: functions.len(),
+            classes: sem.class_count(),
+            imports: sem.imports().len(),
+            http_calls: sem.http_calls().len(),
+            db_operations: sem.db_operations().len(),
+            async_operations: sem.async_operations().len(),
+            lines_of_code: sem.line_count(),
+            avg_function_complexity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ast::FileId;
+    use crate::parse::python::parse_python_file;
+    use crate::semantics::python::model::PyFileSemantics;
+    use crate::types::context::{Language, SourceFile};
+
+    #[test]
+    fn metrics_count_python_file_contents() {
+        let sf = SourceFile {
+            path: "app/users.py".to_string(),
+            language: Language::Python,
+            content: r#"import asyncio
+import requests
+
+class UserService:
+    def load(self, session, user_id):
+        if user_id:
+            return session.query(User).first()
+        return None
+
+async def notify(user_id):
+    asyncio.create_task(send(user_id))
+    return requests.get("https://api.example.com/notify", timeout=5)
+"#
+            .to_string(),
+        };
+        let parsed = parse_python_file(FileId(1), &sf).expect("parsing should succeed");
+        let mut sem = PyFileSemantics::from_parsed(&parsed);
+        sem.analyze_frameworks(&parsed)
+            .expect("framework analysis should succeed");
+
+        let metrics = sem.metrics();
+        assert_eq!(metrics.functions, 2);
+        assert_eq!(metrics.classes, 1);
+        assert_eq!(metrics.imports, 2);
+        assert_eq!(metrics.http_calls, 1);
+        // `session.query(User)` and the chained `.first()` are both reported
+        assert_eq!(metrics.db_operations, 2);
+        assert_eq!(metrics.async_operations, 1);
+        assert_eq!(metrics.lines_of_code, 12);
+        // load has one `if` (2), notify is straight-line (1)
+        assert_eq!(metrics.avg_function_complexity, 1.5);
+    }
+}
@@ -6,6 +6,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::CommonLocation;
+use crate::parse::ast::FileId;
 
 /// A function call site within a function body.
 ///
@@ -80,7 +81,7 @@ impl FunctionCall {
 pub enum Visibility {
     /// Public (exported, pub)
     Public,
-    /// Private (unexported, not pub)
+    /// Private (unexported, not pub, Python `__name` mangling)
     Private,
     /// Protected (Java/C++, Python _prefix convention)
     Protected,
@@ -125,6 +126,10 @@ impl Default for FunctionKind {
     }
 }
 
+fn default_metric() -> u32 {
+    1
+}
+
 /// A language-agnostic function parameter
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionParam {
@@ -138,6 +143,32 @@ pub struct FunctionParam {
     pub is_variadic: bool,
     /// Whether this is a keyword-only parameter
     pub is_keyword_only: bool,
+    /// Whether this is a positional-only parameter (before `/` in Python)
+    #[serde(default)]
+    pub is_positional_only: bool,
+    /// Whether callers may omit this argument (`x?: T` or a default value)
+    #[serde(default)]
+    pub is_optional: bool,
+    /// Whether the parameter is borrowed (`&T`, `&self`)
+    #[serde(default)]
+    pub is_reference: bool,
+    /// Whether the parameter can be mutated (`&mut T`, `&mut self`, `mut x`)
+    #[serde(default)]
+    pub is_mutable: bool,
+    /// How a method receiver takes `self`; `None` for other parameters
+    #[serde(default)]
+    pub self_kind: Option<SelfKind>,
+}
+
+/// How a method receiver takes `self`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelfKind {
+    /// `self` or `mut self`, taking ownership
+    Value,
+    /// `&self`
+    Ref,
+    /// `&mut self`
+    RefMut,
 }
 
 impl FunctionParam {
@@ -148,6 +179,11 @@ impl FunctionParam {
             default_value: None,
             is_variadic: false,
             is_keyword_only: false,
+            is_positional_only: false,
+            is_optional: false,
+            is_reference: false,
+            is_mutable: false,
+            self_kind: None,
         }
     }
 
@@ -158,6 +194,12 @@ impl FunctionParam {
 
     pub fn with_default(mut self, default: impl Into<String>) -> Self {
         self.default_value = Some(default.into());
+        self.is_optional = true;
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.is_optional = true;
         self
     }
 
@@ -166,6 +208,16 @@ impl FunctionParam {
         self
     }
 
+    pub fn keyword_only(mut self) -> Self {
+        self.is_keyword_only = true;
+        self
+    }
+
+    pub fn positional_only(mut self) -> Self {
+        self.is_positional_only = true;
+        self
+    }
+
     /// Check if this parameter has a default value
     pub fn has_default(&self) -> bool {
         self.default_value.is_some()
@@ -235,6 +287,20 @@ impl FunctionDecorator {
     }
 }
 
+/// Stable identity of a function definition within a session.
+///
+/// Names alone collide across classes and overloads; the file and start
+/// offset disambiguate, so ids are safe as map keys and call-graph nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FunctionId {
+    /// File defining the function
+    pub file_id: FileId,
+    /// Name qualified with the owning class, e.g. `Client.send`
+    pub qualified_name: String,
+    /// Start byte offset of the definition
+    pub start_byte: usize,
+}
+
 /// A language-agnostic function definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDef {
@@ -253,6 +319,10 @@ pub struct FunctionDef {
     /// Parameters
     pub params: Vec<FunctionParam>,
 
+    /// Generic type parameters as written, bounds included (`T: Clone`, `T any`)
+    #[serde(default)]
+    pub type_params: Vec<String>,
+
     /// Return type annotation (if present)
     pub return_type: Option<String>,
 
@@ -262,6 +332,25 @@ pub struct FunctionDef {
     /// Class name (if this is a method)
     pub class_name: Option<String>,
 
+    /// Trait or interface this method implements (e.g. `Display` for
+    /// `impl Display for Foo`), `None` for inherent methods
+    #[serde(default)]
+    pub trait_name: Option<String>,
+
+    /// Name of the function this one is nested in (inner `def`, closure,
+    /// callback), `None` for top-level functions and methods
+    #[serde(default)]
+    pub parent_function: Option<String>,
+
+    /// Cyclomatic complexity: 1 plus the number of decision points
+    /// (branches, loops, cases, catch handlers, ternaries, `&&`/`||`)
+    #[serde(default = "default_metric")]
+    pub cyclomatic_complexity: u32,
+
+    /// Deepest nesting of control blocks (if/for/while/try), 1 for flat code
+    #[serde(default = "default_metric")]
+    pub max_nesting_depth: u32,
+
     /// Function calls made within this function's body.
     ///
     /// Used for call graph construction. Contains the names/expressions
@@ -288,6 +377,23 @@ pub struct FunctionDef {
 }
 
 impl FunctionDef {
+    /// Stable identifier for this definition
+    pub fn id(&self) -> FunctionId {
+        FunctionId {
+            file_id: self.location.file_id,
+            qualified_name: self.qualified_name(),
+            start_byte: self.start_byte,
+        }
+    }
+
+    /// Name qualified with the owning class, e.g. `Client.send` for a method
+    pub fn qualified_name(&self) -> String {
+        match &self.class_name {
+            Some(class) => format!("{}.{}", class, self.name),
+            None => self.name.clone(),
+        }
+    }
+
     /// Check if this is a constructor
     pub fn is_constructor(&self) -> bool {
         self.kind == FunctionKind::Constructor
@@ -302,6 +408,13 @@ impl FunctionDef {
             && !self.name.starts_with('_')
     }
 
+    /// Check if this method takes `&mut self`
+    pub fn takes_mut_self(&self) -> bool {
+        self.params
+            .first()
+            .is_some_and(|p| p.self_kind == Some(SelfKind::RefMut))
+    }
+
     /// Check if this is a test function
     pub fn is_test(&self) -> bool {
         self.name.starts_with("test_")
@@ -350,9 +463,14 @@ pub struct FunctionDefBuilder {
     visibility: Visibility,
     is_async: bool,
     params: Vec<FunctionParam>,
+    type_params: Vec<String>,
     return_type: Option<String>,
     decorators: Vec<FunctionDecorator>,
     class_name: Option<String>,
+    trait_name: Option<String>,
+    parent_function: Option<String>,
+    cyclomatic_complexity: u32,
+    max_nesting_depth: u32,
     calls: Vec<FunctionCall>,
     body_lines: u32,
     has_error_handling: bool,
@@ -390,6 +508,11 @@ impl FunctionDefBuilder {
         self
     }
 
+    pub fn type_param(mut self, type_param: impl Into<String>) -> Self {
+        self.type_params.push(type_param.into());
+        self
+    }
+
     pub fn return_type(mut self, return_type: impl Into<String>) -> Self {
         self.return_type = Some(return_type.into());
         self
@@ -405,6 +528,16 @@ impl FunctionDefBuilder {
         self
     }
 
+    pub fn trait_name(mut self, trait_name: impl Into<String>) -> Self {
+        self.trait_name = Some(trait_name.into());
+        self
+    }
+
+    pub fn parent_function(mut self, parent_function: impl Into<String>) -> Self {
+        self.parent_function = Some(parent_function.into());
+        self
+    }
+
     /// Add a function call made within this function.
     pub fn call(mut self, call: FunctionCall) -> Self {
         self.calls.push(call);
@@ -417,6 +550,16 @@ impl FunctionDefBuilder {
         self
     }
 
+    pub fn cyclomatic_complexity(mut self, complexity: u32) -> Self {
+        self.cyclomatic_complexity = complexity;
+        self
+    }
+
+    pub fn max_nesting_depth(mut self, depth: u32) -> Self {
+        self.max_nesting_depth = depth;
+        self
+    }
+
     pub fn body_lines(mut self, lines: u32) -> Self {
         self.body_lines = lines;
         self
@@ -450,9 +593,14 @@ impl FunctionDefBuilder {
             visibility: self.visibility,
             is_async: self.is_async,
             params: self.params,
+            type_params: self.type_params,
             return_type: self.return_type,
             decorators: self.decorators,
             class_name: self.class_name,
+            trait_name: self.trait_name,
+            parent_function: self.parent_function,
+            cyclomatic_complexity: self.cyclomatic_complexity.max(1),
+            max_nesting_depth: self.max_nesting_depth.max(1),
             calls: self.calls,
             body_lines: self.body_lines,
             has_error_handling: self.has_error_handling,
@@ -467,7 +615,6 @@ impl FunctionDefBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parse::ast::FileId;
 
     fn make_location() -> CommonLocation {
         CommonLocation {
@@ -6,6 +6,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::CommonLocation;
+use crate::types::context::Language;
 
 /// Import style classification
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -14,8 +15,11 @@ pub enum ImportStyle {
     Module,
     /// Named imports from module (from foo import bar, import { bar } from "foo")
     Named,
-    /// Star/wildcard import (from foo import *, import * as foo from "bar")
+    /// Star/wildcard import that binds every exported name
+    /// (from foo import *, use foo::*, import . "foo", import foo.*)
     Star,
+    /// Namespace import bound to a single name (import * as foo from "bar")
+    Namespace,
     /// Default import (TypeScript/ES6: import foo from "bar")
     Default,
     /// Side-effect only import (import "foo")
@@ -156,6 +160,42 @@ impl Import {
             .next()
             .unwrap_or(&self.module_path)
     }
+
+    /// Get the root package this import belongs to, collapsing submodules.
+    ///
+    /// `numpy.linalg` → `numpy`, `reqwest::header` → `reqwest`,
+    /// `@scope/pkg/sub` → `@scope/pkg`, `github.com/org/repo/pkg` →
+    /// `github.com/org/repo`, `com.google.common.collect` → `com.google`.
+    pub fn root_package(&self, language: Language) -> &str {
+        let path = self.module_path.as_str();
+        let prefix_len = |separator: char, segments: usize| {
+            path.match_indices(separator)
+                .nth(segments - 1)
+                .map_or(path.len(), |(idx, _)| idx)
+        };
+        let end = match language {
+            Language::Rust => path
+                .find(|c: char| c == ':' || c.is_whitespace())
+                .unwrap_or(path.len()),
+            Language::Go => {
+                // Hosts that put the repository two levels below the domain
+                let host = path.split('/').next().unwrap_or_default();
+                let segments = match host {
+                    "github.com" | "gitlab.com" | "bitbucket.org" | "golang.org" => 3,
+                    _ if host.contains('.') => 2,
+                    _ => 1,
+                };
+                prefix_len('/', segments)
+            }
+            Language::Typescript | Language::Javascript => {
+                let segments = if path.starts_with('@') { 2 } else { 1 };
+                prefix_len('/', segments)
+            }
+            Language::Java => prefix_len('.', 2),
+            _ => prefix_len('.', 1),
+        };
+        &path[..end]
+    }
 }
 
 /// Common import patterns for well-known libraries
@@ -8,18 +8,25 @@ pub mod async_ops;
 pub mod calls;
 pub mod db;
 pub mod error_context;
+pub mod exports;
+pub mod file_metrics;
 pub mod frameworks;
 pub mod functions;
 pub mod http;
 pub mod imports;
 pub mod route_patterns;
+pub mod snapshot;
+
+use std::collections::BTreeSet;
 
 use crate::parse::ast::{AstLocation, FileId};
 use crate::types::context::Language;
 
 pub use self::annotations::{Annotation, AnnotationType, FunctionAnnotations};
 pub use self::error_context::{ErrorContext, ErrorContextType, ErrorSummary};
+pub use self::file_metrics::FileMetrics;
 pub use self::route_patterns::{RouteFramework, RoutePattern};
+pub use self::snapshot::CommonSemanticsSnapshot;
 
 /// Language-agnostic semantic information for a source file.
 ///
@@ -36,9 +43,23 @@ pub trait CommonSemantics: Send + Sync {
     /// Get the file path
     fn file_path(&self) -> &str;
 
+    /// Get the number of lines in the source file
+    ///
+    /// Defaults to 0 for implementations that do not keep the source.
+    fn line_count(&self) -> usize {
+        0
+    }
+
     /// Get the language of this file
     fn language(&self) -> Language;
 
+    /// Get the number of class, struct and enum definitions in this file
+    ///
+    /// Defaults to 0 for implementations that do not track type definitions.
+    fn class_count(&self) -> usize {
+        0
+    }
+
     /// Get HTTP client calls in this file
     fn http_calls(&self) -> Vec<http::HttpCall>;
 
@@ -66,6 +87,22 @@ pub trait CommonSemantics: Send + Sync {
     /// Get error handling contexts (try/catch, error propagation)
     fn error_contexts(&self) -> Vec<error_context::ErrorContext>;
 
+    /// Get the symbols this file exposes to other modules
+    ///
+    /// Defaults to none for implementations that do not track exports.
+    fn exported_symbols(&self) -> Vec<exports::ExportedSymbol> {
+        Vec::new()
+    }
+
+    /// Get imports whose bound names are never referenced in this file.
+    ///
+    /// Named imports keep only their unused items. Re-exported names,
+    /// wildcard, side-effect and dynamic imports always count as used.
+    /// Defaults to none for implementations that do not track references.
+    fn unused_imports(&self) -> Vec<imports::Import> {
+        Vec::new()
+    }
+
     /// Check if a specific import exists by module path
     fn has_import(&self, module: &str) -> bool {
         self.imports().iter().any(|i| i.matches_module(module))
@@ -79,11 +116,49 @@ pub trait CommonSemantics: Send + Sync {
         })
     }
 
+    /// Get wildcard imports, which bring unlisted names into scope
+    fn wildcard_imports(&self) -> Vec<imports::Import> {
+        self.imports()
+            .into_iter()
+            .filter(|i| i.style == imports::ImportStyle::Star)
+            .collect()
+    }
+
+    /// Get the distinct root packages of this file's external imports, sorted.
+    ///
+    /// Dynamic imports are skipped since their module may not be a literal.
+    fn external_dependencies(&self) -> Vec<String> {
+        let language = self.language();
+        let roots: BTreeSet<String> = self
+            .imports()
+            .iter()
+            .filter(|i| i.is_external() && !i.is_dynamic)
+            .map(|i| i.root_package(language).to_string())
+            .collect();
+        roots.into_iter().collect()
+    }
+
+    /// Get a numeric profile of this file (counts, LOC, average complexity)
+    fn metrics(&self) -> FileMetrics {
+        FileMetrics::from_semantics(self)
+    }
+
     /// Find a function by name
     fn find_function(&self, name: &str) -> Option<functions::FunctionDef> {
         self.functions().into_iter().find(|f| f.name == name)
     }
 
+    /// Find the innermost function or method whose byte range contains `byte`.
+    ///
+    /// Nested functions win over their enclosing function, so editors get the
+    /// definition closest to the cursor.
+    fn function_at_byte(&self, byte: usize) -> Option<functions::FunctionDef> {
+        self.functions()
+            .into_iter()
+            .filter(|f| f.start_byte <= byte && byte <= f.end_byte)
+            .min_by_key(|f| f.end_byte - f.start_byte)
+    }
+
     /// Get HTTP calls without timeout
     fn http_calls_without_timeout(&self) -> Vec<http::HttpCall> {
         self.http_calls()
@@ -92,6 +167,11 @@ pub trait CommonSemantics: Send + Sync {
             .collect()
     }
 
+    /// Group HTTP calls by method and endpoint (URL with path params collapsed)
+    fn aggregate_http_calls(&self) -> Vec<http::HttpCallGroup> {
+        http::aggregate_http_calls(&self.http_calls())
+    }
+
     /// Get HTTP calls without retry logic
     fn http_calls_without_retry(&self) -> Vec<http::HttpCall> {
         self.http_calls()
@@ -100,6 +180,20 @@ pub trait CommonSemantics: Send + Sync {
             .collect()
     }
 
+    /// Get HTTP calls with hardcoded credentials or internal hostnames in
+    /// their literals, using the default rules
+    fn suspicious_http_literals(&self) -> Vec<http::HttpCall> {
+        self.suspicious_http_literals_with(&http::HttpLiteralRules::default())
+    }
+
+    /// Like [`Self::suspicious_http_literals`], with custom rules
+    fn suspicious_http_literals_with(&self, rules: &http::HttpLiteralRules) -> Vec<http::HttpCall> {
+        self.http_calls()
+            .into_iter()
+            .filter(|c| rules.is_suspicious(c))
+            .collect()
+    }
+
     /// Get database operations without timeout
     fn db_operations_without_timeout(&self) -> Vec<db::DbOperation> {
         self.db_operations()
@@ -108,6 +202,22 @@ pub trait CommonSemantics: Send + Sync {
             .collect()
     }
 
+    /// Get Redis reads and writes inside loops or comprehensions, which
+    /// usually should be pipelined or batched (`MGET`, `pipeline()`)
+    fn cache_calls_in_loop(&self) -> Vec<db::DbOperation> {
+        self.db_operations()
+            .into_iter()
+            .filter(|op| {
+                op.library == db::DbLibrary::Redis
+                    && matches!(
+                        op.operation_type,
+                        db::DbOperationType::CacheGet | db::DbOperationType::CacheSet
+                    )
+                    && (op.in_loop || op.in_iteration)
+            })
+            .collect()
+    }
+
     /// Get async operations without error handling
     fn async_operations_without_error_handling(&self) -> Vec<async_ops::AsyncOperation> {
         self.async_operations()
@@ -116,6 +226,19 @@ pub trait CommonSemantics: Send + Sync {
             .collect()
     }
 
+    /// Get task spawns inside loops or comprehensions that aren't bounded by a
+    /// concurrency limiter
+    fn unbounded_spawns(&self) -> Vec<async_ops::AsyncOperation> {
+        self.async_operations()
+            .into_iter()
+            .filter(|op| {
+                op.operation_type == async_ops::AsyncOperationType::TaskSpawn
+                    && (op.in_loop || op.in_comprehension)
+                    && !op.is_bounded
+            })
+            .collect()
+    }
+
     /// Get annotations of a specific type
     fn annotations_of_type(&self, annotation_type: &str) -> Vec<annotations::Annotation> {
         self.annotations()
@@ -182,11 +305,49 @@ pub trait CommonSemantics: Send + Sync {
     }
 }
 
+/// How certain a detector is that a finding is what it claims to be.
+///
+/// Ordered from least to most certain, so consumers can filter with
+/// `finding.confidence >= Confidence::Medium`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Confidence {
+    /// Name-based guess (e.g. any receiver called `client`)
+    Low,
+    /// Library-specific pattern that other code could also match
+    #[default]
+    Medium,
+    /// Literal match on a library path or import (e.g. `reqwest::blocking::get`)
+    High,
+}
+
+impl Confidence {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
 /// Location information that can be converted from language-specific locations
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CommonLocation {
     pub file_id: FileId,
+    /// 1-based line
     pub line: u32,
+    /// 1-based column in characters (not bytes), as shown by editors
     pub column: u32,
     pub start_byte: usize,
     pub end_byte: usize,
@@ -197,13 +358,86 @@ impl From<&AstLocation> for CommonLocation {
         Self {
             file_id: loc.file_id,
             line: loc.range.start_line + 1,
-            column: loc.range.start_col + 1,
+            column: loc.range.start_char_col + 1,
             start_byte: 0, // Would need byte info from node
             end_byte: 0,
         }
     }
 }
 
+/// A finding that points at a place in the source file.
+pub(crate) trait Located {
+    fn location(&self) -> &CommonLocation;
+}
+
+/// Sort accessor results into source order.
+///
+/// Language models collect items from several passes (free functions, then
+/// methods; calls, then macros), so their raw order is not source order.
+/// Sorting on line and column rather than `start_byte` also orders items
+/// whose location was built without byte offsets. The sort is stable.
+pub(crate) fn in_source_order<T: Located>(mut items: Vec<T>) -> Vec<T> {
+    items.sort_by_key(|item| {
+        let location = item.location();
+        (location.line, location.column, location.start_byte)
+    });
+    items
+}
+
+impl Located for http::HttpCall {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
+impl Located for db::DbOperation {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
+impl Located for async_ops::AsyncOperation {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
+impl Located for imports::Import {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
+impl Located for functions::FunctionDef {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
+impl Located for Annotation {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
+impl Located for RoutePattern {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
+impl Located for ErrorContext {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
+impl Located for exports::ExportedSymbol {
+    fn location(&self) -> &CommonLocation {
+        &self.location
+    }
+}
+
 /// A common call site structure that can represent calls across languages
 #[derive(Debug, Clone)]
 pub struct CommonCallSite {
@@ -237,6 +471,8 @@ mod tests {
                 start_col: 5,
                 end_line: 10,
                 end_col: 20,
+                start_char_col: 5,
+                end_char_col: 20,
             },
         };
         let common_loc = CommonLocation::from(&ast_loc);
@@ -0,0 +1,156 @@
+//! Serializable snapshot of the common semantics of a file.
+//!
+//! Language-specific models keep their own rich representation; this module
+//! flattens the `CommonSemantics` trait outputs into a single document that
+//! downstream tools can dump to disk or send over the wire.
+
+use serde::{Deserialize, Serialize};
+
+use super::CommonSemantics;
+use super::async_ops::AsyncOperation;
+use super::db::DbOperation;
+use super::functions::FunctionDef;
+use super::http::HttpCall;
+use super::imports::Import;
+use super::route_patterns::RoutePattern;
+use crate::parse::ast::FileId;
+use crate::types::context::Language;
+
+/// Flattened, serializable view of a file's `CommonSemantics`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommonSemanticsSnapshot {
+    /// File identifier
+    pub file_id: FileId,
+    /// File path
+    pub file_path: String,
+    /// Language of the file
+    pub language: Language,
+    /// Function and method definitions
+    pub functions: Vec<FunctionDef>,
+    /// Imports and dependencies
+    pub imports: Vec<Import>,
+    /// HTTP client calls
+    pub http_calls: Vec<HttpCall>,
+    /// Database operations
+    pub db_operations: Vec<DbOperation>,
+    /// Async/concurrent operations
+    pub async_operations: Vec<AsyncOperation>,
+    /// HTTP route patterns
+    pub routes: Vec<RoutePattern>,
+}
+
+impl CommonSemanticsSnapshot {
+    /// Capture the common semantics of a file.
+    pub fn from_semantics<S: CommonSemantics + ?Sized>(sem: &S) -> Self {
+        Self {
+            file_id: sem.file_id(),
+            file_path: sem.file_path().to_string(),
+            language: sem.language(),
+            functions: sem.functions(),
+            imports: sem.imports(),
+            http_calls: sem.http_calls(),
+            db_operations: sem.db_operations(),
+            async_operations: sem.async_operations(),
+            routes: sem.route_patterns(),
+        }
+    }
+
+    /// Serialize the snapshot to a JSON string.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Serialize the snapshot to an indented JSON string.
+    pub fn to_json_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize a snapshot from a JSON string.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::python::parse_python_file;
+    use crate::semantics::python::model::PyFileSemantics;
+    use crate::types::context::SourceFile;
+
+    fn python_semantics(source: &str) -> PyFileSemantics {
+        let sf = SourceFile {
+            path: "app/client.py".to_string(),
+            language: Language::Python,
+            content: source.to_string(),
+        };
+        let parsed = parse_python_file(FileId(7), &sf).expect("parsing should succeed");
+        let mut sem = PyFileSemantics::from_parsed(&parsed);
+        sem.analyze_frameworks(&parsed)
+            .expect("framework analysis should succeed");
+        sem
+    }
+
+    #[test]
+    fn snapshot_captures_common_semantics() {
+        let sem = python_semantics(
+            r#"
+import requests
+
+def fetch_user(user_id):
+    return requests.get("https://api.example.com/users", timeout=5)
+"#,
+        );
+
+        let snapshot = CommonSemanticsSnapshot::from_semantics(&sem);
+        assert_eq!(snapshot.file_id, FileId(7));
+        assert_eq!(snapshot.file_path, "app/client.py");
+        assert_eq!(snapshot.language, Language::Python);
+        assert!(snapshot.functions.iter().any(|f| f.name == "fetch_user"));
+        assert!(snapshot.imports.iter().any(|i| i.module_path == "requests"));
+        assert_eq!(snapshot.http_calls.len(), 1);
+    }
+
+    #[test]
+    fn snapshot_json_round_trip() {
+        let sem = python_semantics(
+            r#"
+import requests
+
+def fetch_user(user_id):
+    return requests.get("https://api.example.com/users", timeout=5)
+"#,
+        );
+
+        let snapshot = CommonSemanticsSnapshot::from_semantics(&sem);
+        let json = snapshot.to_json().expect("serialization should succeed");
+        let restored =
+            CommonSemanticsSnapshot::from_json(&json).expect("deserialization should succeed");
+
+        assert_eq!(restored.file_id, snapshot.file_id);
+        assert_eq!(restored.file_path, snapshot.file_path);
+        assert_eq!(restored.language, snapshot.language);
+        assert_eq!(restored.functions.len(), snapshot.functions.len());
+        assert_eq!(restored.imports.len(), snapshot.imports.len());
+        assert_eq!(restored.http_calls.len(), snapshot.http_calls.len());
+        assert_eq!(
+            restored.http_calls[0].location.line,
+            snapshot.http_calls[0].location.line
+        );
+
+        // Re-serializing the restored snapshot yields the same document
+        assert_eq!(restored.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn snapshot_json_uses_plain_file_id_and_language() {
+        let sem = python_semantics("x = 1\n");
+        let json = CommonSemanticsSnapshot::from_semantics(&sem)
+            .to_json()
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["file_id"], serde_json::json!(7));
+        assert_eq!(value["language"], serde_json::json!("python"));
+    }
+}
@@ -0,0 +1,449 @@
+//! Public-API surface diff between two versions of a file.
+//!
+//! The surface of a file is its exported symbols plus the public methods of
+//! its classes; for Rust, the methods in `impl` blocks, owned by the impl
+//! type. Comparing two surfaces yields added, removed and changed symbols,
+//! with each change classified as breaking or not so callers can flag semver
+//! violations.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::CommonSemantics;
+use super::common::exports::ExportKind;
+use super::common::functions::{FunctionDef, FunctionParam, Visibility};
+
+/// A symbol on the public API surface
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiSymbol {
+    /// Symbol name
+    pub name: String,
+    /// Owning class for methods
+    pub class_name: Option<String>,
+    /// Symbol kind (methods are reported as functions)
+    pub kind: ExportKind,
+    /// Visibility of the symbol
+    pub visibility: Visibility,
+}
+
+impl ApiSymbol {
+    /// Qualified name, e.g. `Client.send` for a method
+    pub fn qualified_name(&self) -> String {
+        match &self.class_name {
+            Some(class) => format!("{}.{}", class, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// A single difference between two versions of a symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApiChange {
+    /// A parameter was removed
+    ParamRemoved { name: String },
+    /// A parameter was added
+    ParamAdded { name: String, has_default: bool },
+    /// A parameter kept its name but moved relative to the other parameters,
+    /// which breaks positional callers
+    ParamMoved {
+        name: String,
+        old_position: usize,
+        new_position: usize,
+    },
+    /// A parameter's type annotation changed
+    ParamTypeChanged {
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// The return type changed
+    ReturnTypeChanged {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// The symbol became less visible (e.g. `pub` to `pub(crate)`)
+    VisibilityNarrowed { old: Visibility, new: Visibility },
+    /// The symbol kind changed (e.g. function to const)
+    KindChanged { old: ExportKind, new: ExportKind },
+}
+
+impl ApiChange {
+    /// Whether existing callers may break because of this change.
+    ///
+    /// Adding an optional parameter is the only non-breaking change.
+    pub fn is_breaking(&self) -> bool {
+        !matches!(
+            self,
+            ApiChange::ParamAdded {
+                has_default: true,
+                ..
+            }
+        )
+    }
+}
+
+/// A symbol present in both versions whose signature differs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedSymbol {
+    /// The symbol as it appears in the new version
+    pub symbol: ApiSymbol,
+    /// What changed
+    pub changes: Vec<ApiChange>,
+}
+
+impl ChangedSymbol {
+    /// Whether any of the changes is breaking
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(ApiChange::is_breaking)
+    }
+}
+
+/// Differences between two public-API surfaces
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiDiff {
+    /// Symbols only present in the new version
+    pub added: Vec<ApiSymbol>,
+    /// Symbols only present in the old version
+    pub removed: Vec<ApiSymbol>,
+    /// Symbols present in both versions with a different signature
+    pub changed: Vec<ChangedSymbol>,
+}
+
+impl ApiDiff {
+    /// Check if the two surfaces are identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Check if the diff contains removals or breaking signature changes
+    pub fn has_breaking_changes(&self) -> bool {
+        !self.removed.is_empty() || self.changed.iter().any(ChangedSymbol::is_breaking)
+    }
+}
+
+/// A surface entry: the symbol and, for functions, its definition.
+struct SurfaceEntry {
+    symbol: ApiSymbol,
+    function: Option<FunctionDef>,
+}
+
+type SurfaceKey = (Option<String>, String);
+
+fn surface(sem: &dyn CommonSemantics) -> BTreeMap<SurfaceKey, SurfaceEntry> {
+    let functions = sem.functions();
+    let mut entries = BTreeMap::new();
+
+    for exported in sem.exported_symbols() {
+        let function = if exported.kind == ExportKind::Function {
+            functions
+                .iter()
+                .find(|f| f.class_name.is_none() && f.name == exported.name)
+                .cloned()
+        } else {
+            None
+        };
+        entries.insert(
+            (None, exported.name.clone()),
+            SurfaceEntry {
+                symbol: ApiSymbol {
+                    name: exported.name,
+                    class_name: None,
+                    kind: exported.kind,
+                    visibility: exported.visibility,
+                },
+                function,
+            },
+        );
+    }
+
+    for method in functions.into_iter().filter(|f| {
+        f.class_name.is_some() && matches!(f.visibility, Visibility::Public | Visibility::Protected)
+    }) {
+        entries.insert(
+            (method.class_name.clone(), method.name.clone()),
+            SurfaceEntry {
+                symbol: ApiSymbol {
+                    name: method.name.clone(),
+                    class_name: method.class_name.clone(),
+                    kind: ExportKind::Function,
+                    visibility: method.visibility.clone(),
+                },
+                function: Some(method),
+            },
+        );
+    }
+
+    entries
+}
+
+/// How widely a visibility exposes a symbol; higher is wider.
+fn visibility_rank(visibility: &Visibility) -> u8 {
+    match visibility {
+        Visibility::Public | Visibility::Unknown => 3,
+        Visibility::Protected | Visibility::Package => 2,
+        Visibility::Private => 0,
+    }
+}
+
+fn signature_changes(old: &FunctionDef, new: &FunctionDef) -> Vec<ApiChange> {
+    let mut changes = Vec::new();
+
+    for old_param in &old.params {
+        match new.params.iter().find(|p| p.name == old_param.name) {
+            None => changes.push(ApiChange::ParamRemoved {
+                name: old_param.name.clone(),
+            }),
+            Some(new_param) if new_param.type_annotation != old_param.type_annotation => {
+                changes.push(ApiChange::ParamTypeChanged {
+                    name: old_param.name.clone(),
+                    old: old_param.type_annotation.clone(),
+                    new: new_param.type_annotation.clone(),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for new_param in &new.params {
+        if !old.params.iter().any(|p| p.name == new_param.name) {
+            changes.push(ApiChange::ParamAdded {
+                name: new_param.name.clone(),
+                has_default: new_param.is_optional || new_param.is_variadic,
+            });
+        }
+    }
+
+    // Compare the order of parameters present in both versions, so a removal
+    // or addition does not also report every later parameter as moved
+    let kept = |params: &[FunctionParam], other: &[FunctionParam]| -> Vec<String> {
+        params
+            .iter()
+            .filter(|p| other.iter().any(|o| o.name == p.name))
+            .map(|p| p.name.clone())
+            .collect()
+    };
+    let old_order = kept(&old.params, &new.params);
+    let new_order = kept(&new.params, &old.params);
+    for (name, new_name) in old_order.iter().zip(&new_order) {
+        if name != new_name {
+            let position = |params: &[FunctionParam]| params.iter().position(|p| p.name == *name);
+            if let (Some(old_position), Some(new_position)) =
+                (position(&old.params), position(&new.params))
+            {
+                changes.push(ApiChange::ParamMoved {
+                    name: name.clone(),
+                    old_position,
+                    new_position,
+                });
+            }
+        }
+    }
+
+    if old.return_type != new.return_type {
+        changes.push(ApiChange::ReturnTypeChanged {
+            old: old.return_type.clone(),
+            new: new.return_type.clone(),
+        });
+    }
+
+    changes
+}
+
+/// Compare the public-API surfaces of two versions of a file.
+///
+/// Symbols are matched by name and owning class. A symbol that stops being
+/// exported shows up in `removed`; one whose visibility narrows but stays
+/// exported (e.g. `pub` to `pub(crate)`) shows up in `changed`.
+pub fn api_diff(old: &dyn CommonSemantics, new: &dyn CommonSemantics) -> ApiDiff {
+    let old_surface = surface(old);
+    let mut new_surface = surface(new);
+    let mut diff = ApiDiff::default();
+
+    for (key, old_entry) in old_surface {
+        let Some(new_entry) = new_surface.remove(&key) else {
+            diff.removed.push(old_entry.symbol);
+            continue;
+        };
+
+        let mut changes = Vec::new();
+        if old_entry.symbol.kind != new_entry.symbol.kind {
+            changes.push(ApiChange::KindChanged {
+                old: old_entry.symbol.kind,
+                new: new_entry.symbol.kind,
+            });
+        }
+        if visibility_rank(&new_entry.symbol.visibility)
+            < visibility_rank(&old_entry.symbol.visibility)
+        {
+            changes.push(ApiChange::VisibilityNarrowed {
+                old: old_entry.symbol.visibility.clone(),
+                new: new_entry.symbol.visibility.clone(),
+            });
+        }
+        if let (Some(old_fn), Some(new_fn)) = (&old_entry.function, &new_entry.function) {
+            changes.extend(signature_changes(old_fn, new_fn));
+        }
+
+        if !changes.is_empty() {
+            diff.changed.push(ChangedSymbol {
+                symbol: new_entry.symbol,
+                changes,
+            });
+        }
+    }
+
+    diff.added = new_surface.into_values().map(|e| e.symbol).collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ast::FileId;
+    use crate::parse::python::parse_python_file;
+    use crate::parse::rust::parse_rust_file;
+    use crate::semantics::python::model::PyFileSemantics;
+    use crate::semantics::rust::build_rust_semantics;
+    use crate::types::context::{Language, SourceFile};
+
+    fn python(source: &str) -> PyFileSemantics {
+        let sf = SourceFile {
+            path: "api.py".to_string(),
+            language: Language::Python,
+            content: source.to_string(),
+        };
+        let parsed = parse_python_file(FileId(1), &sf).unwrap();
+        PyFileSemantics::from_parsed(&parsed)
+    }
+
+    #[test]
+    fn identical_files_have_empty_diff() {
+        let src = "def handler(event):\n    pass\n";
+        let diff = api_diff(&python(src), &python(src));
+        assert!(diff.is_empty());
+        assert!(!diff.has_breaking_changes());
+    }
+
+    #[test]
+    fn reports_added_and_removed_functions() {
+        let old = python("def create(name):\n    pass\n\ndef delete(id):\n    pass\n");
+        let new = python("def create(name):\n    pass\n\ndef archive(id):\n    pass\n");
+
+        let diff = api_diff(&old, &new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "archive");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "delete");
+        assert!(diff.changed.is_empty());
+        assert!(diff.has_breaking_changes());
+    }
+
+    #[test]
+    fn reports_changed_signature() {
+        let old = python("def fetch(url: str, retries: int) -> bytes:\n    pass\n");
+        let new = python("def fetch(url: bytes, timeout=5) -> str:\n    pass\n");
+
+        let diff = api_diff(&old, &new);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+
+        let changed = &diff.changed[0];
+        assert_eq!(changed.symbol.name, "fetch");
+        assert_eq!(
+            changed.changes,
+            vec![
+                ApiChange::ParamTypeChanged {
+                    name: "url".to_string(),
+                    old: Some("str".to_string()),
+                    new: Some("bytes".to_string()),
+                },
+                ApiChange::ParamRemoved {
+                    name: "retries".to_string(),
+                },
+                ApiChange::ParamAdded {
+                    name: "timeout".to_string(),
+                    has_default: true,
+                },
+                ApiChange::ReturnTypeChanged {
+                    old: Some("bytes".to_string()),
+                    new: Some("str".to_string()),
+                },
+            ]
+        );
+        assert!(changed.is_breaking());
+    }
+
+    #[test]
+    fn optional_param_addition_is_not_breaking() {
+        let old = python("def send(msg):\n    pass\n");
+        let new = python("def send(msg, priority=0):\n    pass\n");
+
+        let diff = api_diff(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert!(!diff.has_breaking_changes());
+    }
+
+    fn rust(src: &str) -> crate::semantics::rust::model::RustFileSemantics {
+        let sf = SourceFile {
+            path: "lib.rs".to_string(),
+            language: Language::Rust,
+            content: src.to_string(),
+        };
+        let parsed = parse_rust_file(FileId(1), &sf).unwrap();
+        build_rust_semantics(&parsed).unwrap()
+    }
+
+    #[test]
+    fn reports_removed_rust_impl_method() {
+        let old = rust("pub struct C;\n\nimpl C {\n    pub fn send(&self) {}\n}\n");
+        let new = rust("pub struct C;\n\nimpl C {}\n");
+
+        let diff = api_diff(&old, &new);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].qualified_name(), "C.send");
+        assert!(diff.has_breaking_changes());
+    }
+
+    #[test]
+    fn reordered_params_are_breaking() {
+        let old = python("def f(a, b):\n    pass\n");
+        let new = python("def f(b, a):\n    pass\n");
+
+        let diff = api_diff(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].changes,
+            vec![
+                ApiChange::ParamMoved {
+                    name: "a".to_string(),
+                    old_position: 0,
+                    new_position: 1,
+                },
+                ApiChange::ParamMoved {
+                    name: "b".to_string(),
+                    old_position: 1,
+                    new_position: 0,
+                },
+            ]
+        );
+        assert!(diff.has_breaking_changes());
+    }
+
+    #[test]
+    fn reports_narrowed_rust_visibility() {
+        let old = rust("pub fn connect() {}\n");
+        let new = rust("pub(crate) fn connect() {}\n");
+
+        let diff = api_diff(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].changes,
+            vec![ApiChange::VisibilityNarrowed {
+                old: Visibility::Public,
+                new: Visibility::Package,
+            }]
+        );
+        assert!(diff.has_breaking_changes());
+    }
+}
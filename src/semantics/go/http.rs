@@ -1,4 +1,8 @@
+use std::collections::HashMap;
+
 use crate::parse::ast::{AstLocation, ParsedFile};
+use crate::semantics::common::Confidence;
+use crate::semantics::common::http::HttpDetectionConfig;
 use serde::{Deserialize, Serialize};
 use tree_sitter::Node;
 
@@ -38,6 +42,9 @@ pub struct HttpCallSite {
     /// Whether this call has a timeout configured (context with timeout or client timeout).
     pub has_timeout: bool,
 
+    /// Timeout in seconds, if statically determinable.
+    pub timeout_value: Option<f64>,
+
     /// Whether there's error handling for this call.
     pub error_handled: bool,
 
@@ -47,6 +54,10 @@ pub struct HttpCallSite {
     /// Name of enclosing function, if we know it.
     pub function_name: Option<String>,
 
+    /// Low when the client was recognized only by its variable name.
+    #[serde(default)]
+    pub confidence: Confidence,
+
     /// Byte range of the call in the original source file.
     pub start_byte: usize,
     pub end_byte: usize,
@@ -83,81 +94,312 @@ pub struct HttpHandler {
 
 /// Build a list of HTTP client calls in this Go file.
 pub fn summarize_http_clients(file: &ParsedFile) -> Vec<HttpCallSite> {
+    summarize_http_clients_with(file, &HttpDetectionConfig::default())
+}
+
+/// Like [`summarize_http_clients`], also reporting calls through the custom
+/// clients registered in `config`.
+pub fn summarize_http_clients_with(
+    file: &ParsedFile,
+    config: &HttpDetectionConfig,
+) -> Vec<HttpCallSite> {
     let root = file.tree.root_node();
     let mut calls = Vec::new();
-    collect_http_calls(file, root, &mut calls);
+    collect_http_calls(file, root, &mut calls, config);
     calls
 }
 
-fn collect_http_calls(file: &ParsedFile, root: Node, out: &mut Vec<HttpCallSite>) {
+fn collect_http_calls(
+    file: &ParsedFile,
+    root: Node,
+    out: &mut Vec<HttpCallSite>,
+    config: &HttpDetectionConfig,
+) {
     fn walk(
         file: &ParsedFile,
         node: Node,
         out: &mut Vec<HttpCallSite>,
         enclosing_fn_name: &mut Option<String>,
+        bindings: &mut TimeoutBindings,
+        config: &HttpDetectionConfig,
     ) {
-        // Track function boundaries
-        if matches!(node.kind(), "function_declaration" | "method_declaration") {
-            if let Some(name_node) = node.child_by_field_name("name") {
-                *enclosing_fn_name = Some(file.text_for_node(&name_node));
-            }
+        // Track function boundaries; clients bound inside a function are
+        // dropped on the way out, package-level ones stay visible.
+        let is_function = matches!(node.kind(), "function_declaration" | "method_declaration");
+        let outer_clients = is_function.then(|| bindings.clients.clone());
+        if is_function && let Some(name_node) = node.child_by_field_name("name") {
+            *enclosing_fn_name = Some(file.text_for_node(&name_node));
         }
 
+        // Track clients, contexts and requests that carry a timeout
+        record_timeout_bindings(file, node, bindings);
+
         if node.kind() == "call_expression" {
-            if let Some(site) = extract_http_call(file, node, enclosing_fn_name.clone()) {
+            if let Some(site) =
+                extract_http_call(file, node, enclosing_fn_name.clone(), bindings, config)
+            {
                 out.push(site);
             }
         }
 
         let mut child = node.child(0);
         while let Some(c) = child {
-            walk(file, c, out, enclosing_fn_name);
+            walk(file, c, out, enclosing_fn_name, bindings, config);
             child = c.next_sibling();
         }
 
         // Leaving function scope
-        if matches!(node.kind(), "function_declaration" | "method_declaration") {
+        if let Some(outer) = outer_clients {
             *enclosing_fn_name = None;
+            bindings.clients = outer;
+            bindings.contexts.clear();
+            bindings.requests.clear();
         }
     }
 
     let mut enclosing_fn_name: Option<String> = None;
-    walk(file, root, out, &mut enclosing_fn_name);
+    let mut bindings = TimeoutBindings::default();
+    walk(
+        file,
+        root,
+        out,
+        &mut enclosing_fn_name,
+        &mut bindings,
+        config,
+    );
+}
+
+/// Variables known to carry a timeout, mapped to the duration in seconds when it
+/// is statically determinable.
+#[derive(Debug, Default)]
+struct TimeoutBindings {
+    /// `client := &http.Client{Timeout: 10 * time.Second}` or `client.Timeout = ...`
+    /// (package-level, or scoped to the function that binds it)
+    clients: HashMap<String, Option<f64>>,
+    /// `ctx, cancel := context.WithTimeout(parent, 5 * time.Second)` (function-scoped)
+    contexts: HashMap<String, Option<f64>>,
+    /// `req, err := http.NewRequestWithContext(ctx, ...)` with a bounded `ctx` (function-scoped)
+    requests: HashMap<String, Option<f64>>,
+}
+
+impl TimeoutBindings {
+    /// Timeout carried by a context or request variable passed to a call.
+    fn for_argument(&self, name: &str) -> Option<Option<f64>> {
+        self.requests
+            .get(name)
+            .or_else(|| self.contexts.get(name))
+            .copied()
+    }
+}
+
+/// Record variables bound to a client, context or request with a timeout by this node.
+fn record_timeout_bindings(file: &ParsedFile, node: Node, bindings: &mut TimeoutBindings) {
+    let (left, right) = match node.kind() {
+        "short_var_declaration" | "assignment_statement" => (
+            node.child_by_field_name("left"),
+            node.child_by_field_name("right"),
+        ),
+        "var_spec" => (
+            node.child_by_field_name("name"),
+            node.child_by_field_name("value"),
+        ),
+        _ => return,
+    };
+    let (Some(left), Some(right)) = (left, right) else {
+        return;
+    };
+
+    let left_text = file.text_for_node(&left);
+    let right_text = file.text_for_node(&right);
+    let first_name = left_text
+        .split(',')
+        .next()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && *name != "_");
+
+    // `client.Timeout = ...` on an existing client
+    if let Some(client) = left_text.strip_suffix(".Timeout") {
+        bindings
+            .clients
+            .insert(client.trim().to_string(), parse_go_duration(&right_text));
+        return;
+    }
+
+    // `client := &http.Client{Timeout: ...}`
+    if right_text.contains("http.Client{") {
+        if let Some(value) = composite_field_value(&right_text, "Timeout") {
+            let timeout = parse_go_duration(value);
+            for name in left_text.split(',').map(str::trim) {
+                if !name.is_empty() && name != "_" {
+                    bindings.clients.insert(name.to_string(), timeout);
+                }
+            }
+        }
+        return;
+    }
+
+    let Some(name) = first_name else {
+        return;
+    };
+    let Some(call) = first_call_expression(right) else {
+        return;
+    };
+    let Some(func) = call.child_by_field_name("function") else {
+        return;
+    };
+    let callee = file.text_for_node(&func);
+    let args: Vec<Node> = call
+        .child_by_field_name("arguments")
+        .map(|args| {
+            let mut cursor = args.walk();
+            args.named_children(&mut cursor).collect()
+        })
+        .unwrap_or_default();
+    let arg_text = |i: usize| args.get(i).map(|arg| file.text_for_node(arg));
+
+    match callee.as_str() {
+        // `ctx, cancel := context.WithTimeout(parent, 5 * time.Second)`
+        "context.WithTimeout" => {
+            let timeout = arg_text(1).and_then(|d| parse_go_duration(&d));
+            bindings.contexts.insert(name.to_string(), timeout);
+        }
+        // `ctx, cancel := context.WithDeadline(parent, time.Now().Add(5 * time.Second))`
+        "context.WithDeadline" => {
+            let timeout = arg_text(1).and_then(|d| {
+                d.trim()
+                    .strip_prefix("time.Now().Add(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .and_then(parse_go_duration)
+            });
+            bindings.contexts.insert(name.to_string(), timeout);
+        }
+        // Derived contexts keep the parent's deadline
+        "context.WithCancel" | "context.WithValue" => {
+            if let Some(timeout) = arg_text(0).and_then(|p| bindings.contexts.get(&p).copied()) {
+                bindings.contexts.insert(name.to_string(), timeout);
+            }
+        }
+        // `req, err := http.NewRequestWithContext(ctx, ...)`
+        "http.NewRequestWithContext" => {
+            if let Some(timeout) = arg_text(0).and_then(|ctx| bindings.contexts.get(&ctx).copied())
+            {
+                bindings.requests.insert(name.to_string(), timeout);
+            }
+        }
+        // `req = req.WithContext(ctx)` / `req2 := req.Clone(ctx)`
+        _ if callee.ends_with(".WithContext") || callee.ends_with(".Clone") => {
+            if let Some(timeout) = arg_text(0).and_then(|ctx| bindings.contexts.get(&ctx).copied())
+            {
+                bindings.requests.insert(name.to_string(), timeout);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Find the first call expression on the right-hand side of a declaration.
+fn first_call_expression(node: Node) -> Option<Node> {
+    if node.kind() == "call_expression" {
+        return Some(node);
+    }
+    if node.kind() == "expression_list" {
+        return node
+            .named_child(0)
+            .filter(|n| n.kind() == "call_expression");
+    }
+    None
+}
+
+/// Extract the text of `field: value` from a composite literal like `http.Client{Timeout: 5 * time.Second}`.
+fn composite_field_value<'a>(literal: &'a str, field: &str) -> Option<&'a str> {
+    let start = literal.find(&format!("{}:", field))? + field.len() + 1;
+    let rest = &literal[start..];
+    let end = rest.find([',', '}', '\n']).unwrap_or(rest.len());
+    Some(rest[..end].trim())
+}
+
+/// Parse a Go duration expression into seconds.
+///
+/// Handles products of integer/float literals, `time.Duration(n)` and `time` unit
+/// constants, e.g. `5 * time.Second`, `time.Millisecond * 500`, `time.Minute`.
+fn parse_go_duration(expr: &str) -> Option<f64> {
+    let mut seconds = 1.0;
+    let mut has_unit = false;
+
+    for factor in expr.split('*').map(str::trim) {
+        let factor = factor
+            .strip_prefix("time.Duration(")
+            .and_then(|f| f.strip_suffix(')'))
+            .unwrap_or(factor)
+            .trim();
+        let unit = match factor {
+            "time.Nanosecond" => Some(1e-9),
+            "time.Microsecond" => Some(1e-6),
+            "time.Millisecond" => Some(1e-3),
+            "time.Second" => Some(1.0),
+            "time.Minute" => Some(60.0),
+            "time.Hour" => Some(3600.0),
+            _ => None,
+        };
+        if let Some(unit) = unit {
+            has_unit = true;
+            seconds *= unit;
+        } else {
+            seconds *= factor.parse::<f64>().ok()?;
+        }
+    }
+
+    has_unit.then_some(seconds)
 }
 
 fn extract_http_call(
     file: &ParsedFile,
     call_node: Node,
     enclosing_fn_name: Option<String>,
+    bindings: &TimeoutBindings,
+    config: &HttpDetectionConfig,
 ) -> Option<HttpCallSite> {
     let func = call_node.child_by_field_name("function")?;
     let call_text = file.text_for_node(&call_node);
 
     // Check for http.Get, http.Post, http.Do, etc.
-    let (client_kind, method_name) = if func.kind() == "selector_expression" {
+    let (client_kind, method_name, confidence) = if func.kind() == "selector_expression" {
         let object = func.child_by_field_name("operand")?;
         let field = func.child_by_field_name("field")?;
 
         let object_text = file.text_for_node(&object);
         let method_name = file.text_for_node(&field);
 
-        // Check for net/http client calls
-        if object_text == "http" {
+        // Registered custom clients take precedence over the built-in libraries
+        if let Some(name) = config.match_call(&object_text, &method_name) {
+            (
+                HttpClientKind::Other(name.to_string()),
+                method_name,
+                Confidence::High,
+            )
+        } else if object_text == "http" {
             match method_name.as_str() {
-                "Get" | "Post" | "PostForm" | "Head" => (HttpClientKind::NetHttp, method_name),
+                "Get" | "Post" | "PostForm" | "Head" => {
+                    (HttpClientKind::NetHttp, method_name, Confidence::High)
+                }
                 _ => return None,
             }
         } else if object_text.ends_with("Client") || object_text.contains("client") {
             // Likely an http.Client instance
             if matches!(method_name.as_str(), "Do" | "Get" | "Post" | "Head") {
-                (HttpClientKind::NetHttp, method_name)
+                let confidence = if object_text.starts_with("http.") {
+                    Confidence::High
+                } else {
+                    Confidence::Low
+                };
+                (HttpClientKind::NetHttp, method_name, confidence)
             } else {
                 return None;
             }
         } else if object_text.contains("resty") {
-            (HttpClientKind::Resty, method_name)
+            (HttpClientKind::Resty, method_name, Confidence::Medium)
         } else if object_text.contains("fasthttp") {
-            (HttpClientKind::Fasthttp, method_name)
+            (HttpClientKind::Fasthttp, method_name, Confidence::Medium)
         } else {
             return None;
         }
@@ -166,8 +408,22 @@ fn extract_http_call(
     };
 
     // Check for timeout in the call context
+    // A bounded context or request passed as an argument, e.g. `client.Do(req)`
+    // where `req` was built with `http.NewRequestWithContext(ctx, ...)`
+    let argument_timeout = call_node.child_by_field_name("arguments").and_then(|args| {
+        let mut cursor = args.walk();
+        args.named_children(&mut cursor)
+            .find_map(|arg| bindings.for_argument(&file.text_for_node(&arg)))
+    });
+    let client_timeout = func
+        .child_by_field_name("operand")
+        .and_then(|object| bindings.clients.get(&file.text_for_node(&object)).copied());
+    let timeout_value = argument_timeout.flatten().or(client_timeout.flatten());
+
     // This is a heuristic - we look for context.WithTimeout or client.Timeout patterns
-    let has_timeout = call_text.contains("WithTimeout")
+    let has_timeout = argument_timeout.is_some()
+        || client_timeout.is_some()
+        || call_text.contains("WithTimeout")
         || call_text.contains("WithDeadline")
         || call_text.contains("Timeout:");
 
@@ -188,9 +444,11 @@ fn extract_http_call(
         method_name,
         call_text,
         has_timeout,
+        timeout_value,
         error_handled,
         location,
         function_name: enclosing_fn_name,
+        confidence,
         start_byte: byte_range.start,
         end_byte: byte_range.end,
     })
@@ -282,4 +540,304 @@ func main() {
         let calls = parse_and_summarize_http(src);
         assert!(calls.is_empty());
     }
+
+    #[test]
+    fn default_client_call_has_no_timeout() {
+        let src = r#"
+package main
+
+import "net/http"
+
+func fetch() {
+    resp, err := http.Get("https://example.com")
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(!calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_client_struct_with_timeout() {
+        let src = r#"
+package main
+
+import (
+    "net/http"
+    "time"
+)
+
+func fetch() {
+    client := &http.Client{Timeout: 10 * time.Second}
+    resp, err := client.Get("https://example.com")
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_timeout_assigned_on_client() {
+        let src = r#"
+package main
+
+import (
+    "net/http"
+    "time"
+)
+
+func fetch(req *http.Request) {
+    client := &http.Client{}
+    client.Timeout = 5 * time.Second
+    resp, err := client.Do(req)
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn client_timeout_does_not_leak_into_other_functions() {
+        let src = r#"
+package main
+
+import (
+    "net/http"
+    "time"
+)
+
+var apiClient = &http.Client{Timeout: 3 * time.Second}
+
+func fetch() {
+    client := &http.Client{Timeout: 10 * time.Second}
+    client.Get("https://example.com/a")
+}
+
+func fetchOther(client *http.Client) {
+    client.Get("https://example.com/b")
+    apiClient.Get("https://example.com/c")
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        let timeouts: Vec<_> = calls.iter().map(|c| c.has_timeout).collect();
+        assert_eq!(timeouts, vec![true, false, true]);
+    }
+
+    #[test]
+    fn client_struct_without_timeout_has_no_timeout() {
+        let src = r#"
+package main
+
+import "net/http"
+
+func fetch(req *http.Request) {
+    client := &http.Client{}
+    resp, err := client.Do(req)
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(!calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_context_with_timeout_in_call() {
+        let src = r#"
+package main
+
+import (
+    "context"
+    "net/http"
+    "time"
+)
+
+func fetch(client *http.Client) {
+    resp, err := client.Do(newRequest(context.WithTimeout(context.Background(), time.Second)))
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_request_with_timeout_context() {
+        let src = r#"
+package main
+
+import (
+    "context"
+    "net/http"
+    "time"
+)
+
+func fetch(client *http.Client) error {
+    ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+    defer cancel()
+    req, _ := http.NewRequestWithContext(ctx, "GET", "https://example.com", nil)
+    resp, err := client.Do(req)
+    if err != nil {
+        return err
+    }
+    defer resp.Body.Close()
+    return nil
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+        assert_eq!(calls[0].timeout_value, Some(5.0));
+    }
+
+    #[test]
+    fn detects_request_with_deadline_context() {
+        let src = r#"
+package main
+
+import (
+    "context"
+    "net/http"
+    "time"
+)
+
+func fetch(client *http.Client) {
+    ctx, cancel := context.WithDeadline(context.Background(), time.Now().Add(500*time.Millisecond))
+    defer cancel()
+    req, _ := http.NewRequestWithContext(ctx, http.MethodGet, "https://example.com", nil)
+    resp, err := client.Do(req)
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+        assert_eq!(calls[0].timeout_value, Some(0.5));
+    }
+
+    #[test]
+    fn detects_timeout_through_derived_context_and_with_context() {
+        let src = r#"
+package main
+
+import (
+    "context"
+    "net/http"
+    "time"
+)
+
+func fetch(client *http.Client, req *http.Request) {
+    ctx, cancel := context.WithTimeout(req.Context(), time.Minute)
+    defer cancel()
+    inner, stop := context.WithCancel(ctx)
+    defer stop()
+    req = req.WithContext(inner)
+    resp, err := client.Do(req)
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+        assert_eq!(calls[0].timeout_value, Some(60.0));
+    }
+
+    #[test]
+    fn request_with_background_context_has_no_timeout() {
+        let src = r#"
+package main
+
+import (
+    "context"
+    "net/http"
+)
+
+func fetch(client *http.Client) {
+    req, _ := http.NewRequestWithContext(context.Background(), "GET", "https://example.com", nil)
+    resp, err := client.Do(req)
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(!calls[0].has_timeout);
+        assert_eq!(calls[0].timeout_value, None);
+    }
+
+    #[test]
+    fn timeout_context_does_not_leak_across_functions() {
+        let src = r#"
+package main
+
+import (
+    "context"
+    "net/http"
+    "time"
+)
+
+func bounded(client *http.Client) {
+    req, cancel := context.WithTimeout(context.Background(), time.Second)
+    defer cancel()
+    _ = req
+}
+
+func unbounded(client *http.Client, req *http.Request) {
+    resp, err := client.Do(req)
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(!calls[0].has_timeout);
+    }
+
+    #[test]
+    fn extracts_client_timeout_value() {
+        let src = r#"
+package main
+
+import (
+    "net/http"
+    "time"
+)
+
+func fetch() {
+    client := &http.Client{Timeout: 10 * time.Second}
+    resp, err := client.Get("https://example.com")
+    _ = resp
+    _ = err
+}
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].timeout_value, Some(10.0));
+    }
+
+    #[test]
+    fn parses_go_durations() {
+        assert_eq!(parse_go_duration("5 * time.Second"), Some(5.0));
+        assert_eq!(parse_go_duration("time.Millisecond * 250"), Some(0.25));
+        assert_eq!(parse_go_duration("time.Minute"), Some(60.0));
+        assert_eq!(
+            parse_go_duration("time.Duration(3) * time.Hour"),
+            Some(10800.0)
+        );
+        assert_eq!(parse_go_duration("cfg.Timeout"), None);
+        assert_eq!(parse_go_duration("30"), None);
+    }
 }
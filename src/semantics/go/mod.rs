@@ -20,6 +20,20 @@ pub fn build_go_semantics(parsed: &ParsedFile) -> Result<GoFileSemantics> {
     Ok(sem)
 }
 
+/// Build the semantic model for a Go file that belongs to a known module.
+///
+/// `module_path` is the `module` directive from `go.mod`; imports under it are
+/// treated as local to the project rather than third-party.
+pub fn build_go_semantics_with_module(
+    parsed: &ParsedFile,
+    module_path: &str,
+) -> Result<GoFileSemantics> {
+    let mut sem = GoFileSemantics::from_parsed(parsed);
+    sem.module_path = Some(module_path.to_string());
+    sem.analyze_frameworks(parsed)?;
+    Ok(sem)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +84,53 @@ var x = 42
         assert!(!sem.declarations.is_empty());
     }
 
+    #[test]
+    fn module_imports_are_classified_local() {
+        use crate::semantics::common::CommonSemantics;
+        use crate::semantics::common::imports::ImportSource;
+
+        let sf = SourceFile {
+            path: "cmd/main.go".to_string(),
+            language: Language::Go,
+            content: r#"
+package main
+
+import (
+    "fmt"
+    "github.com/org/app/internal/x"
+    "github.com/org/application"
+    "github.com/other/lib"
+)
+"#
+            .to_string(),
+        };
+        let parsed = parse_go_file(FileId(1), &sf).expect("parsing should succeed");
+        let sem = build_go_semantics_with_module(&parsed, "github.com/org/app")
+            .expect("semantics building should succeed");
+
+        let source_of = |path: &str| {
+            sem.imports()
+                .into_iter()
+                .find(|i| i.module_path == path)
+                .map(|i| i.source)
+                .unwrap()
+        };
+        assert_eq!(source_of("fmt"), ImportSource::StandardLib);
+        assert_eq!(
+            source_of("github.com/org/app/internal/x"),
+            ImportSource::Local
+        );
+        assert_eq!(
+            source_of("github.com/org/application"),
+            ImportSource::External
+        );
+        assert_eq!(source_of("github.com/other/lib"), ImportSource::External);
+
+        // Without a module path the project's own packages look external.
+        let sem = build_go_semantics(&parsed).expect("semantics building should succeed");
+        assert!(!sem.is_module_import("github.com/org/app/internal/x"));
+    }
+
     #[test]
     fn build_go_semantics_populates_http_calls() {
         let src = r#"
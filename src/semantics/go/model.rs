@@ -1,8 +1,16 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
 use serde::{Deserialize, Serialize};
 
 use crate::parse::ast::{AstLocation, FileId, ParsedFile};
-use crate::semantics::common::db::{DbLibrary, DbOperation, DbOperationType};
-use crate::semantics::common::{CommonLocation, calls::FunctionCall};
+use crate::semantics::common::async_ops::{AsyncOperation, AsyncOperationType};
+use crate::semantics::common::db::{
+    DbLibrary, DbOperation, DbOperationType, is_mongo_collection_receiver, is_redis_receiver,
+};
+use crate::semantics::common::functions::{FunctionDef, Visibility};
+use crate::semantics::common::{CommonLocation, CommonSemantics, Confidence, calls::FunctionCall};
+use crate::semantics::metrics::{GO_RULES, cyclomatic_complexity, max_nesting_depth};
+use crate::semantics::references::{self, referenced_identifiers};
 use crate::types::context::Language;
 
 use super::frameworks::{GoFrameworkSummary, extract_go_routes};
@@ -67,6 +75,22 @@ pub struct GoroutineSpawn {
     pub has_unbounded_channel_send: bool,
     /// Whether this is an anonymous function goroutine
     pub is_anonymous: bool,
+    /// Whether this goroutine is spawned inside a loop
+    pub in_loop: bool,
+    /// Whether the enclosing function manages the goroutine with a sync.WaitGroup
+    /// or an errgroup.Group
+    pub uses_wait_group: bool,
+    /// Whether this is an errgroup `g.Go(...)` call rather than a `go` statement
+    #[serde(default)]
+    pub is_errgroup: bool,
+    /// Whether concurrency is limited by errgroup, a semaphore.Weighted or a
+    /// `chan struct{}` semaphore
+    #[serde(default)]
+    pub is_bounded: bool,
+    /// Literal limit from `SetLimit(n)`, `semaphore.NewWeighted(n)` or
+    /// `make(chan struct{}, n)`
+    #[serde(default)]
+    pub bound_limit: Option<u32>,
     /// Name of the enclosing function
     pub function_name: Option<String>,
     /// Start byte offset
@@ -126,6 +150,12 @@ pub struct SelectStatement {
     pub has_receive_cases: bool,
     /// Whether this select is used for cancellation (ctx.Done())
     pub is_cancellation_pattern: bool,
+    /// Cases in source order
+    #[serde(default)]
+    pub branches: Vec<SelectBranch>,
+    /// Whether a case fires on a timer or cancellation (`time.After`, `ctx.Done()`)
+    #[serde(default)]
+    pub has_timeout_case: bool,
     /// Name of the enclosing function
     pub function_name: Option<String>,
     /// Start byte offset
@@ -136,6 +166,26 @@ pub struct SelectStatement {
     pub location: AstLocation,
 }
 
+/// Kind of a select case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectBranchKind {
+    Send,
+    Receive,
+    Default,
+}
+
+/// A single case of a select statement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectBranch {
+    pub kind: SelectBranchKind,
+    /// Channel expression sent to or received from; `None` for `default`
+    pub channel: Option<String>,
+    /// Whether the channel is a timer or cancellation signal
+    pub is_timeout: bool,
+    /// 1-based line number
+    pub line: u32,
+}
+
 /// Information about a defer statement.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeferStatement {
@@ -227,10 +277,22 @@ pub struct GoFileSemantics {
     pub file_id: FileId,
     pub path: String,
     pub language: Language,
+    /// Number of lines in the source file
+    #[serde(default)]
+    pub line_count: usize,
+
+    /// Names referenced outside import statements (for unused-import detection)
+    #[serde(default)]
+    pub identifiers: BTreeSet<String>,
 
     /// Package name
     pub package_name: String,
 
+    /// Module path from `go.mod` (e.g. "github.com/org/app"), if known.
+    /// Imports under this path belong to the project itself.
+    #[serde(default)]
+    pub module_path: Option<String>,
+
     /// Imports
     pub imports: Vec<GoImport>,
 
@@ -240,6 +302,9 @@ pub struct GoFileSemantics {
     /// Type declarations (struct, interface)
     pub types: Vec<GoTypeDecl>,
 
+    /// Interface declarations with their method signatures
+    pub interfaces: Vec<GoInterface>,
+
     /// Method declarations
     pub methods: Vec<GoMethod>,
 
@@ -303,6 +368,8 @@ pub struct MutexOperation {
     pub is_rlock: bool,
     /// Whether this lock uses defer for unlock
     pub uses_defer_unlock: bool,
+    /// Whether the enclosing function unlocks the same mutex (deferred or explicit)
+    pub has_matching_unlock: bool,
     /// Whether this is an empty critical section (lock followed immediately by unlock)
     pub is_empty_critical_section: bool,
     /// Name of the enclosing function
@@ -326,6 +393,14 @@ pub struct GoImport {
     pub is_blank: bool,
     /// Whether this is a dot import (import . "...")
     pub is_dot: bool,
+    /// Byte range of the import spec in the source
+    #[serde(default)]
+    pub start_byte: usize,
+    #[serde(default)]
+    pub end_byte: usize,
+    /// Source text of the import spec, e.g. `mux "github.com/gorilla/mux"`
+    #[serde(default)]
+    pub raw_text: String,
     /// Location
     pub location: AstLocation,
 }
@@ -334,6 +409,9 @@ pub struct GoImport {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoFunction {
     pub name: String,
+    /// Type parameters as written, e.g. `T any` or `K comparable`
+    #[serde(default)]
+    pub type_params: Vec<String>,
     pub params: Vec<GoParam>,
     pub return_types: Vec<String>,
     /// Whether this function returns an error (last return type is "error")
@@ -344,6 +422,10 @@ pub struct GoFunction {
     pub start_byte: usize,
     /// End byte offset for patching
     pub end_byte: usize,
+    /// Cyclomatic complexity (1 for straight-line code)
+    pub cyclomatic_complexity: u32,
+    /// Deepest nesting of control blocks (1 for straight-line code)
+    pub max_nesting_depth: u32,
 }
 
 /// Representation of a Go method.
@@ -364,6 +446,10 @@ pub struct GoMethod {
     pub start_byte: usize,
     /// End byte offset for patching
     pub end_byte: usize,
+    /// Cyclomatic complexity (1 for straight-line code)
+    pub cyclomatic_complexity: u32,
+    /// Deepest nesting of control blocks (1 for straight-line code)
+    pub max_nesting_depth: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -385,6 +471,28 @@ pub struct GoTypeDecl {
     pub location: AstLocation,
 }
 
+/// Representation of a Go interface declaration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoInterface {
+    pub name: String,
+    /// Method signatures declared by the interface
+    pub methods: Vec<GoInterfaceMethod>,
+    /// Embedded interfaces and type constraints, e.g. "io.Reader"
+    pub embedded: Vec<String>,
+    /// Location
+    pub location: AstLocation,
+}
+
+/// A method signature declared inside an interface.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoInterfaceMethod {
+    pub name: String,
+    pub params: Vec<GoParam>,
+    pub return_types: Vec<String>,
+    /// Full signature text, e.g. "Get(ctx context.Context, id string) (*User, error)"
+    pub signature: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GoTypeKind {
     Struct,
@@ -438,10 +546,14 @@ impl GoFileSemantics {
             file_id: parsed.file_id,
             path: parsed.path.clone(),
             language: parsed.language,
+            line_count: parsed.source.lines().count(),
+            identifiers: referenced_identifiers(parsed, &references::GO_RULES),
             package_name: String::new(),
+            module_path: None,
             imports: Vec::new(),
             functions: Vec::new(),
             types: Vec::new(),
+            interfaces: Vec::new(),
             methods: Vec::new(),
             declarations: Vec::new(),
             calls: Vec::new(),
@@ -467,6 +579,134 @@ impl GoFileSemantics {
         sem
     }
 
+    /// Whether an import path belongs to this file's own module.
+    ///
+    /// Always false when the `go.mod` module path is unknown.
+    pub fn is_module_import(&self, import_path: &str) -> bool {
+        self.module_path.as_deref().is_some_and(|module| {
+            import_path == module
+                || import_path
+                    .strip_prefix(module)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
+    /// Look up an interface declared in this file by name.
+    pub fn interface(&self, name: &str) -> Option<&GoInterface> {
+        self.interfaces.iter().find(|i| i.name == name)
+    }
+
+    /// Names of receiver types in this file whose method sets cover every
+    /// method of the given interface (matched by method name).
+    pub fn types_implementing(&self, interface_name: &str) -> Vec<String> {
+        let Some(interface) = self.interface(interface_name) else {
+            return Vec::new();
+        };
+        if interface.methods.is_empty() {
+            return Vec::new();
+        }
+
+        let mut receivers: Vec<String> = Vec::new();
+        for method in &self.methods {
+            let receiver = method.receiver_type.trim_start_matches('*').to_string();
+            if !receivers.contains(&receiver) {
+                receivers.push(receiver);
+            }
+        }
+
+        receivers
+            .into_iter()
+            .filter(|receiver| {
+                interface.methods.iter().all(|required| {
+                    self.methods.iter().any(|m| {
+                        m.name == required.name
+                            && m.receiver_type.trim_start_matches('*') == receiver
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Goroutine spawns that may leak: they have no context or done channel
+    /// for cancellation and are not tracked by a WaitGroup in the enclosing
+    /// function.
+    pub fn potential_goroutine_leaks(&self) -> Vec<AsyncOperation> {
+        self.async_operations()
+            .into_iter()
+            .filter(|op| op.operation_type == AsyncOperationType::TaskSpawn && !op.has_cancellation)
+            .filter(|op| {
+                self.goroutines
+                    .iter()
+                    .any(|g| g.start_byte == op.start_byte && !g.uses_wait_group)
+            })
+            .collect()
+    }
+
+    /// Lock acquisitions whose enclosing function never unlocks the same
+    /// mutex, either explicitly or via `defer`.
+    pub fn unpaired_locks(&self) -> Vec<AsyncOperation> {
+        self.async_operations()
+            .into_iter()
+            .filter(|op| op.operation_type == AsyncOperationType::LockAcquire)
+            .filter(|op| {
+                self.mutex_operations
+                    .iter()
+                    .any(|m| m.lock_start_byte == op.start_byte && !m.has_matching_unlock)
+            })
+            .collect()
+    }
+
+    /// Selects that can block forever: no `default` case and no case that
+    /// fires on a timer or cancellation (`time.After`, `ctx.Done()`).
+    pub fn selects_without_default_or_timeout(&self) -> Vec<AsyncOperation> {
+        self.async_operations()
+            .into_iter()
+            .filter(|op| op.operation_type == AsyncOperationType::SelectRace)
+            .filter(|op| {
+                self.select_statements
+                    .iter()
+                    .any(|s| s.start_byte == op.start_byte && !s.has_default && !s.has_timeout_case)
+            })
+            .collect()
+    }
+
+    /// Functions that perform HTTP or database I/O without propagating a
+    /// `context.Context`: exported functions that take no context parameter,
+    /// and functions that take one but call `context.Background()` or
+    /// `context.TODO()` instead of passing it on.
+    pub fn functions_missing_context(&self) -> Vec<FunctionDef> {
+        let io_offsets: Vec<usize> = self
+            .http_calls()
+            .iter()
+            .map(|c| c.start_byte)
+            .chain(self.db_operations().iter().map(|op| op.start_byte))
+            .collect();
+
+        self.functions()
+            .into_iter()
+            .filter(|f| {
+                io_offsets
+                    .iter()
+                    .any(|&b| f.start_byte <= b && b < f.end_byte)
+            })
+            .filter(|f| {
+                let has_ctx = f.params.iter().any(|p| {
+                    p.type_annotation
+                        .as_deref()
+                        .is_some_and(|t| t.contains("context.Context"))
+                });
+                if !has_ctx {
+                    return matches!(f.visibility, Visibility::Public);
+                }
+                self.context_usages.iter().any(|u| {
+                    u.is_background_or_todo
+                        && f.start_byte <= u.start_byte
+                        && u.start_byte < f.end_byte
+                })
+            })
+            .collect()
+    }
+
     /// Run framework-specific analysis (Gin, Echo, net/http, etc.).
     pub fn analyze_frameworks(&mut self, parsed: &ParsedFile) -> anyhow::Result<()> {
         self.http_calls = super::http::summarize_http_clients(parsed);
@@ -591,6 +831,11 @@ fn walk_nodes_with_context(
             if let Some(ch_op) = detect_channel_close(parsed, &node, &new_ctx) {
                 sem.channel_ops.push(ch_op);
             }
+            if is_errgroup_go(parsed, &node)
+                && let Some(goroutine) = build_goroutine(parsed, &node, &new_ctx)
+            {
+                sem.goroutines.push(goroutine);
+            }
         }
         "unary_expression" => {
             if let Some(ch_op) = detect_channel_receive_unary(parsed, &node, &new_ctx) {
@@ -682,6 +927,9 @@ fn collect_imports(parsed: &ParsedFile, node: &tree_sitter::Node, sem: &mut GoFi
                 alias,
                 is_blank,
                 is_dot,
+                start_byte: spec.start_byte(),
+                end_byte: spec.end_byte(),
+                raw_text: parsed.text_for_node(&spec),
                 location: parsed.location_for_node(&spec),
             });
         }
@@ -718,15 +966,32 @@ fn build_function(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<GoFun
 
     Some(GoFunction {
         name,
+        type_params: extract_type_params(parsed, node),
         params,
         return_types,
         returns_error,
         location: parsed.location_for_node(node),
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &GO_RULES),
+        max_nesting_depth: max_nesting_depth(node, &GO_RULES),
     })
 }
 
+/// Extract the `[...]` type parameter declarations of a generic function.
+fn extract_type_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<String> {
+    let Some(type_params) = node.child_by_field_name("type_parameters") else {
+        return Vec::new();
+    };
+
+    let mut cursor = type_params.walk();
+    type_params
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "type_parameter_declaration")
+        .map(|child| parsed.text_for_node(&child))
+        .collect()
+}
+
 /// Build a GoMethod from a method_declaration node.
 fn build_method(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<GoMethod> {
     let name_node = node.child_by_field_name("name")?;
@@ -748,6 +1013,8 @@ fn build_method(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<GoMetho
         location: parsed.location_for_node(node),
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &GO_RULES),
+        max_nesting_depth: max_nesting_depth(node, &GO_RULES),
     })
 }
 
@@ -884,6 +1151,9 @@ fn collect_type_declarations(
                 if let Some(type_decl) = build_type_decl(parsed, &child) {
                     sem.types.push(type_decl);
                 }
+                if let Some(interface) = build_interface(parsed, &child) {
+                    sem.interfaces.push(interface);
+                }
             }
         }
     }
@@ -984,10 +1254,10 @@ fn extract_interface_methods(
 
     for i in 0..interface_node.child_count() {
         if let Some(child) = interface_node.child(i) {
-            if child.kind() == "method_spec" {
-                if let Some(name_node) = child.child_by_field_name("name") {
-                    methods.push(parsed.text_for_node(&name_node));
-                }
+            if matches!(child.kind(), "method_elem" | "method_spec")
+                && let Some(name_node) = child.child_by_field_name("name")
+            {
+                methods.push(parsed.text_for_node(&name_node));
             }
         }
     }
@@ -995,6 +1265,52 @@ fn extract_interface_methods(
     methods
 }
 
+/// Build a GoInterface from a type_spec node whose type is an interface.
+fn build_interface(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<GoInterface> {
+    let name_node = node.child_by_field_name("name")?;
+    let type_node = node.child_by_field_name("type")?;
+    if type_node.kind() != "interface_type" {
+        return None;
+    }
+
+    let mut methods = Vec::new();
+    let mut embedded = Vec::new();
+    for i in 0..type_node.named_child_count() {
+        if let Some(child) = type_node.named_child(i) {
+            match child.kind() {
+                "method_elem" | "method_spec" => {
+                    let Some(method_name) = child.child_by_field_name("name") else {
+                        continue;
+                    };
+                    let params = child
+                        .child_by_field_name("parameters")
+                        .map(|p| extract_params(parsed, &p))
+                        .unwrap_or_default();
+                    let return_types = child
+                        .child_by_field_name("result")
+                        .map(|r| extract_return_types(parsed, &r))
+                        .unwrap_or_default();
+                    methods.push(GoInterfaceMethod {
+                        name: parsed.text_for_node(&method_name),
+                        params,
+                        return_types,
+                        signature: parsed.text_for_node(&child),
+                    });
+                }
+                "type_elem" => embedded.push(parsed.text_for_node(&child)),
+                _ => {}
+            }
+        }
+    }
+
+    Some(GoInterface {
+        name: parsed.text_for_node(&name_node),
+        methods,
+        embedded,
+        location: parsed.location_for_node(node),
+    })
+}
+
 /// Collect variable/constant declarations.
 fn collect_declarations(
     parsed: &ParsedFile,
@@ -1056,8 +1372,12 @@ fn detect_db_operation_from_call(
 ) -> Option<DbOperation> {
     let func_node = node.child_by_field_name("function")?;
     let callee_expr = parsed.text_for_node(&func_node);
+    let nosql = detect_nosql_operation(&callee_expr);
 
     let (library, operation_type) = match callee_expr.as_str() {
+        // mongo-go-driver collections and go-redis clients, before the GORM
+        // method names they share (`.Find`, `.Delete`)
+        _ if nosql.is_some() => nosql?,
         s if s.to_lowercase().contains("queryx") || s.to_lowercase().contains("queryrowx") => {
             (DbLibrary::Sqlx, DbOperationType::Select)
         }
@@ -1104,6 +1424,18 @@ fn detect_db_operation_from_call(
 
     let text = parsed.text_for_node(node);
     let ast_location = parsed.location_for_node(node);
+    let model_name = if library == DbLibrary::Gorm {
+        extract_gorm_model(&text)
+    } else {
+        None
+    };
+    // Connections are opened through the library package itself; GORM query
+    // methods (`.Find`, `.Create`, ...) are matched on the method name alone.
+    let confidence = match (&library, &operation_type) {
+        (_, DbOperationType::Connect) => Confidence::High,
+        (DbLibrary::Gorm, _) => Confidence::Low,
+        _ => Confidence::Medium,
+    };
 
     Some(DbOperation {
         library,
@@ -1114,13 +1446,16 @@ fn detect_db_operation_from_call(
         eager_loading: None,
         in_loop: ctx.in_loop,
         in_iteration: false,
-        model_name: None,
+        model_name,
+        has_string_interpolation: is_interpolated_sql(parsed, node),
         relationship_field: None,
+        sql: None,
+        confidence,
         operation_text: text,
         location: CommonLocation {
             file_id: ast_location.file_id,
             line: ast_location.range.start_line + 1,
-            column: ast_location.range.start_col + 1,
+            column: ast_location.range.start_char_col + 1,
             start_byte: node.start_byte(),
             end_byte: node.end_byte(),
         },
@@ -1130,6 +1465,89 @@ fn detect_db_operation_from_call(
     })
 }
 
+/// Detect mongo-go-driver (`coll.Find`) and go-redis (`rdb.Get`) calls by receiver name.
+fn detect_nosql_operation(callee: &str) -> Option<(DbLibrary, DbOperationType)> {
+    let (receiver, method) = callee.rsplit_once('.')?;
+
+    if is_redis_receiver(receiver) {
+        return Some((
+            DbLibrary::Redis,
+            DbOperationType::from_cache_method(method)?,
+        ));
+    }
+    if is_mongo_collection_receiver(receiver) {
+        return Some((
+            DbLibrary::Mongo,
+            DbOperationType::from_document_method(method)?,
+        ));
+    }
+    None
+}
+
+/// Extract the model or table a GORM chain operates on, from
+/// `db.Model(&User{})` or `db.Table("users")`.
+fn extract_gorm_model(call_text: &str) -> Option<String> {
+    if let Some(start) = call_text.find(".Model(") {
+        let rest = &call_text[start + ".Model(".len()..];
+        let name: String = rest
+            .trim_start_matches(['&', ' '])
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+            .collect();
+        let name = name.rsplit('.').next().unwrap_or_default();
+        if !name.is_empty() {
+            return Some(name.to_string());
+        }
+    }
+
+    if let Some(start) = call_text.find(".Table(") {
+        let rest = &call_text[start + ".Table(".len()..];
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '`')?;
+        let table = rest[1..].split(quote).next()?;
+        if !table.is_empty() {
+            return Some(table.to_string());
+        }
+    }
+
+    None
+}
+
+/// Check whether a database call passes SQL built from non-literal values,
+/// via `fmt.Sprintf(...)` or `+` concatenation with a non-literal operand.
+fn is_interpolated_sql(parsed: &ParsedFile, node: &tree_sitter::Node) -> bool {
+    fn is_dynamic_string(parsed: &ParsedFile, node: tree_sitter::Node) -> bool {
+        match node.kind() {
+            "call_expression" => node
+                .child_by_field_name("function")
+                .is_some_and(|f| parsed.text_for_node(&f) == "fmt.Sprintf"),
+            "binary_expression" => {
+                let is_concat = node
+                    .child_by_field_name("operator")
+                    .is_some_and(|op| parsed.text_for_node(&op) == "+");
+                is_concat
+                    && ["left", "right"]
+                        .into_iter()
+                        .filter_map(|field| node.child_by_field_name(field))
+                        .any(|operand| {
+                            !matches!(
+                                operand.kind(),
+                                "interpreted_string_literal" | "raw_string_literal"
+                            ) || is_dynamic_string(parsed, operand)
+                        })
+            }
+            _ => false,
+        }
+    }
+
+    let Some(args) = node.child_by_field_name("arguments") else {
+        return false;
+    };
+    let mut cursor = args.walk();
+    args.named_children(&mut cursor)
+        .any(|arg| is_dynamic_string(parsed, arg))
+}
+
 /// Build a GoCallSite from a call_expression node.
 fn build_callsite(
     parsed: &ParsedFile,
@@ -1167,7 +1585,7 @@ fn build_callsite(
         location: CommonLocation {
             file_id: parsed.file_id,
             line: location.range.start_line + 1,
-            column: location.range.start_col + 1,
+            column: location.range.start_char_col + 1,
             start_byte: node.start_byte(),
             end_byte: node.end_byte(),
         },
@@ -1186,7 +1604,156 @@ fn build_callsite(
     })
 }
 
-/// Build a GoroutineSpawn from a go_statement node.
+/// Check if a call is `g.Go(...)`/`g.TryGo(...)` on an errgroup created in
+/// the enclosing function.
+fn is_errgroup_go(parsed: &ParsedFile, node: &tree_sitter::Node) -> bool {
+    let Some((receiver, method)) = method_call_parts(parsed, node) else {
+        return false;
+    };
+    matches!(method.as_str(), "Go" | "TryGo")
+        && enclosing_function_node(node).is_some_and(|func| {
+            concurrency_limiters(parsed, func)
+                .errgroups
+                .contains(&receiver)
+        })
+}
+
+/// Receiver identifier and method name of a `recv.Method(...)` call.
+fn method_call_parts(parsed: &ParsedFile, call: &tree_sitter::Node) -> Option<(String, String)> {
+    let function = call
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "selector_expression")?;
+    let operand = function
+        .child_by_field_name("operand")
+        .filter(|o| o.kind() == "identifier")?;
+    let field = function.child_by_field_name("field")?;
+    Some((parsed.text_for_node(&operand), parsed.text_for_node(&field)))
+}
+
+/// Concurrency limiters bound in a function body, by variable name.
+#[derive(Debug, Default)]
+struct ConcurrencyLimiters {
+    /// `g, ctx := errgroup.WithContext(ctx)` or `var g errgroup.Group`
+    errgroups: HashSet<String>,
+    /// `sem := semaphore.NewWeighted(n)`, with the literal weight
+    weighted: HashMap<String, Option<u32>>,
+    /// `sem := make(chan struct{}, n)`, with the literal capacity
+    channels: HashMap<String, Option<u32>>,
+}
+
+/// Collect the errgroups and semaphores bound in `func`.
+fn concurrency_limiters(parsed: &ParsedFile, func: tree_sitter::Node) -> ConcurrencyLimiters {
+    let mut limiters = ConcurrencyLimiters::default();
+    let mut stack = vec![func];
+    while let Some(node) = stack.pop() {
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+
+        let (left, right, ty) = match node.kind() {
+            "short_var_declaration" | "assignment_statement" => (
+                node.child_by_field_name("left"),
+                node.child_by_field_name("right"),
+                None,
+            ),
+            "var_spec" => (
+                node.child_by_field_name("name"),
+                node.child_by_field_name("value"),
+                node.child_by_field_name("type"),
+            ),
+            _ => continue,
+        };
+        let Some(name) = left
+            .map(|l| parsed.text_for_node(&l))
+            .and_then(|l| l.split(',').next().map(|n| n.trim().to_string()))
+            .filter(|n| !n.is_empty() && n != "_")
+        else {
+            continue;
+        };
+        let value: String = right
+            .map(|r| parsed.text_for_node(&r))
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect();
+        let ty = ty.map(|t| parsed.text_for_node(&t));
+
+        if value.starts_with("errgroup.WithContext(")
+            || ty.as_deref() == Some("errgroup.Group")
+            || matches!(
+                value.as_str(),
+                "errgroup.Group{}" | "&errgroup.Group{}" | "new(errgroup.Group)"
+            )
+        {
+            limiters.errgroups.insert(name);
+        } else if value.starts_with("semaphore.NewWeighted(") {
+            let weight = literal_argument(&value, "semaphore.NewWeighted(");
+            limiters.weighted.insert(name, weight);
+        } else if let Some(capacity) = value.strip_prefix("make(chanstruct{},") {
+            limiters
+                .channels
+                .insert(name, capacity.trim_end_matches(')').parse().ok());
+        }
+    }
+    limiters
+}
+
+/// Limit of the first semaphore acquired in `func`: a `sem.Acquire(...)` on a
+/// `semaphore.Weighted` or a `sem <- struct{}{}` on a buffered channel.
+///
+/// `None` if no semaphore is acquired; `Some(None)` if its size is not a literal.
+fn acquired_semaphore(
+    parsed: &ParsedFile,
+    func: tree_sitter::Node,
+    limiters: &ConcurrencyLimiters,
+) -> Option<Option<u32>> {
+    let mut stack = vec![func];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "call_expression" => {
+                if let Some((receiver, method)) = method_call_parts(parsed, &node)
+                    && matches!(method.as_str(), "Acquire" | "TryAcquire")
+                    && let Some(weight) = limiters.weighted.get(&receiver)
+                {
+                    return Some(*weight);
+                }
+            }
+            "send_statement" => {
+                if let Some(channel) = node.child_by_field_name("channel")
+                    && let Some(capacity) = limiters.channels.get(&parsed.text_for_node(&channel))
+                {
+                    return Some(*capacity);
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    None
+}
+
+/// Literal `n` of a `recv.SetLimit(n)` call in `func`.
+fn errgroup_limit(parsed: &ParsedFile, func: tree_sitter::Node, receiver: &str) -> Option<u32> {
+    let mut stack = vec![func];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "call_expression"
+            && method_call_parts(parsed, &node)
+                .is_some_and(|(recv, method)| recv == receiver && method == "SetLimit")
+        {
+            return literal_argument(&parsed.text_for_node(&node), ".SetLimit(");
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    None
+}
+
+/// Integer literal passed to the first `callee(` in `text`, e.g. `SetLimit(8)`.
+fn literal_argument(text: &str, callee: &str) -> Option<u32> {
+    let start = text.find(callee)? + callee.len();
+    text[start..].split(')').next()?.trim().parse().ok()
+}
+
+/// Build a GoroutineSpawn from a go_statement node or an errgroup `g.Go(...)` call.
 fn build_goroutine(
     parsed: &ParsedFile,
     node: &tree_sitter::Node,
@@ -1194,6 +1761,10 @@ fn build_goroutine(
 ) -> Option<GoroutineSpawn> {
     let text = parsed.text_for_node(node);
     let range = node.range();
+    let is_errgroup = node.kind() == "call_expression";
+    let func = enclosing_function_node(node);
+    let func_text = func.map(|func| parsed.text_for_node(&func));
+    let func_text = func_text.as_deref().unwrap_or_default();
 
     let has_recover = text.contains("recover()");
     let has_context_param = text.contains("ctx") || text.contains("context.Context");
@@ -1202,7 +1773,22 @@ fn build_goroutine(
         || text.contains("stop")
         || text.contains("<-ctx.Done()");
     let has_unbounded_channel_send = text.contains("<-") && !text.contains("select");
-    let is_anonymous = text.contains("go func(");
+    let is_anonymous = text.contains("go func(") || (is_errgroup && text.contains("(func("));
+    let uses_wait_group = is_errgroup
+        || func_text.contains("WaitGroup")
+        || (func_text.contains(".Add(") && text.contains(".Done()"));
+
+    let semaphore = func.and_then(|func| {
+        let limiters = concurrency_limiters(parsed, func);
+        acquired_semaphore(parsed, func, &limiters)
+    });
+    let is_bounded = is_errgroup || semaphore.is_some();
+    let bound_limit = if is_errgroup {
+        func.zip(method_call_parts(parsed, node))
+            .and_then(|(func, (receiver, _))| errgroup_limit(parsed, func, &receiver))
+    } else {
+        semaphore.flatten()
+    };
 
     Some(GoroutineSpawn {
         line: range.start_point.row as u32 + 1,
@@ -1213,6 +1799,11 @@ fn build_goroutine(
         has_done_channel,
         has_unbounded_channel_send,
         is_anonymous,
+        in_loop: ctx.in_loop,
+        uses_wait_group,
+        is_errgroup,
+        is_bounded,
+        bound_limit,
         function_name: ctx.current_function.clone(),
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
@@ -1220,6 +1811,18 @@ fn build_goroutine(
     })
 }
 
+/// Find the nearest enclosing function or method declaration.
+fn enclosing_function_node<'a>(node: &tree_sitter::Node<'a>) -> Option<tree_sitter::Node<'a>> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if matches!(n.kind(), "function_declaration" | "method_declaration") {
+            return Some(n);
+        }
+        current = n.parent();
+    }
+    None
+}
+
 /// Build a DeferStatement from a defer_statement node.
 fn build_defer(
     parsed: &ParsedFile,
@@ -1388,6 +1991,11 @@ fn build_select_statement(
     let has_receive_cases =
         text.contains("case ") && (text.contains(" <- ") || text.contains(":="));
     let is_cancellation_pattern = text.contains("ctx.Done()") || text.contains("ctx.Done");
+    let branches: Vec<SelectBranch> = node
+        .named_children(&mut node.walk())
+        .filter_map(|case| build_select_branch(parsed, &case))
+        .collect();
+    let has_timeout_case = branches.iter().any(|b| b.is_timeout);
 
     Some(SelectStatement {
         line: range.start_point.row as u32 + 1,
@@ -1398,6 +2006,8 @@ fn build_select_statement(
         has_send_cases,
         has_receive_cases,
         is_cancellation_pattern,
+        branches,
+        has_timeout_case,
         function_name: ctx.current_function.clone(),
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
@@ -1405,6 +2015,48 @@ fn build_select_statement(
     })
 }
 
+/// Build a SelectBranch from a communication_case or default_case node.
+fn build_select_branch(parsed: &ParsedFile, case: &tree_sitter::Node) -> Option<SelectBranch> {
+    let line = case.start_position().row as u32 + 1;
+    if case.kind() == "default_case" {
+        return Some(SelectBranch {
+            kind: SelectBranchKind::Default,
+            channel: None,
+            is_timeout: false,
+            line,
+        });
+    }
+    if case.kind() != "communication_case" {
+        return None;
+    }
+
+    let communication = case.child_by_field_name("communication")?;
+    let (kind, channel) = match communication.kind() {
+        "send_statement" => (
+            SelectBranchKind::Send,
+            communication.child_by_field_name("channel")?,
+        ),
+        "receive_statement" => {
+            let right = communication.child_by_field_name("right")?;
+            let channel = right.child_by_field_name("operand").unwrap_or(right);
+            (SelectBranchKind::Receive, channel)
+        }
+        _ => return None,
+    };
+    let channel = parsed.text_for_node(&channel);
+    let is_timeout = kind == SelectBranchKind::Receive
+        && (channel.starts_with("time.After")
+            || channel.starts_with("time.Tick")
+            || channel.ends_with(".Done()"));
+
+    Some(SelectBranch {
+        kind,
+        channel: Some(channel),
+        is_timeout,
+        line,
+    })
+}
+
 /// Collect error handling information.
 fn collect_error_handling(parsed: &ParsedFile, sem: &mut GoFileSemantics) {
     let root = parsed.tree.root_node();
@@ -1676,6 +2328,8 @@ fn collect_mutex_ops_recursive(
                 let operation_type = if is_rlock { "RLock" } else { "Lock" };
 
                 let uses_defer_unlock = check_for_defer_unlock_in_scope(node, parsed);
+                let has_matching_unlock =
+                    check_for_matching_unlock(node, parsed, &mutex_var, is_rlock);
                 let is_empty_critical_section = check_for_empty_critical_section(node, parsed);
 
                 sem.mutex_operations.push(MutexOperation {
@@ -1686,6 +2340,7 @@ fn collect_mutex_ops_recursive(
                     operation_type: operation_type.to_string(),
                     is_rlock,
                     uses_defer_unlock,
+                    has_matching_unlock,
                     is_empty_critical_section,
                     function_name: current_fn.clone(),
                     lock_start_byte: node.start_byte(),
@@ -1716,6 +2371,22 @@ fn check_for_defer_unlock_in_scope(node: tree_sitter::Node, parsed: &ParsedFile)
     false
 }
 
+/// Check whether the enclosing function unlocks `mutex_var` anywhere in its body.
+fn check_for_matching_unlock(
+    node: tree_sitter::Node,
+    parsed: &ParsedFile,
+    mutex_var: &str,
+    is_rlock: bool,
+) -> bool {
+    let Some(func) = enclosing_function_node(&node) else {
+        return false;
+    };
+    let unlock = if is_rlock { "RUnlock" } else { "Unlock" };
+    parsed
+        .text_for_node(&func)
+        .contains(&format!("{}.{}()", mutex_var, unlock))
+}
+
 fn check_for_empty_critical_section(node: tree_sitter::Node, parsed: &ParsedFile) -> bool {
     let parent = node.parent();
     if parent.is_none() {
@@ -1818,15 +2489,7 @@ fn collect_tags_for_struct(
 
                 if let Some(tag_str) = tag {
                     let range = field_decl.range();
-                    let location = AstLocation {
-                        file_id: parsed.file_id,
-                        range: crate::parse::ast::TextRange {
-                            start_line: range.start_point.row as u32,
-                            start_col: range.start_point.column as u32,
-                            end_line: range.end_point.row as u32,
-                            end_col: range.end_point.column as u32,
-                        },
-                    };
+                    let location = parsed.location_for_node(&field_decl);
                     parse_and_add_tag(
                         parsed,
                         &tag_str,
@@ -2033,6 +2696,124 @@ func main() {
         assert_eq!(sem.goroutines.len(), 1);
     }
 
+    #[test]
+    fn flags_fire_and_forget_goroutine_as_potential_leak() {
+        let src = r#"
+package main
+
+func notify(events []Event) {
+    for _, e := range events {
+        go func(e Event) {
+            publish(e)
+        }(e)
+    }
+}
+
+func process(items []Item) {
+    var wg sync.WaitGroup
+    for _, item := range items {
+        wg.Add(1)
+        go func(it Item) {
+            defer wg.Done()
+            handle(it)
+        }(item)
+    }
+    wg.Wait()
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.goroutines.len(), 2);
+
+        let leaks = sem.potential_goroutine_leaks();
+        assert_eq!(leaks.len(), 1);
+        assert_eq!(leaks[0].enclosing_function.as_deref(), Some("notify"));
+        assert!(leaks[0].in_loop);
+    }
+
+    #[test]
+    fn goroutine_with_context_is_not_a_potential_leak() {
+        let src = r#"
+package main
+
+func run(ctx context.Context) {
+    go func() {
+        <-ctx.Done()
+    }()
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert!(sem.potential_goroutine_leaks().is_empty());
+    }
+
+    #[test]
+    fn flags_locks_without_matching_unlock() {
+        let src = r#"
+package main
+
+func (s *Store) Get(k string) string {
+    s.mu.Lock()
+    defer s.mu.Unlock()
+    return s.data[k]
+}
+
+func (s *Store) Set(k, v string) {
+    s.mu.Lock()
+    s.data[k] = v
+    s.mu.Unlock()
+}
+
+func (s *Store) Reset() {
+    s.mu.Lock()
+    s.other.Unlock()
+    s.data = nil
+}
+
+func (s *Store) Peek(k string) string {
+    s.rw.RLock()
+    return s.data[k]
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.mutex_operations.len(), 4);
+
+        let unpaired = sem.unpaired_locks();
+        let functions: Vec<_> = unpaired
+            .iter()
+            .filter_map(|op| op.enclosing_function.as_deref())
+            .collect();
+        assert_eq!(functions, vec!["Reset", "Peek"]);
+    }
+
+    #[test]
+    fn flags_io_functions_without_context() {
+        let src = r#"
+package main
+
+func LoadUser(db *sql.DB, id int) {
+    db.Query("SELECT * FROM users WHERE id = ?", id)
+}
+
+func LoadUserCtx(ctx context.Context, db *sql.DB, id int) {
+    db.QueryContext(ctx, "SELECT * FROM users WHERE id = ?", id)
+}
+
+func SaveUser(ctx context.Context, db *sql.DB, id int) {
+    db.ExecContext(context.Background(), "DELETE FROM users WHERE id = ?", id)
+}
+
+func loadLocal(db *sql.DB) {
+    db.Query("SELECT 1")
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let names: Vec<_> = sem
+            .functions_missing_context()
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(names, vec!["LoadUser", "SaveUser"]);
+    }
+
     #[test]
     fn collects_defer() {
         let src = r#"
@@ -2078,6 +2859,138 @@ func main() {
         );
     }
 
+    #[test]
+    fn errgroup_goroutines_are_bounded() {
+        use crate::semantics::common::CommonSemantics;
+
+        let src = r#"
+package main
+
+import "golang.org/x/sync/errgroup"
+
+func fetchAll(ctx context.Context, urls []string) error {
+    g, ctx := errgroup.WithContext(ctx)
+    g.SetLimit(8)
+    for _, url := range urls {
+        g.Go(func() error {
+            return fetch(ctx, url)
+        })
+    }
+    return g.Wait()
+}
+
+func fireAll(urls []string) {
+    for _, url := range urls {
+        go fetch(context.Background(), url)
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.goroutines.len(), 2);
+
+        let managed = &sem.goroutines[0];
+        assert!(managed.is_errgroup);
+        assert!(managed.is_anonymous);
+        assert!(managed.in_loop);
+        assert!(managed.is_bounded);
+        assert_eq!(managed.bound_limit, Some(8));
+
+        let unbounded = sem.unbounded_spawns();
+        assert_eq!(unbounded.len(), 1);
+        assert_eq!(unbounded[0].enclosing_function.as_deref(), Some("fireAll"));
+    }
+
+    #[test]
+    fn semaphore_guarded_goroutine_is_bounded() {
+        let src = r#"
+package main
+
+import "golang.org/x/sync/semaphore"
+
+func process(ctx context.Context, jobs []Job) {
+    sem := semaphore.NewWeighted(4)
+    for _, job := range jobs {
+        if err := sem.Acquire(ctx, 1); err != nil {
+            return
+        }
+        go func(j Job) {
+            defer sem.Release(1)
+            run(j)
+        }(job)
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let goroutine = &sem.goroutines[0];
+        assert!(!goroutine.is_errgroup);
+        assert!(goroutine.is_bounded);
+        assert_eq!(goroutine.bound_limit, Some(4));
+    }
+
+    #[test]
+    fn channel_semaphore_goroutine_is_bounded() {
+        let src = r#"
+package main
+
+func process(jobs []Job) {
+    sem := make(chan struct{}, 10)
+    for _, job := range jobs {
+        sem <- struct{}{}
+        go func(j Job) {
+            defer func() { <-sem }()
+            run(j)
+        }(job)
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let goroutine = &sem.goroutines[0];
+        assert!(goroutine.is_bounded);
+        assert_eq!(goroutine.bound_limit, Some(10));
+    }
+
+    #[test]
+    fn pool_acquire_does_not_bound_goroutines() {
+        let src = r#"
+package main
+
+import "github.com/jackc/pgx/v5/pgxpool"
+
+func process(ctx context.Context, pool *pgxpool.Pool, jobs []Job) {
+    for _, job := range jobs {
+        conn, err := pool.Acquire(ctx)
+        if err != nil {
+            return
+        }
+        go func(j Job) {
+            defer conn.Release()
+            run(j)
+        }(job)
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let goroutine = &sem.goroutines[0];
+        assert!(!goroutine.is_bounded);
+        assert_eq!(goroutine.bound_limit, None);
+    }
+
+    #[test]
+    fn go_method_on_non_errgroup_is_not_a_spawn() {
+        let src = r#"
+package main
+
+// Unlike errgroup.Group, a Runner executes tasks inline.
+func runAll(r *Runner, tasks []Task) {
+    for _, t := range tasks {
+        r.Go(t)
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert!(sem.goroutines.is_empty());
+    }
+
     #[test]
     fn collects_select_statement() {
         let src = r#"
@@ -2099,6 +3012,66 @@ func main() {
         assert!(select.has_default);
     }
 
+    #[test]
+    fn select_branches_record_kind_and_channel() {
+        let src = r#"
+package main
+
+func worker(ctx context.Context, in chan int, out chan int) {
+    select {
+    case v := <-in:
+        out <- v
+    case out <- 0:
+    case <-ctx.Done():
+        return
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let select = &sem.select_statements[0];
+        let branches: Vec<_> = select
+            .branches
+            .iter()
+            .map(|b| (b.kind, b.channel.as_deref(), b.is_timeout))
+            .collect();
+        assert_eq!(
+            branches,
+            vec![
+                (SelectBranchKind::Receive, Some("in"), false),
+                (SelectBranchKind::Send, Some("out"), false),
+                (SelectBranchKind::Receive, Some("ctx.Done()"), true),
+            ]
+        );
+        assert!(select.has_timeout_case);
+        assert!(sem.selects_without_default_or_timeout().is_empty());
+    }
+
+    #[test]
+    fn select_on_data_channels_only_can_block_forever() {
+        let src = r#"
+package main
+
+func relay(a chan int, b chan int) {
+    select {
+    case v := <-a:
+        fmt.Println(v)
+    case v := <-b:
+        fmt.Println(v)
+    }
+    select {
+    case v := <-a:
+        fmt.Println(v)
+    case <-time.After(time.Second):
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let blocking = sem.selects_without_default_or_timeout();
+        assert_eq!(blocking.len(), 1);
+        assert_eq!(blocking[0].start_byte, sem.select_statements[0].start_byte);
+        assert_eq!(blocking[0].enclosing_function.as_deref(), Some("relay"));
+    }
+
     #[test]
     fn channel_ops_track_in_select() {
         let src = r#"
@@ -2171,6 +3144,46 @@ func getUser(db *sql.DB, id int) error {
         assert_eq!(sem.db_operations[0].operation_type.as_str(), "SELECT");
     }
 
+    #[test]
+    fn flags_sprintf_query_as_interpolated() {
+        let src = r#"
+package main
+
+import (
+    "database/sql"
+    "fmt"
+)
+
+func getUser(db *sql.DB, id string) {
+    db.Query(fmt.Sprintf("SELECT * FROM users WHERE id = %s", id))
+    db.Exec("DELETE FROM users WHERE id = " + id)
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.db_operations.len(), 2);
+        assert!(
+            sem.db_operations
+                .iter()
+                .all(|op| op.has_string_interpolation)
+        );
+    }
+
+    #[test]
+    fn parameterized_query_not_interpolated() {
+        let src = r#"
+package main
+
+import "database/sql"
+
+func getUser(db *sql.DB, id int) {
+    db.Query("SELECT * FROM users " + "WHERE id = ?", id)
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.db_operations.len(), 1);
+        assert!(!sem.db_operations[0].has_string_interpolation);
+    }
+
     #[test]
     fn detects_gorm_operations() {
         let src = r#"
@@ -2195,6 +3208,91 @@ func findUsers(db *gorm.DB) []User {
         assert_eq!(sem.db_operations[0].operation_type.as_str(), "SELECT");
     }
 
+    #[test]
+    fn extracts_gorm_model_and_table_names() {
+        let src = r#"
+package main
+
+import "gorm.io/gorm"
+
+func activeUsers(db *gorm.DB) {
+    var users []User
+    db.Model(&User{}).Where("active = ?", true).Find(&users)
+    var results []Result
+    db.Table("orders").Where("total > ?", 100).Find(&results)
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let models: Vec<_> = sem
+            .db_operations
+            .iter()
+            .filter_map(|op| op.model_name.as_deref())
+            .collect();
+        assert!(models.contains(&"User"));
+        assert!(models.contains(&"orders"));
+    }
+
+    #[test]
+    fn collects_interface_method_signatures() {
+        let src = r#"
+package store
+
+type UserStore interface {
+    io.Closer
+    Get(ctx context.Context, id string) (*User, error)
+    Delete(id string) error
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.interfaces.len(), 1);
+        let iface = &sem.interfaces[0];
+        assert_eq!(iface.name, "UserStore");
+        assert_eq!(iface.embedded, vec!["io.Closer".to_string()]);
+        assert_eq!(iface.methods.len(), 2);
+
+        let get = &iface.methods[0];
+        assert_eq!(get.name, "Get");
+        assert_eq!(
+            get.signature,
+            "Get(ctx context.Context, id string) (*User, error)"
+        );
+        assert_eq!(get.params.len(), 2);
+        assert_eq!(get.params[1].param_type, "string");
+        assert_eq!(get.return_types, vec!["*User", "error"]);
+
+        let delete = &iface.methods[1];
+        assert_eq!(delete.signature, "Delete(id string) error");
+        assert_eq!(delete.return_types, vec!["error"]);
+
+        let type_decl = sem.types.iter().find(|t| t.name == "UserStore").unwrap();
+        assert_eq!(type_decl.interface_methods, vec!["Get", "Delete"]);
+    }
+
+    #[test]
+    fn finds_types_implementing_interface() {
+        let src = r#"
+package shapes
+
+type Shape interface {
+    Area() float64
+    Perimeter() float64
+}
+
+type Square struct{ side float64 }
+
+func (s *Square) Area() float64      { return s.side * s.side }
+func (s *Square) Perimeter() float64 { return 4 * s.side }
+
+type Line struct{}
+
+func (l Line) Perimeter() float64 { return 0 }
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert!(sem.interface("Shape").is_some());
+        assert_eq!(sem.types_implementing("Shape"), vec!["Square".to_string()]);
+        assert!(sem.types_implementing("Missing").is_empty());
+    }
+
     #[test]
     fn collects_struct_tag_annotations() {
         let src = r#"
@@ -0,0 +1,55 @@
+//! Java semantic model (classes, methods, imports, calls, catch clauses)
+
+pub mod model;
+
+pub use model::JavaFileSemantics;
+
+use anyhow::Result;
+
+use crate::parse::ast::ParsedFile;
+
+/// Build the semantic model for a single Java file.
+///
+/// This is the entry point the engine will call after parsing.
+pub fn build_java_semantics(parsed: &ParsedFile) -> Result<JavaFileSemantics> {
+    Ok(JavaFileSemantics::from_parsed(parsed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ast::FileId;
+    use crate::parse::java::parse_java_file;
+    use crate::types::context::{Language, SourceFile};
+
+    #[test]
+    fn build_java_semantics_populates_basic_structure() {
+        let src = r#"
+package com.example;
+
+import java.util.List;
+
+public class Greeter {
+    public String greet(String name) {
+        return String.format("Hello, %s", name);
+    }
+}
+"#;
+        let sf = SourceFile {
+            path: "Greeter.java".to_string(),
+            language: Language::Java,
+            content: src.to_string(),
+        };
+        let parsed = parse_java_file(FileId(1), &sf).expect("parsing should succeed");
+        let sem = build_java_semantics(&parsed).expect("semantics building should succeed");
+
+        assert_eq!(sem.file_id, FileId(1));
+        assert_eq!(sem.path, "Greeter.java");
+        assert_eq!(sem.language, Language::Java);
+        assert_eq!(sem.package_name, "com.example");
+        assert_eq!(sem.imports.len(), 1);
+        assert_eq!(sem.classes.len(), 1);
+        assert_eq!(sem.methods.len(), 1);
+        assert_eq!(sem.calls.len(), 1);
+    }
+}
@@ -0,0 +1,972 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::parse::ast::{AstLocation, FileId, ParsedFile};
+use crate::semantics::common::{CommonLocation, calls::FunctionCall};
+use crate::semantics::metrics::{JAVA_RULES, cyclomatic_complexity, max_nesting_depth};
+use crate::semantics::references::{self, referenced_identifiers};
+use crate::types::context::Language;
+
+/// Semantic model for a single Java file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaFileSemantics {
+    pub file_id: FileId,
+    pub path: String,
+    pub language: Language,
+    /// Number of lines in the source file
+    #[serde(default)]
+    pub line_count: usize,
+
+    /// Names referenced outside import statements (for unused-import detection)
+    #[serde(default)]
+    pub identifiers: BTreeSet<String>,
+
+    /// Package name (empty for the default package)
+    pub package_name: String,
+
+    /// Import declarations
+    pub imports: Vec<JavaImport>,
+
+    /// Type declarations (class, interface, enum, record), including nested ones
+    pub classes: Vec<JavaClass>,
+
+    /// Method and constructor declarations
+    pub methods: Vec<JavaMethod>,
+
+    /// Method invocations
+    pub calls: Vec<JavaCallSite>,
+
+    /// Catch clauses of try statements
+    pub catch_clauses: Vec<JavaCatchClause>,
+}
+
+/// Representation of a Java import declaration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaImport {
+    /// The imported name, e.g., "java.util.List" or "java.util" for `java.util.*`
+    pub path: String,
+    /// Whether this is a static import (`import static ...`)
+    pub is_static: bool,
+    /// Whether this is an on-demand import (`import foo.*`)
+    pub is_wildcard: bool,
+    /// Byte range of the import declaration in the source
+    #[serde(default)]
+    pub start_byte: usize,
+    #[serde(default)]
+    pub end_byte: usize,
+    /// Source text of the import declaration
+    #[serde(default)]
+    pub raw_text: String,
+    /// Location
+    pub location: AstLocation,
+}
+
+impl JavaImport {
+    /// The simple name brought into scope, e.g., "List" for `java.util.List`.
+    ///
+    /// Returns `None` for wildcard imports.
+    pub fn simple_name(&self) -> Option<&str> {
+        if self.is_wildcard {
+            None
+        } else {
+            self.path.rsplit('.').next()
+        }
+    }
+
+    /// Whether this import comes from the JDK (`java.*` / `javax.*`).
+    pub fn is_stdlib(&self) -> bool {
+        self.path == "java"
+            || self.path.starts_with("java.")
+            || self.path == "javax"
+            || self.path.starts_with("javax.")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JavaTypeKind {
+    Class,
+    Interface,
+    Enum,
+    Record,
+}
+
+/// Representation of a Java type declaration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaClass {
+    pub name: String,
+    pub kind: JavaTypeKind,
+    /// Modifier keywords, e.g., ["public", "abstract"]
+    pub modifiers: Vec<String>,
+    /// Annotations on the type declaration
+    pub annotations: Vec<JavaAnnotation>,
+    /// Superclass from the `extends` clause (classes only)
+    pub superclass: Option<String>,
+    /// Implemented interfaces (or extended interfaces for an interface)
+    pub interfaces: Vec<String>,
+    /// Name of the enclosing type for nested declarations
+    pub enclosing_class: Option<String>,
+    /// Location
+    pub location: AstLocation,
+    /// Start byte offset for patching
+    pub start_byte: usize,
+    /// End byte offset for patching
+    pub end_byte: usize,
+}
+
+/// Representation of a Java annotation (`@Override`, `@GetMapping("/users")`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaAnnotation {
+    /// Annotation name without the `@`, e.g., "GetMapping"
+    pub name: String,
+    /// Raw argument list without the parentheses, if any
+    pub arguments: Option<String>,
+    /// Full annotation text
+    pub text: String,
+    /// Location
+    pub location: AstLocation,
+    /// Start byte offset
+    pub start_byte: usize,
+    /// End byte offset
+    pub end_byte: usize,
+}
+
+/// Representation of a Java method or constructor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaMethod {
+    pub name: String,
+    /// Name of the declaring type
+    pub class_name: Option<String>,
+    /// Whether this is a constructor
+    pub is_constructor: bool,
+    /// Modifier keywords, e.g., ["public", "static"]
+    pub modifiers: Vec<String>,
+    /// Annotations on the method
+    pub annotations: Vec<JavaAnnotation>,
+    pub params: Vec<JavaParam>,
+    /// Declared return type (`None` for constructors)
+    pub return_type: Option<String>,
+    /// Exception types from the `throws` clause
+    pub throws: Vec<String>,
+    /// Whether the method has a body (false for abstract/interface methods)
+    pub has_body: bool,
+    /// Whether the method body contains a try statement
+    pub has_try_catch: bool,
+    /// Location
+    pub location: AstLocation,
+    /// Start byte offset for patching
+    pub start_byte: usize,
+    /// End byte offset for patching
+    pub end_byte: usize,
+    /// Cyclomatic complexity (1 for straight-line code)
+    pub cyclomatic_complexity: u32,
+    /// Deepest nesting of control blocks (1 for straight-line code)
+    pub max_nesting_depth: u32,
+}
+
+impl JavaMethod {
+    /// Whether the method carries the given modifier keyword.
+    pub fn has_modifier(&self, modifier: &str) -> bool {
+        self.modifiers.iter().any(|m| m == modifier)
+    }
+
+    /// Whether this is a static method.
+    pub fn is_static(&self) -> bool {
+        self.has_modifier("static")
+    }
+
+    /// Qualified name, e.g., "UserService.findAll".
+    pub fn qualified_name(&self) -> String {
+        match &self.class_name {
+            Some(class) => format!("{}.{}", class, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaParam {
+    pub name: String,
+    pub param_type: String,
+    /// Whether this is a varargs parameter (`String... args`)
+    pub is_varargs: bool,
+}
+
+/// Representation of a method invocation in the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaCallSite {
+    /// Resolution information for this call site.
+    pub function_call: FunctionCall,
+    /// Arguments (simple text representation)
+    pub args_repr: String,
+    /// Whether this call is inside a loop
+    pub in_loop: bool,
+    /// Start byte offset
+    pub start_byte: usize,
+    /// End byte offset
+    pub end_byte: usize,
+}
+
+/// Representation of a `catch` clause.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaCatchClause {
+    /// Caught exception types (several for multi-catch)
+    pub exception_types: Vec<String>,
+    /// Name of the exception variable
+    pub variable: Option<String>,
+    /// Whether the catch body is empty (comments only)
+    pub is_empty: bool,
+    /// Whether the catch body logs the exception
+    pub has_logging: bool,
+    /// Whether the catch body throws
+    pub rethrows: bool,
+    /// Name of the enclosing method
+    pub function_name: Option<String>,
+    /// Location
+    pub location: AstLocation,
+    /// Start byte offset
+    pub start_byte: usize,
+    /// End byte offset
+    pub end_byte: usize,
+}
+
+impl JavaFileSemantics {
+    /// Build the semantic model from a parsed Java file.
+    pub fn from_parsed(parsed: &ParsedFile) -> Self {
+        let mut sem = JavaFileSemantics {
+            file_id: parsed.file_id,
+            path: parsed.path.clone(),
+            language: parsed.language,
+            line_count: parsed.source.lines().count(),
+            identifiers: referenced_identifiers(parsed, &references::JAVA_RULES),
+            package_name: String::new(),
+            imports: Vec::new(),
+            classes: Vec::new(),
+            methods: Vec::new(),
+            calls: Vec::new(),
+            catch_clauses: Vec::new(),
+        };
+
+        if parsed.language == Language::Java {
+            collect_semantics(parsed, &mut sem);
+        }
+
+        sem
+    }
+
+    /// Find a type declaration by name.
+    pub fn find_class(&self, name: &str) -> Option<&JavaClass> {
+        self.classes.iter().find(|c| c.name == name)
+    }
+
+    /// Methods declared on the given type.
+    pub fn methods_of<'a>(&'a self, class_name: &'a str) -> impl Iterator<Item = &'a JavaMethod> {
+        self.methods
+            .iter()
+            .filter(move |m| m.class_name.as_deref() == Some(class_name))
+    }
+}
+
+/// Context for tracking state during AST traversal.
+#[derive(Default, Clone)]
+struct TraversalContext {
+    in_loop: bool,
+    current_class: Option<String>,
+    current_function: Option<String>,
+    current_qualified_name: Option<String>,
+}
+
+/// Collect semantics by walking the tree-sitter AST.
+fn collect_semantics(parsed: &ParsedFile, sem: &mut JavaFileSemantics) {
+    let root = parsed.tree.root_node();
+    let ctx = TraversalContext::default();
+    walk_nodes_with_context(root, parsed, sem, ctx);
+}
+
+/// Walk nodes while tracking context.
+fn walk_nodes_with_context(
+    node: tree_sitter::Node,
+    parsed: &ParsedFile,
+    sem: &mut JavaFileSemantics,
+    ctx: TraversalContext,
+) {
+    let new_ctx = match node.kind() {
+        "for_statement" | "enhanced_for_statement" | "while_statement" | "do_statement" => {
+            TraversalContext {
+                in_loop: true,
+                ..ctx.clone()
+            }
+        }
+        "class_declaration"
+        | "interface_declaration"
+        | "enum_declaration"
+        | "record_declaration" => {
+            let class_name = node
+                .child_by_field_name("name")
+                .map(|n| parsed.text_for_node(&n));
+            TraversalContext {
+                in_loop: false,
+                current_class: class_name,
+                current_function: None,
+                current_qualified_name: None,
+            }
+        }
+        "method_declaration" | "constructor_declaration" => {
+            let func_name = node
+                .child_by_field_name("name")
+                .map(|n| parsed.text_for_node(&n));
+            let qualified = match (&ctx.current_class, &func_name) {
+                (Some(class), Some(name)) => Some(format!("{}.{}", class, name)),
+                _ => func_name.clone(),
+            };
+            TraversalContext {
+                in_loop: false,
+                current_function: func_name,
+                current_qualified_name: qualified,
+                ..ctx.clone()
+            }
+        }
+        _ => ctx.clone(),
+    };
+
+    match node.kind() {
+        "package_declaration" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                if matches!(child.kind(), "scoped_identifier" | "identifier") {
+                    sem.package_name = parsed.text_for_node(&child);
+                    break;
+                }
+            }
+        }
+        "import_declaration" => {
+            if let Some(import) = build_import(parsed, &node) {
+                sem.imports.push(import);
+            }
+        }
+        "class_declaration"
+        | "interface_declaration"
+        | "enum_declaration"
+        | "record_declaration" => {
+            if let Some(class) = build_class(parsed, &node, &ctx) {
+                sem.classes.push(class);
+            }
+        }
+        "method_declaration" | "constructor_declaration" => {
+            if let Some(method) = build_method(parsed, &node, &ctx) {
+                sem.methods.push(method);
+            }
+        }
+        "method_invocation" => {
+            if let Some(call) = build_callsite(parsed, &node, &new_ctx) {
+                sem.calls.push(call);
+            }
+        }
+        "catch_clause" => {
+            if let Some(clause) = build_catch_clause(parsed, &node, &new_ctx) {
+                sem.catch_clauses.push(clause);
+            }
+        }
+        _ => {}
+    }
+
+    let child_count = node.child_count();
+    for i in 0..child_count {
+        if let Some(child) = node.child(i) {
+            walk_nodes_with_context(child, parsed, sem, new_ctx.clone());
+        }
+    }
+}
+
+/// Build a JavaImport from an import_declaration node.
+fn build_import(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<JavaImport> {
+    let mut path = None;
+    let mut is_static = false;
+    let mut is_wildcard = false;
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "static" => is_static = true,
+            "asterisk" => is_wildcard = true,
+            "scoped_identifier" | "identifier" => path = Some(parsed.text_for_node(&child)),
+            _ => {}
+        }
+    }
+
+    Some(JavaImport {
+        path: path?,
+        is_static,
+        is_wildcard,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        raw_text: parsed.text_for_node(node),
+        location: parsed.location_for_node(node),
+    })
+}
+
+/// Build a JavaClass from a type declaration node.
+fn build_class(
+    parsed: &ParsedFile,
+    node: &tree_sitter::Node,
+    ctx: &TraversalContext,
+) -> Option<JavaClass> {
+    let name = parsed.text_for_node(&node.child_by_field_name("name")?);
+
+    let kind = match node.kind() {
+        "interface_declaration" => JavaTypeKind::Interface,
+        "enum_declaration" => JavaTypeKind::Enum,
+        "record_declaration" => JavaTypeKind::Record,
+        _ => JavaTypeKind::Class,
+    };
+
+    let (modifiers, annotations) = collect_modifiers(parsed, node);
+
+    // `superclass` wraps the `extends` keyword and the type
+    let superclass = node.child_by_field_name("superclass").and_then(|sc| {
+        let mut cursor = sc.walk();
+        sc.named_children(&mut cursor)
+            .next()
+            .map(|t| parsed.text_for_node(&t))
+    });
+
+    // Classes, enums and records use `super_interfaces`; interfaces use `extends_interfaces`
+    let mut interfaces = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if matches!(child.kind(), "super_interfaces" | "extends_interfaces") {
+            let mut list_cursor = child.walk();
+            for list in child.named_children(&mut list_cursor) {
+                if list.kind() == "type_list" {
+                    let mut type_cursor = list.walk();
+                    interfaces.extend(
+                        list.named_children(&mut type_cursor)
+                            .map(|t| parsed.text_for_node(&t)),
+                    );
+                }
+            }
+        }
+    }
+
+    Some(JavaClass {
+        name,
+        kind,
+        modifiers,
+        annotations,
+        superclass,
+        interfaces,
+        enclosing_class: ctx.current_class.clone(),
+        location: parsed.location_for_node(node),
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+    })
+}
+
+/// Build a JavaMethod from a method_declaration or constructor_declaration node.
+fn build_method(
+    parsed: &ParsedFile,
+    node: &tree_sitter::Node,
+    ctx: &TraversalContext,
+) -> Option<JavaMethod> {
+    let name = parsed.text_for_node(&node.child_by_field_name("name")?);
+    let is_constructor = node.kind() == "constructor_declaration";
+
+    let (modifiers, annotations) = collect_modifiers(parsed, node);
+
+    let params = node
+        .child_by_field_name("parameters")
+        .map(|p| extract_params(parsed, &p))
+        .unwrap_or_default();
+
+    let return_type = if is_constructor {
+        None
+    } else {
+        node.child_by_field_name("type")
+            .map(|t| parsed.text_for_node(&t))
+    };
+
+    let mut throws = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "throws" {
+            let mut throws_cursor = child.walk();
+            throws.extend(
+                child
+                    .named_children(&mut throws_cursor)
+                    .map(|t| parsed.text_for_node(&t)),
+            );
+        }
+    }
+
+    let body = node.child_by_field_name("body");
+    let has_try_catch = body.is_some_and(|b| contains_kind(&b, "catch_clause"));
+
+    Some(JavaMethod {
+        name,
+        class_name: ctx.current_class.clone(),
+        is_constructor,
+        modifiers,
+        annotations,
+        params,
+        return_type,
+        throws,
+        has_body: body.is_some(),
+        has_try_catch,
+        location: parsed.location_for_node(node),
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &JAVA_RULES),
+        max_nesting_depth: max_nesting_depth(node, &JAVA_RULES),
+    })
+}
+
+/// Collect modifier keywords and annotations from the `modifiers` child of a declaration.
+fn collect_modifiers(
+    parsed: &ParsedFile,
+    node: &tree_sitter::Node,
+) -> (Vec<String>, Vec<JavaAnnotation>) {
+    let mut modifiers = Vec::new();
+    let mut annotations = Vec::new();
+
+    let mut cursor = node.walk();
+    let Some(mods) = node.children(&mut cursor).find(|c| c.kind() == "modifiers") else {
+        return (modifiers, annotations);
+    };
+
+    let mut mods_cursor = mods.walk();
+    for child in mods.children(&mut mods_cursor) {
+        match child.kind() {
+            "annotation" | "marker_annotation" => {
+                if let Some(ann) = build_annotation(parsed, &child) {
+                    annotations.push(ann);
+                }
+            }
+            "line_comment" | "block_comment" => {}
+            _ => modifiers.push(parsed.text_for_node(&child)),
+        }
+    }
+
+    (modifiers, annotations)
+}
+
+/// Build a JavaAnnotation from an annotation or marker_annotation node.
+fn build_annotation(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<JavaAnnotation> {
+    let name = parsed.text_for_node(&node.child_by_field_name("name")?);
+    let arguments = node.child_by_field_name("arguments").map(|args| {
+        let text = parsed.text_for_node(&args);
+        text.strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+            .unwrap_or(&text)
+            .trim()
+            .to_string()
+    });
+
+    Some(JavaAnnotation {
+        name,
+        arguments,
+        text: parsed.text_for_node(node),
+        location: parsed.location_for_node(node),
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+    })
+}
+
+/// Extract parameters from a formal_parameters node.
+fn extract_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<JavaParam> {
+    let mut params = Vec::new();
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "formal_parameter" => {
+                let name = child
+                    .child_by_field_name("name")
+                    .map(|n| parsed.text_for_node(&n))
+                    .unwrap_or_default();
+                let param_type = child
+                    .child_by_field_name("type")
+                    .map(|t| parsed.text_for_node(&t))
+                    .unwrap_or_default();
+                params.push(JavaParam {
+                    name,
+                    param_type,
+                    is_varargs: false,
+                });
+            }
+            "spread_parameter" => {
+                // spread_parameter: [modifiers] type `...` variable_declarator
+                let mut param_type = String::new();
+                let mut name = String::new();
+                let mut spread_cursor = child.walk();
+                for part in child.named_children(&mut spread_cursor) {
+                    match part.kind() {
+                        "modifiers" => {}
+                        "variable_declarator" => {
+                            name = part
+                                .child_by_field_name("name")
+                                .map(|n| parsed.text_for_node(&n))
+                                .unwrap_or_default();
+                        }
+                        _ if param_type.is_empty() => param_type = parsed.text_for_node(&part),
+                        _ => {}
+                    }
+                }
+                params.push(JavaParam {
+                    name,
+                    param_type,
+                    is_varargs: true,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    params
+}
+
+/// Build a JavaCallSite from a method_invocation node.
+fn build_callsite(
+    parsed: &ParsedFile,
+    node: &tree_sitter::Node,
+    ctx: &TraversalContext,
+) -> Option<JavaCallSite> {
+    let name = parsed.text_for_node(&node.child_by_field_name("name")?);
+    let object = node
+        .child_by_field_name("object")
+        .map(|o| parsed.text_for_node(&o));
+
+    let callee_expr = match &object {
+        Some(obj) => format!("{}.{}", obj, name),
+        None => name.clone(),
+    };
+
+    let args_repr = node
+        .child_by_field_name("arguments")
+        .map(|a| parsed.text_for_node(&a))
+        .unwrap_or_default();
+
+    let callee_parts: Vec<String> = callee_expr.split('.').map(String::from).collect();
+
+    // Unqualified calls and `this.foo()` target the enclosing class
+    let is_self_call = object.as_deref().is_none_or(|o| o == "this");
+
+    // `Foo.bar()` on a capitalized receiver is a static call on a (usually imported) type
+    let is_import_call = object.as_deref().is_some_and(|o| {
+        !o.contains('(')
+            && o.split('.')
+                .next()
+                .and_then(|s| s.chars().next())
+                .is_some_and(|c| c.is_uppercase())
+    });
+    let import_alias = if is_import_call {
+        Some(callee_parts[0].clone())
+    } else {
+        None
+    };
+
+    let location = parsed.location_for_node(node);
+
+    let function_call = FunctionCall {
+        callee_expr,
+        callee_parts,
+        caller_function: ctx.current_function.clone().unwrap_or_default(),
+        caller_qualified_name: ctx.current_qualified_name.clone().unwrap_or_default(),
+        location: CommonLocation {
+            file_id: parsed.file_id,
+            line: location.range.start_line + 1,
+            column: location.range.start_char_col + 1,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+        },
+        is_self_call,
+        is_import_call,
+        import_alias,
+    };
+
+    Some(JavaCallSite {
+        function_call,
+        args_repr,
+        in_loop: ctx.in_loop,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+    })
+}
+
+/// Build a JavaCatchClause from a catch_clause node.
+fn build_catch_clause(
+    parsed: &ParsedFile,
+    node: &tree_sitter::Node,
+    ctx: &TraversalContext,
+) -> Option<JavaCatchClause> {
+    let mut exception_types = Vec::new();
+    let mut variable = None;
+
+    let mut cursor = node.walk();
+    if let Some(param) = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "catch_formal_parameter")
+    {
+        variable = param
+            .child_by_field_name("name")
+            .map(|n| parsed.text_for_node(&n));
+        let mut param_cursor = param.walk();
+        if let Some(catch_type) = param
+            .named_children(&mut param_cursor)
+            .find(|c| c.kind() == "catch_type")
+        {
+            let mut type_cursor = catch_type.walk();
+            exception_types.extend(
+                catch_type
+                    .named_children(&mut type_cursor)
+                    .map(|t| parsed.text_for_node(&t)),
+            );
+        }
+    }
+
+    let body = node.child_by_field_name("body")?;
+    let mut body_cursor = body.walk();
+    let is_empty = body
+        .named_children(&mut body_cursor)
+        .all(|c| matches!(c.kind(), "line_comment" | "block_comment"));
+
+    let body_text = parsed.text_for_node(&body);
+    let has_logging = body_text.contains("log.")
+        || body_text.contains("logger.")
+        || body_text.contains("LOG.")
+        || body_text.contains("LOGGER.")
+        || body_text.contains("printStackTrace")
+        || body_text.contains("System.err");
+
+    Some(JavaCatchClause {
+        exception_types,
+        variable,
+        is_empty,
+        has_logging,
+        rethrows: contains_kind(&body, "throw_statement"),
+        function_name: ctx.current_function.clone(),
+        location: parsed.location_for_node(node),
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+    })
+}
+
+/// Whether any descendant of `node` has the given kind.
+fn contains_kind(node: &tree_sitter::Node, kind: &str) -> bool {
+    if node.kind() == kind {
+        return true;
+    }
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .any(|child| contains_kind(&child, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::java::parse_java_file;
+    use crate::types::context::SourceFile;
+
+    fn parse_and_build_semantics(source: &str) -> JavaFileSemantics {
+        let sf = SourceFile {
+            path: "UserService.java".to_string(),
+            language: Language::Java,
+            content: source.to_string(),
+        };
+        let parsed = parse_java_file(FileId(1), &sf).expect("parsing should succeed");
+        JavaFileSemantics::from_parsed(&parsed)
+    }
+
+    #[test]
+    fn collects_package_name() {
+        let sem = parse_and_build_semantics("package com.example.service;\n\nclass A {}");
+        assert_eq!(sem.package_name, "com.example.service");
+    }
+
+    #[test]
+    fn collects_imports() {
+        let src = r#"
+import java.util.List;
+import java.util.concurrent.*;
+import static org.junit.Assert.assertEquals;
+import com.example.repo.UserRepository;
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.imports.len(), 4);
+
+        let list = &sem.imports[0];
+        assert_eq!(list.path, "java.util.List");
+        assert_eq!(list.simple_name(), Some("List"));
+        assert!(list.is_stdlib());
+        assert!(!list.is_wildcard);
+
+        let concurrent = &sem.imports[1];
+        assert_eq!(concurrent.path, "java.util.concurrent");
+        assert!(concurrent.is_wildcard);
+        assert_eq!(concurrent.simple_name(), None);
+
+        let assert_eq = &sem.imports[2];
+        assert!(assert_eq.is_static);
+        assert_eq!(assert_eq.path, "org.junit.Assert.assertEquals");
+        assert!(!assert_eq.is_stdlib());
+
+        assert_eq!(sem.imports[3].path, "com.example.repo.UserRepository");
+    }
+
+    #[test]
+    fn collects_classes() {
+        let src = r#"
+@Service
+public class UserService extends BaseService implements Auditable, Closeable {
+    static class Cache {}
+}
+
+interface Repository extends CrudRepository {}
+
+enum Status { ACTIVE, INACTIVE }
+
+record Point(int x, int y) {}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.classes.len(), 5);
+
+        let service = sem.find_class("UserService").unwrap();
+        assert_eq!(service.kind, JavaTypeKind::Class);
+        assert_eq!(service.superclass.as_deref(), Some("BaseService"));
+        assert_eq!(service.interfaces, vec!["Auditable", "Closeable"]);
+        assert_eq!(service.modifiers, vec!["public"]);
+        assert_eq!(service.annotations[0].name, "Service");
+        assert!(service.enclosing_class.is_none());
+
+        let cache = sem.find_class("Cache").unwrap();
+        assert_eq!(cache.enclosing_class.as_deref(), Some("UserService"));
+
+        let repo = sem.find_class("Repository").unwrap();
+        assert_eq!(repo.kind, JavaTypeKind::Interface);
+        assert_eq!(repo.interfaces, vec!["CrudRepository"]);
+
+        assert_eq!(sem.find_class("Status").unwrap().kind, JavaTypeKind::Enum);
+        assert_eq!(sem.find_class("Point").unwrap().kind, JavaTypeKind::Record);
+    }
+
+    #[test]
+    fn collects_methods() {
+        let src = r#"
+public class UserService {
+    private final UserRepository repo;
+
+    public UserService(UserRepository repo) {
+        this.repo = repo;
+    }
+
+    @Override
+    public List<User> findAll(String filter, int... ids) throws IOException, SQLException {
+        return repo.findAll();
+    }
+
+    static void helper() {}
+}
+
+interface Named {
+    String name();
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.methods.len(), 4);
+
+        let ctor = &sem.methods[0];
+        assert!(ctor.is_constructor);
+        assert_eq!(ctor.name, "UserService");
+        assert!(ctor.return_type.is_none());
+        assert_eq!(ctor.params.len(), 1);
+        assert_eq!(ctor.params[0].param_type, "UserRepository");
+
+        let find_all = &sem.methods[1];
+        assert_eq!(find_all.qualified_name(), "UserService.findAll");
+        assert_eq!(find_all.return_type.as_deref(), Some("List<User>"));
+        assert_eq!(find_all.annotations[0].name, "Override");
+        assert!(find_all.has_modifier("public"));
+        assert_eq!(find_all.throws, vec!["IOException", "SQLException"]);
+        assert_eq!(find_all.params.len(), 2);
+        assert_eq!(find_all.params[0].name, "filter");
+        assert!(find_all.params[1].is_varargs);
+        assert_eq!(find_all.params[1].name, "ids");
+        assert_eq!(find_all.params[1].param_type, "int");
+
+        let helper = &sem.methods[2];
+        assert!(helper.is_static());
+        assert!(helper.has_body);
+
+        let name = &sem.methods[3];
+        assert_eq!(name.class_name.as_deref(), Some("Named"));
+        assert!(!name.has_body);
+
+        assert_eq!(sem.methods_of("UserService").count(), 3);
+    }
+
+    #[test]
+    fn collects_calls_with_context() {
+        let src = r#"
+class Worker {
+    void run(List<Job> jobs) {
+        prepare();
+        for (Job job : jobs) {
+            this.process(job);
+        }
+        Collections.sort(jobs);
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.calls.len(), 3);
+
+        let prepare = &sem.calls[0];
+        assert_eq!(prepare.function_call.callee_expr, "prepare");
+        assert!(prepare.function_call.is_self_call);
+        assert!(!prepare.in_loop);
+        assert_eq!(prepare.function_call.caller_qualified_name, "Worker.run");
+
+        let process = &sem.calls[1];
+        assert_eq!(process.function_call.callee_expr, "this.process");
+        assert!(process.function_call.is_self_call);
+        assert!(process.in_loop);
+
+        let sort = &sem.calls[2];
+        assert!(sort.function_call.is_import_call);
+        assert_eq!(
+            sort.function_call.import_alias.as_deref(),
+            Some("Collections")
+        );
+    }
+
+    #[test]
+    fn collects_catch_clauses() {
+        let src = r#"
+class Loader {
+    void load() {
+        try {
+            read();
+        } catch (IOException | SQLException e) {
+            // ignored
+        } catch (Exception e) {
+            logger.error("failed", e);
+            throw new RuntimeException(e);
+        }
+    }
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.catch_clauses.len(), 2);
+        assert!(sem.methods[0].has_try_catch);
+
+        let multi = &sem.catch_clauses[0];
+        assert_eq!(multi.exception_types, vec!["IOException", "SQLException"]);
+        assert_eq!(multi.variable.as_deref(), Some("e"));
+        assert!(multi.is_empty);
+        assert_eq!(multi.function_name.as_deref(), Some("load"));
+
+        let generic = &sem.catch_clauses[1];
+        assert!(!generic.is_empty);
+        assert!(generic.has_logging);
+        assert!(generic.rethrows);
+    }
+}
@@ -0,0 +1,386 @@
+//! Per-function code metrics computed from the syntax tree.
+//!
+//! Each language supplies the node kinds that matter for a metric; the
+//! traversal itself is shared.
+
+use tree_sitter::Node;
+
+use crate::parse::ast::ParsedFile;
+
+/// Node kinds a language contributes to function metrics.
+pub(crate) struct MetricRules {
+    /// Node kinds that each add one decision point
+    pub decision_kinds: &'static [&'static str],
+    /// Node kinds that open a nested block (if/for/while/try)
+    pub nesting_kinds: &'static [&'static str],
+    /// Nested definitions that are measured as functions of their own
+    pub nested_definition_kinds: &'static [&'static str],
+}
+
+pub(crate) const PYTHON_RULES: MetricRules = MetricRules {
+    decision_kinds: &[
+        "if_statement",
+        "elif_clause",
+        "for_statement",
+        "while_statement",
+        "except_clause",
+        "conditional_expression",
+        "boolean_operator",
+        "case_clause",
+    ],
+    nesting_kinds: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "try_statement",
+        "with_statement",
+        "match_statement",
+    ],
+    nested_definition_kinds: &["function_definition", "class_definition"],
+};
+
+pub(crate) const GO_RULES: MetricRules = MetricRules {
+    decision_kinds: &[
+        "if_statement",
+        "for_statement",
+        "expression_case",
+        "type_case",
+        "communication_case",
+    ],
+    nesting_kinds: &[
+        "if_statement",
+        "for_statement",
+        "expression_switch_statement",
+        "type_switch_statement",
+        "select_statement",
+    ],
+    nested_definition_kinds: &[],
+};
+
+pub(crate) const RUST_RULES: MetricRules = MetricRules {
+    decision_kinds: &[
+        "if_expression",
+        "for_expression",
+        "while_expression",
+        "match_arm",
+        "try_expression",
+    ],
+    nesting_kinds: &[
+        "if_expression",
+        "for_expression",
+        "while_expression",
+        "loop_expression",
+        "match_expression",
+    ],
+    nested_definition_kinds: &["function_item"],
+};
+
+pub(crate) const TYPESCRIPT_RULES: MetricRules = MetricRules {
+    decision_kinds: &[
+        "if_statement",
+        "for_statement",
+        "for_in_statement",
+        "while_statement",
+        "do_statement",
+        "switch_case",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    nesting_kinds: &[
+        "if_statement",
+        "for_statement",
+        "for_in_statement",
+        "while_statement",
+        "do_statement",
+        "switch_statement",
+        "try_statement",
+    ],
+    nested_definition_kinds: &[
+        "function_declaration",
+        "method_definition",
+        "class_declaration",
+    ],
+};
+
+pub(crate) const JAVA_RULES: MetricRules = MetricRules {
+    decision_kinds: &[
+        "if_statement",
+        "for_statement",
+        "enhanced_for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_label",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    nesting_kinds: &[
+        "if_statement",
+        "for_statement",
+        "enhanced_for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_expression",
+        "try_statement",
+        "try_with_resources_statement",
+    ],
+    nested_definition_kinds: &["method_declaration", "class_declaration"],
+};
+
+/// Cyclomatic complexity of the function rooted at `node`.
+///
+/// Starts at 1 and adds one per decision point: branches, loops, non-default
+/// cases and their guards, catch handlers, ternaries and short-circuit
+/// `&&`/`||` operators.
+/// Nested named definitions are skipped since they are reported separately.
+pub(crate) fn cyclomatic_complexity(parsed: &ParsedFile, node: &Node, rules: &MetricRules) -> u32 {
+    let mut complexity = 1;
+    let mut stack = vec![*node];
+
+    while let Some(current) = stack.pop() {
+        if current.id() != node.id() && rules.nested_definition_kinds.contains(&current.kind()) {
+            continue;
+        }
+        if is_decision_point(parsed, &current, rules) {
+            complexity += 1;
+        }
+        let mut cursor = current.walk();
+        stack.extend(current.children(&mut cursor));
+    }
+
+    complexity
+}
+
+/// Deepest nesting of control blocks in the function rooted at `node`.
+///
+/// Each if/loop/switch/try block nested inside another adds a level; `else if`
+/// chains stay at the level of their first `if`. Straight-line code reports 1.
+pub(crate) fn max_nesting_depth(node: &Node, rules: &MetricRules) -> u32 {
+    let mut max_depth = 0;
+    let mut stack = vec![(*node, 0)];
+
+    while let Some((current, depth)) = stack.pop() {
+        if current.id() != node.id() && rules.nested_definition_kinds.contains(&current.kind()) {
+            continue;
+        }
+        let depth = if rules.nesting_kinds.contains(&current.kind()) && !is_else_if(&current) {
+            depth + 1
+        } else {
+            depth
+        };
+        max_depth = max_depth.max(depth);
+        let mut cursor = current.walk();
+        stack.extend(current.children(&mut cursor).map(|child| (child, depth)));
+    }
+
+    max_depth.max(1)
+}
+
+/// Whether `node` is the `if` of an `else if`, which continues its parent's chain.
+fn is_else_if(node: &Node) -> bool {
+    node.parent().is_some_and(|parent| {
+        parent.kind() == "else_clause"
+            || (parent.kind() == node.kind()
+                && parent
+                    .child_by_field_name("alternative")
+                    .is_some_and(|alt| alt.id() == node.id()))
+    })
+}
+
+fn is_decision_point(parsed: &ParsedFile, node: &Node, rules: &MetricRules) -> bool {
+    let kind = node.kind();
+    if kind == "binary_expression" {
+        return node
+            .child_by_field_name("operator")
+            .is_some_and(|op| matches!(op.kind(), "&&" | "||"));
+    }
+    // A guarded Python `case pattern if cond:` branches once more on its guard
+    if kind == "if_clause" {
+        return node.parent().is_some_and(|p| p.kind() == "case_clause");
+    }
+    if !rules.decision_kinds.contains(&kind) {
+        return false;
+    }
+    if matches!(kind, "switch_label" | "match_arm" | "case_clause") {
+        return !is_default_case(&parsed.text_for_node(node));
+    }
+    true
+}
+
+/// Whether a case label is the catch-all branch (`default`, `_ =>`, `case _:`).
+fn is_default_case(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("default")
+        || text.starts_with("_ ")
+        || text.starts_with("_=>")
+        || text.starts_with("case _:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ast::FileId;
+    use crate::parse::go::parse_go_file;
+    use crate::parse::java::parse_java_file;
+    use crate::parse::python::parse_python_file;
+    use crate::parse::rust::parse_rust_file;
+    use crate::parse::typescript::parse_typescript_file;
+    use crate::types::context::{Language, SourceFile};
+
+    fn parse(language: Language, path: &str, src: &str) -> ParsedFile {
+        let sf = SourceFile {
+            path: path.to_string(),
+            language,
+            content: src.to_string(),
+        };
+        match language {
+            Language::Python => parse_python_file(FileId(1), &sf),
+            Language::Go => parse_go_file(FileId(1), &sf),
+            Language::Rust => parse_rust_file(FileId(1), &sf),
+            Language::Typescript => parse_typescript_file(FileId(1), &sf),
+            Language::Java => parse_java_file(FileId(1), &sf),
+            _ => unreachable!(),
+        }
+        .unwrap()
+    }
+
+    /// Complexity of the first node of `kind` in the file.
+    fn complexity_of(parsed: &ParsedFile, kind: &str, rules: &MetricRules) -> u32 {
+        let root = parsed.tree.root_node();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if node.kind() == kind {
+                return cyclomatic_complexity(parsed, &node, rules);
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        panic!("no {kind} node");
+    }
+
+    fn depth_of(parsed: &ParsedFile, kind: &str, rules: &MetricRules) -> u32 {
+        let root = parsed.tree.root_node();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if node.kind() == kind {
+                return max_nesting_depth(&node, rules);
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        panic!("no {kind} node");
+    }
+
+    #[test]
+    fn nesting_depth_of_flat_and_nested_python() {
+        let flat = parse(Language::Python, "a.py", "def f(x):\n    return x\n");
+        assert_eq!(depth_of(&flat, "function_definition", &PYTHON_RULES), 1);
+
+        let nested = parse(
+            Language::Python,
+            "a.py",
+            "def f(x):\n    if x:\n        if x > 1:\n            if x > 2:\n                pass\n    elif x:\n        pass\n",
+        );
+        assert_eq!(depth_of(&nested, "function_definition", &PYTHON_RULES), 3);
+    }
+
+    #[test]
+    fn else_if_chain_does_not_add_nesting() {
+        let go = parse(
+            Language::Go,
+            "a.go",
+            "package a\nfunc f(x int) {\n\tif x > 2 {\n\t} else if x > 1 {\n\t} else if x > 0 {\n\t\tfor {\n\t\t}\n\t}\n}\n",
+        );
+        assert_eq!(depth_of(&go, "function_declaration", &GO_RULES), 2);
+
+        let ts = parse(
+            Language::Typescript,
+            "a.ts",
+            "function f(x) {\n  if (x) {} else if (!x) { try { g(); } catch (e) {} }\n}\n",
+        );
+        assert_eq!(depth_of(&ts, "function_declaration", &TYPESCRIPT_RULES), 2);
+    }
+
+    #[test]
+    fn python_counts_branches_loops_and_boolean_operators() {
+        let parsed = parse(
+            Language::Python,
+            "a.py",
+            "def f(x):\n    if x and x > 1:\n        pass\n    elif x:\n        pass\n    for i in x:\n        pass\n",
+        );
+        assert_eq!(
+            complexity_of(&parsed, "function_definition", &PYTHON_RULES),
+            5
+        );
+    }
+
+    #[test]
+    fn python_counts_match_arms_but_not_wildcard() {
+        let parsed = parse(
+            Language::Python,
+            "a.py",
+            "def f(cmd):\n    match cmd:\n        case \"start\":\n            pass\n        case \"stop\":\n            pass\n        case _:\n            pass\n",
+        );
+        assert_eq!(
+            complexity_of(&parsed, "function_definition", &PYTHON_RULES),
+            3
+        );
+        assert_eq!(depth_of(&parsed, "function_definition", &PYTHON_RULES), 1);
+    }
+
+    #[test]
+    fn python_counts_case_guards_and_tolerates_walrus() {
+        let parsed = parse(
+            Language::Python,
+            "a.py",
+            "def f(cmd, items):\n    if (n := len(items)) > 2:\n        pass\n    match cmd:\n        case [x, *_] if (m := len(x)) > n:\n            pass\n        case _:\n            pass\n    return [y for y in items if (z := y)]\n",
+        );
+        // if + case arm + guard; the comprehension filter is not a branch
+        assert_eq!(
+            complexity_of(&parsed, "function_definition", &PYTHON_RULES),
+            4
+        );
+    }
+
+    #[test]
+    fn go_skips_default_case() {
+        let parsed = parse(
+            Language::Go,
+            "a.go",
+            "package a\nfunc f(x int) {\n\tswitch x {\n\tcase 1:\n\tcase 2:\n\tdefault:\n\t}\n}\n",
+        );
+        assert_eq!(complexity_of(&parsed, "function_declaration", &GO_RULES), 3);
+    }
+
+    #[test]
+    fn rust_counts_match_arms_and_try() {
+        let parsed = parse(
+            Language::Rust,
+            "a.rs",
+            "fn f(x: Option<u8>) -> Result<(), E> {\n    match x {\n        Some(1) => {}\n        Some(_) => {}\n        _ => {}\n    }\n    g()?;\n    Ok(())\n}\n",
+        );
+        assert_eq!(complexity_of(&parsed, "function_item", &RUST_RULES), 4);
+    }
+
+    #[test]
+    fn typescript_counts_ternary_and_logical_operators() {
+        let parsed = parse(
+            Language::Typescript,
+            "a.ts",
+            "function f(a, b) {\n  const x = a || b ? 1 : 2;\n  try { g(); } catch (e) {}\n}\n",
+        );
+        assert_eq!(
+            complexity_of(&parsed, "function_declaration", &TYPESCRIPT_RULES),
+            4
+        );
+    }
+
+    #[test]
+    fn java_skips_nested_classes() {
+        let parsed = parse(
+            Language::Java,
+            "A.java",
+            "class A {\n  void f(int x) {\n    while (x > 0) { x--; }\n    Runnable r = new Runnable() { public void run() { if (true) {} } };\n  }\n}\n",
+        );
+        assert_eq!(complexity_of(&parsed, "method_declaration", &JAVA_RULES), 2);
+    }
+}
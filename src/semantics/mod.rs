@@ -1,25 +1,35 @@
 pub mod common;
 mod common_impl; // CommonSemantics trait implementations
+pub mod diff;
 pub mod go;
+pub mod java;
+mod metrics; // Per-function metrics (complexity, nesting depth)
+pub mod project;
 pub mod python;
+mod references; // Identifier references (unused-import detection)
 pub mod rust;
 pub mod typescript;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{EngineError, SemanticError};
 use crate::parse::ast::{FileId, ParsedFile};
-use crate::types::context::Language;
+use crate::types::context::{Language, SourceFile};
+use common::http::HttpDetectionConfig;
 use go::model::GoFileSemantics;
+use java::model::JavaFileSemantics;
 use python::model::PyFileSemantics;
 use rust::model::RustFileSemantics;
 use typescript::model::TsFileSemantics;
 
 // Re-export common types for convenience
 pub use common::{
-    CommonSemantics, async_ops::AsyncOperation, db::DbOperation, functions::FunctionDef,
-    http::HttpCall, imports::Import,
+    CommonSemantics, CommonSemanticsSnapshot, async_ops::AsyncOperation, db::DbOperation,
+    functions::FunctionDef, http::HttpCall, imports::Import,
 };
+pub use diff::{ApiDiff, api_diff};
+pub use project::{ProjectSemantics, aggregate};
 
 /// Language-agnostic wrapper for per-file semantics.
 ///
@@ -30,7 +40,7 @@ pub enum SourceSemantics {
     Go(GoFileSemantics),
     Rust(RustFileSemantics),
     Typescript(TsFileSemantics),
-    // Java(JavaFileSemantics),
+    Java(JavaFileSemantics),
 }
 
 impl SourceSemantics {
@@ -40,7 +50,8 @@ impl SourceSemantics {
             SourceSemantics::Python(_) => Language::Python,
             SourceSemantics::Go(_) => Language::Go,
             SourceSemantics::Rust(_) => Language::Rust,
-            SourceSemantics::Typescript(_) => Language::Typescript,
+            SourceSemantics::Typescript(ts) => ts.language,
+            SourceSemantics::Java(_) => Language::Java,
         }
     }
 
@@ -51,6 +62,7 @@ impl SourceSemantics {
             SourceSemantics::Go(sem) => sem.file_id,
             SourceSemantics::Rust(sem) => sem.file_id,
             SourceSemantics::Typescript(sem) => sem.file_id,
+            SourceSemantics::Java(sem) => sem.file_id,
         }
     }
 
@@ -61,6 +73,29 @@ impl SourceSemantics {
             SourceSemantics::Go(sem) => &sem.path,
             SourceSemantics::Rust(sem) => &sem.path,
             SourceSemantics::Typescript(sem) => &sem.path,
+            SourceSemantics::Java(sem) => &sem.path,
+        }
+    }
+
+    /// Capture a serializable snapshot of the common semantics of this file
+    pub fn snapshot(&self) -> CommonSemanticsSnapshot {
+        match self {
+            SourceSemantics::Python(sem) => CommonSemanticsSnapshot::from_semantics(sem),
+            SourceSemantics::Go(sem) => CommonSemanticsSnapshot::from_semantics(sem),
+            SourceSemantics::Rust(sem) => CommonSemanticsSnapshot::from_semantics(sem),
+            SourceSemantics::Typescript(sem) => CommonSemanticsSnapshot::from_semantics(sem),
+            SourceSemantics::Java(sem) => CommonSemanticsSnapshot::from_semantics(sem),
+        }
+    }
+
+    /// Box the inner semantics behind the language-agnostic trait
+    pub fn into_common(self) -> Box<dyn CommonSemantics> {
+        match self {
+            SourceSemantics::Python(sem) => Box::new(sem),
+            SourceSemantics::Go(sem) => Box::new(sem),
+            SourceSemantics::Rust(sem) => Box::new(sem),
+            SourceSemantics::Typescript(sem) => Box::new(sem),
+            SourceSemantics::Java(sem) => Box::new(sem),
         }
     }
 
@@ -95,6 +130,14 @@ impl SourceSemantics {
             _ => None,
         }
     }
+
+    /// Get the inner Java semantics if this is Java
+    pub fn as_java(&self) -> Option<&JavaFileSemantics> {
+        match self {
+            SourceSemantics::Java(sem) => Some(sem),
+            _ => None,
+        }
+    }
 }
 
 // NOTE: CommonSemantics trait implementation is on hold until language-specific
@@ -114,35 +157,139 @@ impl SourceSemantics {
 /// - Ok(None) if the language is not yet supported.
 /// - Err(_) if something went wrong building semantics.
 pub fn build_source_semantics(parsed: &ParsedFile) -> Result<Option<SourceSemantics>> {
+    language_semantics(parsed).map(Some)
+}
+
+/// Build the semantic model for `parsed`'s language; every language has one.
+fn language_semantics(parsed: &ParsedFile) -> Result<SourceSemantics> {
     match parsed.language {
         Language::Python => {
             let mut sem = PyFileSemantics::from_parsed(parsed);
             sem.analyze_frameworks(parsed)?;
-            Ok(Some(SourceSemantics::Python(sem)))
+            Ok(SourceSemantics::Python(sem))
         }
         Language::Go => {
             let sem = go::build_go_semantics(parsed)?;
-            Ok(Some(SourceSemantics::Go(sem)))
+            Ok(SourceSemantics::Go(sem))
         }
         Language::Rust => {
             let sem = rust::build_rust_semantics(parsed)?;
-            Ok(Some(SourceSemantics::Rust(sem)))
+            Ok(SourceSemantics::Rust(sem))
         }
-        Language::Typescript => {
+        // JavaScript shares the TypeScript model; its grammar is a subset
+        Language::Typescript | Language::Javascript => {
             let sem = typescript::build_typescript_semantics(parsed)?;
-            Ok(Some(SourceSemantics::Typescript(sem)))
+            Ok(SourceSemantics::Typescript(sem))
+        }
+        Language::Java => {
+            let sem = java::build_java_semantics(parsed)?;
+            Ok(SourceSemantics::Java(sem))
         }
-        // Later:
-        // Language::Java => { ... }
-        _ => Ok(None),
     }
 }
 
+/// Build semantics for a parsed file, also detecting HTTP calls made through
+/// the custom clients registered in `config` (e.g. internal wrapper libraries).
+pub fn build_source_semantics_with_http_config(
+    parsed: &ParsedFile,
+    config: &HttpDetectionConfig,
+) -> Result<Option<SourceSemantics>> {
+    let mut sem = build_source_semantics(parsed)?;
+    if config.is_empty() {
+        return Ok(sem);
+    }
+
+    match &mut sem {
+        Some(SourceSemantics::Python(py)) => {
+            py.http_calls = python::http::summarize_http_clients_with(parsed, config);
+        }
+        Some(SourceSemantics::Go(go_sem)) => {
+            go_sem.http_calls = go::http::summarize_http_clients_with(parsed, config);
+        }
+        Some(SourceSemantics::Rust(rs)) => rust::analyze_http_calls(parsed, rs, config),
+        Some(SourceSemantics::Typescript(ts)) => {
+            ts.http_calls = typescript::http::summarize_http_clients_with(parsed, config);
+        }
+        // Java HTTP client detection is not implemented yet
+        Some(SourceSemantics::Java(_)) | None => {}
+    }
+    Ok(sem)
+}
+
+/// Build semantics for a parsed file behind the `CommonSemantics` trait.
+///
+/// Generic tooling can work with any file without matching on its language.
+pub fn build_semantics(
+    parsed: &ParsedFile,
+) -> std::result::Result<Box<dyn CommonSemantics>, SemanticError> {
+    let sem = language_semantics(parsed).map_err(|err| SemanticError::File {
+        file_path: parsed.path.clone(),
+        reason: err.to_string(),
+    })?;
+
+    Ok(sem.into_common())
+}
+
+/// Like [`build_semantics`], but refuses trees tree-sitter could only partially parse.
+///
+/// Returns [`SemanticError::IncompleteTree`] at the first ERROR/MISSING node, for
+/// callers that would rather skip a file than analyze its recovered tree.
+pub fn build_semantics_strict(
+    parsed: &ParsedFile,
+) -> std::result::Result<Box<dyn CommonSemantics>, SemanticError> {
+    if let Some(location) = parsed.error_ranges().into_iter().next() {
+        return Err(SemanticError::IncompleteTree {
+            file_id: parsed.file_id,
+            file_path: parsed.path.clone(),
+            location,
+        });
+    }
+    build_semantics(parsed)
+}
+
+/// Parse and analyze many files in parallel, preserving input order.
+///
+/// Every file goes through [`crate::analyze_file`]. Sources are split into
+/// contiguous chunks, one per available core, and each chunk is analyzed on
+/// its own `std::thread::scope` thread; no rayon pool is involved. Tree-sitter
+/// parsers are not shared: every parse creates its own. Each file's `FileId`
+/// is its index in `sources`, so the output matches a serial run file for file.
+pub fn analyze_files_parallel(
+    sources: &[SourceFile],
+) -> Vec<std::result::Result<Box<dyn CommonSemantics>, EngineError>> {
+    let analyze = |offset: usize, chunk: &[SourceFile]| {
+        chunk
+            .iter()
+            .enumerate()
+            .map(|(idx, sf)| crate::analyze_file(FileId((offset + idx) as u64), sf))
+            .collect::<Vec<_>>()
+    };
+
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = sources.len().div_ceil(workers).max(1);
+    if workers == 1 || sources.len() <= chunk_size {
+        return analyze(0, sources);
+    }
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = sources
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(i, chunk)| scope.spawn(move || analyze(i * chunk_size, chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("analysis worker panicked"))
+            .collect()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::parse::ast::FileId;
     use crate::parse::go::parse_go_file;
+    use crate::parse::java::parse_java_file;
     use crate::parse::python::parse_python_file;
     use crate::parse::rust::parse_rust_file;
     use crate::parse::typescript::parse_typescript_file;
@@ -224,15 +371,22 @@ mod tests {
     }
 
     #[test]
-    fn build_source_semantics_unsupported_language_returns_none() {
-        // Create a parsed file but manually set language to something unsupported
-        let sf = make_source_file("test.py", Language::Python, "x = 1");
-        let mut parsed = parse_python_file(FileId(1), &sf).expect("parsing should succeed");
-        parsed.language = Language::Java; // Override to unsupported
+    fn build_source_semantics_javascript_uses_typescript_model() {
+        use crate::parse::javascript::parse_javascript_file;
 
-        let result = build_source_semantics(&parsed);
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        let sf = make_source_file(
+            "app.js",
+            Language::Javascript,
+            "function main(...args) {}\n",
+        );
+        let parsed = parse_javascript_file(FileId(1), &sf).expect("parsing should succeed");
+
+        let result = build_source_semantics(&parsed).unwrap().unwrap();
+        assert!(matches!(result, SourceSemantics::Typescript(_)));
+        assert_eq!(result.language(), Language::Javascript);
+        let functions = result.into_common().functions();
+        assert_eq!(functions[0].name, "main");
+        assert!(functions[0].params[0].is_variadic);
     }
 
     #[test]
@@ -276,14 +430,15 @@ mod tests {
     }
 
     #[test]
-    fn build_source_semantics_java_returns_none() {
-        let sf = make_source_file("test.py", Language::Python, "x = 1");
-        let mut parsed = parse_python_file(FileId(1), &sf).expect("parsing should succeed");
-        parsed.language = Language::Java;
+    fn build_source_semantics_java_returns_java_variant() {
+        let sf = make_source_file("Test.java", Language::Java, "public class Test {}");
+        let parsed = parse_java_file(FileId(1), &sf).expect("parsing should succeed");
 
-        let result = build_source_semantics(&parsed);
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        let result = build_source_semantics(&parsed).unwrap().unwrap();
+        assert!(matches!(result, SourceSemantics::Java(_)));
+        assert_eq!(result.language(), Language::Java);
+        assert!(result.as_java().is_some());
+        assert!(result.as_go().is_none());
     }
 
     #[test]
@@ -326,14 +481,34 @@ mod tests {
     }
 
     #[test]
-    fn build_source_semantics_javascript_returns_none() {
-        let sf = make_source_file("test.py", Language::Python, "x = 1");
-        let mut parsed = parse_python_file(FileId(1), &sf).expect("parsing should succeed");
-        parsed.language = Language::Javascript;
+    fn custom_http_client_is_detected_with_config() {
+        use common::http::{CustomHttpClient, HttpClientLibrary};
 
-        let result = build_source_semantics(&parsed);
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        let sf = make_source_file(
+            "api.ts",
+            Language::Typescript,
+            "async function load() {\n  return InternalHttp.fetch('/users');\n}\n",
+        );
+        let parsed = parse_typescript_file(FileId(1), &sf).expect("parsing should succeed");
+
+        let plain = build_source_semantics(&parsed).unwrap().unwrap();
+        assert!(plain.into_common().http_calls().is_empty());
+
+        let config = HttpDetectionConfig::new().with_client(
+            CustomHttpClient::new("internal-http")
+                .object("InternalHttp")
+                .method("fetch"),
+        );
+        let sem = build_source_semantics_with_http_config(&parsed, &config)
+            .unwrap()
+            .unwrap();
+        let calls = sem.into_common().http_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(
+            calls[0].library,
+            HttpClientLibrary::Other("internal-http".to_string())
+        );
+        assert_eq!(calls[0].url.as_deref(), Some("/users"));
     }
 
     #[test]
@@ -368,6 +543,18 @@ app = FastAPI()
         }
     }
 
+    #[test]
+    fn source_semantics_snapshot_matches_language() {
+        let sf = make_source_file("test.go", Language::Go, "package main\n\nfunc main() {}\n");
+        let parsed = parse_go_file(FileId(4), &sf).expect("parsing should succeed");
+        let source_sem = build_source_semantics(&parsed).unwrap().unwrap();
+
+        let snapshot = source_sem.snapshot();
+        assert_eq!(snapshot.language, Language::Go);
+        assert_eq!(snapshot.file_id, FileId(4));
+        assert!(snapshot.functions.iter().any(|f| f.name == "main"));
+    }
+
     // =============================================================================
     // Rust Function Calls Tests
     // =============================================================================
@@ -569,4 +756,108 @@ function helper(): void {}
         let my_func = functions.iter().find(|f| f.name == "myFunc").unwrap();
         assert_eq!(my_func.calls.len(), 2);
     }
+
+    // ==================== build_semantics Tests ====================
+
+    #[test]
+    fn build_semantics_python_and_go_report_language() {
+        let py = make_source_file(
+            "app.py",
+            Language::Python,
+            "import requests\n\ndef fetch():\n    requests.get('https://example.com')\n",
+        );
+        let parsed = parse_python_file(FileId(1), &py).expect("parsing should succeed");
+        let sem = build_semantics(&parsed).expect("python semantics should build");
+        assert_eq!(sem.language(), Language::Python);
+        assert_eq!(sem.file_path(), "app.py");
+        assert_eq!(sem.http_calls().len(), 1);
+
+        let go = make_source_file("main.go", Language::Go, "package main\n\nfunc main() {}\n");
+        let parsed = parse_go_file(FileId(2), &go).expect("parsing should succeed");
+        let sem = build_semantics(&parsed).expect("go semantics should build");
+        assert_eq!(sem.language(), Language::Go);
+        assert_eq!(sem.file_id(), FileId(2));
+    }
+
+    #[test]
+    fn build_semantics_strict_rejects_incomplete_tree() {
+        let broken = make_source_file("broken.py", Language::Python, "x = 1\ndef f(:\n    pass\n");
+        let parsed = parse_python_file(FileId(7), &broken).expect("parsing should succeed");
+        assert!(build_semantics(&parsed).is_ok());
+
+        let err = build_semantics_strict(&parsed).err().expect("should fail");
+        match err {
+            SemanticError::IncompleteTree {
+                file_id,
+                file_path,
+                location,
+            } => {
+                assert_eq!(file_id, FileId(7));
+                assert_eq!(file_path, "broken.py");
+                assert_eq!(location.range.start_line, 1);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let clean = make_source_file("ok.py", Language::Python, "x = 1\n");
+        let parsed = parse_python_file(FileId(8), &clean).expect("parsing should succeed");
+        assert!(build_semantics_strict(&parsed).is_ok());
+    }
+
+    #[test]
+    fn analyze_files_parallel_matches_serial_run() {
+        let mut sources = Vec::new();
+        for i in 0..24 {
+            sources.push(make_source_file(
+                &format!("mod_{i}.py"),
+                Language::Python,
+                &format!("import requests\n\ndef fetch_{i}():\n    return requests.get('http://x', timeout=1)\n"),
+            ));
+            sources.push(make_source_file(
+                &format!("svc_{i}.go"),
+                Language::Go,
+                &format!("package svc\n\nfunc Handle{i}() {{}}\n"),
+            ));
+        }
+        sources.push(make_source_file(
+            "notes.js",
+            Language::Javascript,
+            "console.log(1);\n",
+        ));
+        sources.push(make_source_file(
+            "broken.py",
+            Language::Python,
+            "def broken(:\n    pass\n",
+        ));
+
+        let parallel = analyze_files_parallel(&sources);
+        assert_eq!(parallel.len(), sources.len());
+
+        for (idx, (sf, result)) in sources.iter().zip(&parallel).enumerate() {
+            let serial = crate::analyze_file(FileId(idx as u64), sf);
+            match (result, serial) {
+                (Ok(par), Ok(ser)) => {
+                    assert_eq!(par.file_id(), ser.file_id());
+                    assert_eq!(par.file_path(), ser.file_path());
+                    assert_eq!(
+                        serde_json::to_value(par.functions()).unwrap(),
+                        serde_json::to_value(ser.functions()).unwrap()
+                    );
+                    assert_eq!(par.http_calls().len(), ser.http_calls().len());
+                }
+                (Err(_), Err(_)) => {}
+                _ => panic!("parallel and serial results differ for {}", sf.path),
+            }
+        }
+        let js = parallel[parallel.len() - 2]
+            .as_ref()
+            .expect("javascript is analyzed");
+        assert_eq!(js.language(), Language::Javascript);
+        assert!(matches!(
+            parallel.last(),
+            Some(Err(EngineError::Parse(
+                crate::error::ParseError::SyntaxErrors { .. }
+            )))
+        ));
+    }
 }
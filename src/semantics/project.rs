@@ -0,0 +1,186 @@
+//! Project-wide rollup of per-file semantics.
+//!
+//! Concatenates the `CommonSemantics` outputs of many files into one view.
+//! Every item keeps the `FileId` of the file it came from (in its location),
+//! and cross-file indexes answer questions like "which files define `foo`".
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::CommonSemantics;
+use super::common::async_ops::AsyncOperation;
+use super::common::db::DbOperation;
+use super::common::functions::FunctionDef;
+use super::common::http::HttpCall;
+use super::common::imports::Import;
+use crate::parse::ast::FileId;
+use crate::types::context::Language;
+
+/// A file that contributed to a [`ProjectSemantics`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFile {
+    /// File identifier
+    pub file_id: FileId,
+    /// File path
+    pub path: String,
+    /// Language of the file
+    pub language: Language,
+}
+
+/// Semantics of a whole project, aggregated from its files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectSemantics {
+    /// Files in the order they were aggregated
+    pub files: Vec<ProjectFile>,
+    /// Function and method definitions across all files
+    pub functions: Vec<FunctionDef>,
+    /// Imports across all files
+    pub imports: Vec<Import>,
+    /// HTTP client calls across all files
+    pub http_calls: Vec<HttpCall>,
+    /// Database operations across all files
+    pub db_operations: Vec<DbOperation>,
+    /// Async/concurrent operations across all files
+    pub async_operations: Vec<AsyncOperation>,
+    /// Function name → files defining a function or method with that name
+    pub function_index: BTreeMap<String, Vec<FileId>>,
+    /// Imported module path → files importing it
+    pub import_index: BTreeMap<String, Vec<FileId>>,
+}
+
+impl ProjectSemantics {
+    /// Files that define a function or method named `name`
+    pub fn files_defining(&self, name: &str) -> &[FileId] {
+        self.function_index
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Files that import the module at `module_path`
+    pub fn files_importing(&self, module_path: &str) -> &[FileId] {
+        self.import_index
+            .get(module_path)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// All definitions of functions or methods named `name`
+    pub fn functions_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a FunctionDef> {
+        self.functions.iter().filter(move |f| f.name == name)
+    }
+
+    /// Look up a file by ID
+    pub fn file(&self, file_id: FileId) -> Option<&ProjectFile> {
+        self.files.iter().find(|f| f.file_id == file_id)
+    }
+}
+
+/// Record `file_id` under `key`, once per file.
+fn index_file(index: &mut BTreeMap<String, Vec<FileId>>, key: &str, file_id: FileId) {
+    let files = index.entry(key.to_string()).or_default();
+    if !files.contains(&file_id) {
+        files.push(file_id);
+    }
+}
+
+/// Aggregate per-file semantics into a project-wide view.
+///
+/// Items are concatenated in file order, each file's items in source order.
+pub fn aggregate(files: &[Box<dyn CommonSemantics>]) -> ProjectSemantics {
+    let mut project = ProjectSemantics::default();
+
+    for sem in files {
+        let file_id = sem.file_id();
+        project.files.push(ProjectFile {
+            file_id,
+            path: sem.file_path().to_string(),
+            language: sem.language(),
+        });
+
+        let functions = sem.functions();
+        for function in &functions {
+            index_file(&mut project.function_index, &function.name, file_id);
+        }
+        let imports = sem.imports();
+        for import in &imports {
+            index_file(&mut project.import_index, &import.module_path, file_id);
+        }
+
+        project.functions.extend(functions);
+        project.imports.extend(imports);
+        project.http_calls.extend(sem.http_calls());
+        project.db_operations.extend(sem.db_operations());
+        project.async_operations.extend(sem.async_operations());
+    }
+
+    project
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::python::parse_python_file;
+    use crate::semantics::build_source_semantics;
+    use crate::types::context::SourceFile;
+
+    fn python(file_id: u64, path: &str, source: &str) -> Box<dyn CommonSemantics> {
+        let sf = SourceFile {
+            path: path.to_string(),
+            language: Language::Python,
+            content: source.to_string(),
+        };
+        let parsed = parse_python_file(FileId(file_id), &sf).expect("parsing should succeed");
+        build_source_semantics(&parsed)
+            .expect("semantics should build")
+            .expect("python is supported")
+            .into_common()
+    }
+
+    #[test]
+    fn aggregates_two_files_with_attribution() {
+        let files = vec![
+            python(
+                1,
+                "app/users.py",
+                r#"
+import requests
+
+def fetch_user(user_id):
+    return requests.get("https://api.example.com/users", timeout=5)
+"#,
+            ),
+            python(
+                2,
+                "app/views.py",
+                r#"
+from app.users import fetch_user
+
+def show(user_id):
+    return fetch_user(user_id)
+"#,
+            ),
+        ];
+
+        let project = aggregate(&files);
+        assert_eq!(project.files.len(), 2);
+        assert_eq!(project.functions.len(), 2);
+        assert_eq!(project.http_calls.len(), 1);
+        assert_eq!(project.http_calls[0].location.file_id, FileId(1));
+
+        assert_eq!(project.files_defining("fetch_user"), &[FileId(1)]);
+        assert_eq!(project.files_defining("show"), &[FileId(2)]);
+        assert!(project.files_defining("missing").is_empty());
+        assert_eq!(project.files_importing("requests"), &[FileId(1)]);
+
+        let definition = project
+            .functions_named("fetch_user")
+            .next()
+            .expect("fetch_user is defined");
+        let file = project
+            .file(definition.location.file_id)
+            .expect("file is known");
+        assert_eq!(file.path, "app/users.py");
+    }
+}
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use crate::parse::ast::ParsedFile;
 use crate::semantics::python::model::{AsyncOperation, AsyncOperationType};
 
+use super::iteration::IterationContext;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PyAsyncSummary {
     pub operations: Vec<AsyncOperation>,
@@ -19,7 +21,13 @@ pub fn summarize_async_operations(parsed: &ParsedFile) -> PyAsyncSummary {
     let mut summary = PyAsyncSummary::default();
 
     let root = parsed.tree.root_node();
-    walk_for_async(root, parsed, &mut summary, None);
+    walk_for_async(
+        root,
+        parsed,
+        &mut summary,
+        None,
+        IterationContext::default(),
+    );
 
     summary.without_error_handling = summary
         .operations
@@ -43,15 +51,18 @@ fn walk_for_async(
     parsed: &ParsedFile,
     summary: &mut PyAsyncSummary,
     current_function: Option<&str>,
+    iteration: IterationContext,
 ) {
     let func_name = node
         .child_by_field_name("name")
         .map(|n| parsed.text_for_node(&n));
     let effective_function = func_name.as_deref().or(current_function);
 
+    let iteration = iteration.enter(&node);
+
     match node.kind() {
         "call" => {
-            if let Some(op) = detect_asyncio_call(parsed, &node, effective_function) {
+            if let Some(op) = detect_asyncio_call(parsed, &node, effective_function, iteration) {
                 summary.operations.push(op.clone());
                 match op.operation_type {
                     AsyncOperationType::TaskSpawn => summary.task_spawns.push(op),
@@ -63,7 +74,7 @@ fn walk_for_async(
             }
         }
         "await_expression" => {
-            if let Some(op) = detect_await(parsed, &node, effective_function) {
+            if let Some(op) = detect_await(parsed, &node, effective_function, iteration) {
                 summary.operations.push(op.clone());
                 summary.awaits.push(op);
             }
@@ -74,7 +85,7 @@ fn walk_for_async(
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
-            walk_for_async(child, parsed, summary, effective_function);
+            walk_for_async(child, parsed, summary, effective_function, iteration);
         }
     }
 }
@@ -83,6 +94,7 @@ fn detect_asyncio_call(
     parsed: &ParsedFile,
     node: &tree_sitter::Node,
     current_function: Option<&str>,
+    iteration: IterationContext,
 ) -> Option<AsyncOperation> {
     let func_node = node.child_by_field_name("function")?;
     let callee = parsed.text_for_node(&func_node);
@@ -109,6 +121,8 @@ fn detect_asyncio_call(
     let text = parsed.text_for_node(node);
     let has_error_handling = has_try_around(node);
     let (has_timeout, timeout_value) = extract_timeout_from_call(parsed, node, &callee);
+    let is_bounded =
+        operation_type == AsyncOperationType::TaskSpawn && is_semaphore_guarded(parsed, node);
 
     Some(AsyncOperation {
         operation_type,
@@ -116,8 +130,14 @@ fn detect_asyncio_call(
         has_timeout,
         timeout_value,
         has_cancellation: false,
-        is_bounded: false,
+        is_bounded,
         bound_limit: None,
+        in_loop: iteration.in_loop,
+        in_comprehension: iteration.in_comprehension,
+        has_cleanup: has_cleanup_around(node),
+        is_discarded: node
+            .parent()
+            .is_some_and(|p| p.kind() == "expression_statement"),
         operation_text: text,
         enclosing_function: current_function.map(|s| s.to_string()),
         start_byte: node.start_byte(),
@@ -130,6 +150,7 @@ fn detect_await(
     parsed: &ParsedFile,
     node: &tree_sitter::Node,
     current_function: Option<&str>,
+    iteration: IterationContext,
 ) -> Option<AsyncOperation> {
     let text = parsed.text_for_node(node);
     let has_error_handling = has_try_around(node);
@@ -142,6 +163,10 @@ fn detect_await(
         has_cancellation: false,
         is_bounded: false,
         bound_limit: None,
+        in_loop: iteration.in_loop,
+        in_comprehension: iteration.in_comprehension,
+        has_cleanup: has_cleanup_around(node),
+        is_discarded: false,
         operation_text: text,
         enclosing_function: current_function.map(|s| s.to_string()),
         start_byte: node.start_byte(),
@@ -161,6 +186,85 @@ fn has_try_around(node: &tree_sitter::Node) -> bool {
     false
 }
 
+/// Check whether a spawn is guarded by a semaphore within its function:
+/// an enclosing `async with sem:`, a preceding `await sem.acquire()` in an
+/// enclosing block, or a semaphore passed to the spawned coroutine.
+fn is_semaphore_guarded(parsed: &ParsedFile, node: &tree_sitter::Node) -> bool {
+    let args_mention_semaphore = node
+        .child_by_field_name("arguments")
+        .is_some_and(|args| mentions_semaphore(&parsed.text_for_node(&args)));
+    if args_mention_semaphore {
+        return true;
+    }
+
+    let mut current = *node;
+    while let Some(parent) = current.parent() {
+        match parent.kind() {
+            "with_statement" => {
+                let mut cursor = parent.walk();
+                let guarded = parent
+                    .children(&mut cursor)
+                    .filter(|c| c.kind() == "with_clause")
+                    .any(|c| mentions_semaphore(&parsed.text_for_node(&c)));
+                if guarded {
+                    return true;
+                }
+            }
+            "block" | "module" => {
+                let mut sibling = current.prev_named_sibling();
+                while let Some(stmt) = sibling {
+                    let text = parsed.text_for_node(&stmt);
+                    if text.contains(".acquire(") && mentions_semaphore(&text) {
+                        return true;
+                    }
+                    sibling = stmt.prev_named_sibling();
+                }
+            }
+            "function_definition" | "lambda" => return false,
+            _ => {}
+        }
+        current = parent;
+    }
+    false
+}
+
+/// Whether the text references a semaphore or limiter by name.
+fn mentions_semaphore(text: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(|token| token.to_lowercase())
+        .any(|token| {
+            token.contains("semaphore")
+                || token.contains("limiter")
+                || token == "sem"
+                || token.ends_with("_sem")
+        })
+}
+
+/// Check whether the node runs inside an `async with` block or a `try`
+/// statement with a `finally` clause within its function.
+fn has_cleanup_around(node: &tree_sitter::Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "with_statement" if parent.child(0).is_some_and(|c| c.kind() == "async") => {
+                return true;
+            }
+            "try_statement" if has_finally_clause(&parent) => return true,
+            "function_definition" | "lambda" => return false,
+            _ => {}
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+fn has_finally_clause(try_node: &tree_sitter::Node) -> bool {
+    let mut cursor = try_node.walk();
+    try_node
+        .children(&mut cursor)
+        .any(|c| c.kind() == "finally_clause")
+}
+
 fn extract_timeout_from_call(
     parsed: &ParsedFile,
     node: &tree_sitter::Node,
@@ -396,4 +500,111 @@ class MyClass:
             Some("async_method".to_string())
         );
     }
+
+    #[test]
+    fn create_task_in_loop_is_unbounded() {
+        let src = r#"
+import asyncio
+
+async def main(urls):
+    for url in urls:
+        asyncio.create_task(fetch(url))
+"#;
+        let summary = parse_and_summarize(src);
+        assert_eq!(summary.task_spawns.len(), 1);
+        assert!(summary.task_spawns[0].in_loop);
+        assert!(!summary.task_spawns[0].is_bounded);
+    }
+
+    #[test]
+    fn create_task_under_semaphore_is_bounded() {
+        let src = r#"
+import asyncio
+
+async def main(urls):
+    sem = asyncio.Semaphore(10)
+    for url in urls:
+        async with sem:
+            asyncio.create_task(fetch(url))
+"#;
+        let summary = parse_and_summarize(src);
+        assert_eq!(summary.task_spawns.len(), 1);
+        assert!(summary.task_spawns[0].in_loop);
+        assert!(summary.task_spawns[0].is_bounded);
+    }
+
+    #[test]
+    fn create_task_after_semaphore_acquire_is_bounded() {
+        let src = r#"
+import asyncio
+
+async def main(urls, semaphore):
+    for url in urls:
+        await semaphore.acquire()
+        asyncio.create_task(fetch(url))
+"#;
+        let summary = parse_and_summarize(src);
+        assert!(summary.task_spawns[0].is_bounded);
+    }
+
+    #[test]
+    fn create_task_in_comprehension_is_in_comprehension() {
+        let src = r#"
+import asyncio
+
+async def main(urls):
+    tasks = [asyncio.create_task(fetch(u)) for u in urls]
+"#;
+        let summary = parse_and_summarize(src);
+        assert!(summary.task_spawns[0].in_comprehension);
+        assert!(!summary.task_spawns[0].in_loop);
+    }
+
+    #[test]
+    fn awaited_call_inside_async_with_has_cleanup() {
+        let src = r#"
+import asyncio
+
+async def main(lock):
+    async with lock:
+        await asyncio.sleep(1)
+"#;
+        let summary = parse_and_summarize(src);
+        assert_eq!(summary.sleeps.len(), 1);
+        assert!(summary.sleeps[0].has_cleanup);
+    }
+
+    #[test]
+    fn awaited_call_inside_try_finally_has_cleanup() {
+        let src = r#"
+import asyncio
+
+async def main(task):
+    try:
+        await asyncio.wait_for(task, timeout=5)
+    finally:
+        task.cancel()
+"#;
+        let summary = parse_and_summarize(src);
+        assert_eq!(summary.gathers.len(), 1);
+        assert!(summary.gathers[0].has_cleanup);
+    }
+
+    #[test]
+    fn bare_awaited_call_has_no_cleanup() {
+        let src = r#"
+import asyncio
+
+async def main():
+    try:
+        await asyncio.sleep(1)
+    except Exception:
+        pass
+    with open("f") as f:
+        await asyncio.sleep(2)
+"#;
+        let summary = parse_and_summarize(src);
+        assert_eq!(summary.sleeps.len(), 2);
+        assert!(summary.sleeps.iter().all(|op| !op.has_cleanup));
+    }
 }
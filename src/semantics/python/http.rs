@@ -1,7 +1,11 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::parse::ast::AstLocation;
 use serde::{Deserialize, Serialize};
 
 use crate::parse::ast::ParsedFile;
+use crate::semantics::common::http::HttpDetectionConfig;
+use crate::semantics::python::iteration::IterationContext;
 use tree_sitter::Node;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,7 +48,8 @@ pub struct HttpCallSite {
     /// Exact text of the call expression.
     pub call_text: String,
 
-    /// Whether this call has an explicit `timeout=` kwarg.
+    /// Whether this call has a timeout, either an explicit `timeout=` kwarg
+    /// or a client that was constructed with one.
     pub has_timeout: bool,
 
     /// Where in the file this call is (line/col).
@@ -60,6 +65,14 @@ pub struct HttpCallSite {
     /// This makes blocking calls safe in async context.
     pub is_thread_offloaded: bool,
 
+    /// Whether the call is inside a `for`/`while` loop.
+    #[serde(default)]
+    pub in_loop: bool,
+
+    /// Whether the call is inside a comprehension or generator expression.
+    #[serde(default)]
+    pub in_comprehension: bool,
+
     /// Byte range of the call in the original source file.
     /// These are absolute byte offsets into `ParsedFile.source`.
     pub start_byte: usize,
@@ -70,12 +83,21 @@ pub struct HttpCallSite {
 ///
 /// This is best-effort and conservative:
 /// - detects module-level `requests.*` and `httpx.*` calls
-/// - uses a simple textual "timeout=" check
+/// - uses a simple textual "timeout=" check, on the call or on the client constructor
 /// - detects retry patterns (tenacity, backoff, stamina decorators, session retry config)
 pub fn summarize_http_clients(file: &ParsedFile) -> Vec<HttpCallSite> {
+    summarize_http_clients_with(file, &HttpDetectionConfig::default())
+}
+
+/// Like [`summarize_http_clients`], also reporting calls through the custom
+/// clients registered in `config`.
+pub fn summarize_http_clients_with(
+    file: &ParsedFile,
+    config: &HttpDetectionConfig,
+) -> Vec<HttpCallSite> {
     let root = file.tree.root_node();
     let mut calls = Vec::new();
-    collect_http_calls(file, root, &mut calls);
+    collect_http_calls(file, root, &mut calls, config);
     calls
 }
 
@@ -147,22 +169,27 @@ fn detect_session_retry_config(file: &ParsedFile) -> bool {
     false
 }
 
-fn collect_http_calls(file: &ParsedFile, root: Node, out: &mut Vec<HttpCallSite>) {
-    use std::collections::HashMap;
-
-    // Pre-check if file has session-level retry config
-    let has_session_retry = detect_session_retry_config(file);
+/// State threaded through the HTTP call walk.
+struct HttpWalkState<'a> {
+    config: &'a HttpDetectionConfig,
+    /// The file configures retries on a session (HTTPAdapter/Retry, httpx transport)
+    has_session_retry: bool,
+    enclosing_fn_name: Option<String>,
+    enclosing_fn_is_async: bool,
+    enclosing_fn_retry: Option<RetrySource>,
+    /// Variables bound to an HTTP client, e.g. `session = requests.Session()`
+    http_client_vars: HashMap<String, HttpClientKind>,
+    /// Client variables constructed with `timeout=...`
+    timeout_client_vars: HashSet<String>,
+}
 
-    fn walk(
-        file: &ParsedFile,
-        node: Node,
-        out: &mut Vec<HttpCallSite>,
-        enclosing_fn_name: &mut Option<String>,
-        enclosing_fn_is_async: &mut bool,
-        enclosing_fn_retry: &mut Option<RetrySource>,
-        has_session_retry: bool,
-        http_client_vars: &mut HashMap<String, HttpClientKind>,
-    ) {
+fn collect_http_calls(
+    file: &ParsedFile,
+    root: Node,
+    out: &mut Vec<HttpCallSite>,
+    config: &HttpDetectionConfig,
+) {
+    fn walk(file: &ParsedFile, node: Node, out: &mut Vec<HttpCallSite>, state: &mut HttpWalkState) {
         // Detect decorated function definitions (for retry decorator detection)
         // In tree-sitter Python, decorated functions are:
         //   decorated_definition
@@ -170,18 +197,23 @@ fn collect_http_calls(file: &ParsedFile, root: Node, out: &mut Vec<HttpCallSite>
         //     function_definition
         if node.kind() == "decorated_definition" {
             // Check for retry decorators on this decorated definition
-            *enclosing_fn_retry = detect_retry_decorator(file, node);
+            state.enclosing_fn_retry = detect_retry_decorator(file, node);
         }
 
+        // Clients bound inside a function are only visible in that function;
+        // module-level clients stay visible everywhere.
+        let outer_timeout_clients =
+            (node.kind() == "function_definition").then(|| state.timeout_client_vars.clone());
+
         // Detect (async) function boundaries.
         if node.kind() == "function_definition" {
             if let Some(name_node) = node.child_by_field_name("name") {
-                *enclosing_fn_name = Some(file.text_for_node(&name_node));
+                state.enclosing_fn_name = Some(file.text_for_node(&name_node));
             }
 
             // Very simple heuristic: does the function text start with "async def"?
             let fn_text = file.text_for_node(&node);
-            *enclosing_fn_is_async = fn_text.trim_start().starts_with("async def");
+            state.enclosing_fn_is_async = fn_text.trim_start().starts_with("async def");
 
             // For non-decorated functions, retry is None (already set by decorated_definition if present)
         }
@@ -190,26 +222,27 @@ fn collect_http_calls(file: &ParsedFile, root: Node, out: &mut Vec<HttpCallSite>
         // - `client = httpx.Client()` / `client = httpx.AsyncClient()`
         // - `session = requests.Session()`
         // - `async with httpx.AsyncClient() as client:` / `with requests.Session() as s:`
-        if let Some((var_name, client_kind)) = extract_http_client_assignment(file, node) {
-            http_client_vars.insert(var_name, client_kind);
+        // Clients constructed with `timeout=...` make every call on them bounded.
+        if let Some((var_name, client_kind, client_timeout)) =
+            extract_http_client_assignment(file, node)
+        {
+            if client_timeout {
+                state.timeout_client_vars.insert(var_name.clone());
+            } else {
+                state.timeout_client_vars.remove(&var_name);
+            }
+            state.http_client_vars.insert(var_name, client_kind);
         }
 
         if node.kind() == "call" {
             // Check if this call is wrapped in asyncio.to_thread/run_in_executor
             let is_thread_offloaded = check_thread_offload(file, node);
 
-            if let Some(mut site) = extract_http_call(
-                file,
-                node,
-                enclosing_fn_name.clone(),
-                *enclosing_fn_is_async,
-                is_thread_offloaded,
-                http_client_vars,
-            ) {
+            if let Some(mut site) = extract_http_call(file, node, is_thread_offloaded, state) {
                 // Set retry source based on context
-                if enclosing_fn_retry.is_some() {
-                    site.retry_source = enclosing_fn_retry.clone();
-                } else if has_session_retry {
+                if state.enclosing_fn_retry.is_some() {
+                    site.retry_source = state.enclosing_fn_retry.clone();
+                } else if state.has_session_retry {
                     site.retry_source = Some(RetrySource::SessionConfiguredRetry);
                 }
                 out.push(site);
@@ -218,45 +251,34 @@ fn collect_http_calls(file: &ParsedFile, root: Node, out: &mut Vec<HttpCallSite>
 
         let mut child = node.child(0);
         while let Some(c) = child {
-            walk(
-                file,
-                c,
-                out,
-                enclosing_fn_name,
-                enclosing_fn_is_async,
-                enclosing_fn_retry,
-                has_session_retry,
-                http_client_vars,
-            );
+            walk(file, c, out, state);
             child = c.next_sibling();
         }
 
         // Leaving the function scope.
-        if node.kind() == "function_definition" {
-            *enclosing_fn_name = None;
-            *enclosing_fn_is_async = false;
+        if let Some(outer) = outer_timeout_clients {
+            state.enclosing_fn_name = None;
+            state.enclosing_fn_is_async = false;
+            state.timeout_client_vars = outer;
         }
 
         // Clear retry when leaving decorated_definition scope
         if node.kind() == "decorated_definition" {
-            *enclosing_fn_retry = None;
+            state.enclosing_fn_retry = None;
         }
     }
 
-    let mut enclosing_fn_name: Option<String> = None;
-    let mut enclosing_fn_is_async = false;
-    let mut enclosing_fn_retry: Option<RetrySource> = None;
-    let mut http_client_vars: HashMap<String, HttpClientKind> = HashMap::new();
-    walk(
-        file,
-        root,
-        out,
-        &mut enclosing_fn_name,
-        &mut enclosing_fn_is_async,
-        &mut enclosing_fn_retry,
-        has_session_retry,
-        &mut http_client_vars,
-    );
+    let mut state = HttpWalkState {
+        config,
+        // Pre-check if file has session-level retry config
+        has_session_retry: detect_session_retry_config(file),
+        enclosing_fn_name: None,
+        enclosing_fn_is_async: false,
+        enclosing_fn_retry: None,
+        http_client_vars: HashMap::new(),
+        timeout_client_vars: HashSet::new(),
+    };
+    walk(file, root, out, &mut state);
 }
 
 /// Check if a call node is wrapped in asyncio.to_thread() or similar thread offloading patterns.
@@ -317,11 +339,13 @@ fn check_thread_offload(file: &ParsedFile, call_node: Node) -> bool {
 /// - `async with httpx.AsyncClient() as client:`
 /// - `with requests.Session() as s:`
 ///
-/// Returns the variable name and client kind if this node creates an HTTP client binding.
+/// Returns the variable name, client kind, and whether the constructor configures a
+/// timeout (e.g. `httpx.Client(timeout=httpx.Timeout(5.0))`) if this node creates an
+/// HTTP client binding.
 fn extract_http_client_assignment(
     file: &ParsedFile,
     node: Node,
-) -> Option<(String, HttpClientKind)> {
+) -> Option<(String, HttpClientKind, bool)> {
 
     // Pattern 1: Assignment like `client = httpx.Client()`
     if node.kind() == "assignment" {
@@ -340,7 +364,7 @@ fn extract_http_client_assignment(
         }
 
         let client_kind = detect_http_client_constructor(file, right)?;
-        return Some((var_name, client_kind));
+        return Some((var_name, client_kind, call_has_timeout_kwarg(file, right)));
     }
 
     // Pattern 2: With statement like `with httpx.Client() as client:`
@@ -360,12 +384,12 @@ fn extract_http_client_assignment(
 fn extract_with_statement_binding(
     file: &ParsedFile,
     with_node: Node,
-) -> Option<(String, HttpClientKind)> {
+) -> Option<(String, HttpClientKind, bool)> {
     // Walk through all descendants looking for with_item nodes
     fn find_with_item_binding(
         file: &ParsedFile,
         node: Node,
-    ) -> Option<(String, HttpClientKind)> {
+    ) -> Option<(String, HttpClientKind, bool)> {
         
         if node.kind() == "with_item" {
             // The with_item structure is:
@@ -406,7 +430,7 @@ fn extract_with_statement_binding(
 
             if let (Some(call), Some(name)) = (call_node, var_name) {
                 if let Some(client_kind) = detect_http_client_constructor(file, call) {
-                    return Some((name, client_kind));
+                    return Some((name, client_kind, call_has_timeout_kwarg(file, call)));
                 }
             }
         }
@@ -449,13 +473,28 @@ fn detect_http_client_constructor(file: &ParsedFile, call_node: Node) -> Option<
     }
 }
 
+/// Check whether a call passes a `timeout=` keyword argument.
+///
+/// Any value counts, including `httpx.Timeout(...)` and `aiohttp.ClientTimeout(...)` objects.
+fn call_has_timeout_kwarg(file: &ParsedFile, call_node: Node) -> bool {
+    let Some(args) = call_node.child_by_field_name("arguments") else {
+        return false;
+    };
+
+    let mut cursor = args.walk();
+    args.named_children(&mut cursor).any(|arg| {
+        arg.kind() == "keyword_argument"
+            && arg
+                .child_by_field_name("name")
+                .is_some_and(|name| file.text_for_node(&name) == "timeout")
+    })
+}
+
 fn extract_http_call(
     file: &ParsedFile,
     call_node: Node,
-    enclosing_fn_name: Option<String>,
-    in_async_function: bool,
     is_thread_offloaded: bool,
-    http_client_vars: &std::collections::HashMap<String, HttpClientKind>,
+    state: &HttpWalkState,
 ) -> Option<HttpCallSite> {
     let func = call_node.child_by_field_name("function")?;
 
@@ -470,13 +509,17 @@ fn extract_http_call(
     let object_text = file.text_for_node(&object);
     let method_name = file.text_for_node(&attr);
 
+    // Registered custom clients take precedence over the built-in libraries
+    let custom_client = state.config.match_call(&object_text, &method_name);
+
     // First, try to match module-level calls (requests.get, httpx.post)
-    let client_kind = match object_text.as_str() {
-        "requests" => Some(HttpClientKind::Requests),
-        "httpx" => Some(HttpClientKind::Httpx),
+    let client_kind = match (custom_client, object_text.as_str()) {
+        (Some(name), _) => Some(HttpClientKind::Other(name.to_string())),
+        (None, "requests") => Some(HttpClientKind::Requests),
+        (None, "httpx") => Some(HttpClientKind::Httpx),
         _ => {
             // Check if the object is a known HTTP client variable
-            http_client_vars.get(&object_text).cloned()
+            state.http_client_vars.get(&object_text).cloned()
         }
     }?;
 
@@ -486,7 +529,7 @@ fn extract_http_call(
         method_name.to_lowercase().as_str(),
         "get" | "post" | "put" | "patch" | "delete" | "head" | "options" | "request"
     );
-    if !is_http_method {
+    if !is_http_method && custom_client.is_none() {
         return None;
     }
 
@@ -497,10 +540,12 @@ fn extract_http_call(
         String::new()
     };
 
-    let has_timeout = args_text.contains("timeout=");
+    let has_timeout =
+        args_text.contains("timeout=") || state.timeout_client_vars.contains(&object_text);
 
     let location = file.location_for_node(&call_node);
     let byte_range = call_node.byte_range();
+    let iteration = IterationContext::of(&call_node);
 
     Some(HttpCallSite {
         client_kind,
@@ -508,9 +553,11 @@ fn extract_http_call(
         call_text,
         has_timeout,
         location,
-        function_name: enclosing_fn_name,
-        in_async_function,
+        function_name: state.enclosing_fn_name.clone(),
+        in_async_function: state.enclosing_fn_is_async,
         is_thread_offloaded,
+        in_loop: iteration.in_loop,
+        in_comprehension: iteration.in_comprehension,
         start_byte: byte_range.start,
         end_byte: byte_range.end,
         retry_source: None,
@@ -563,6 +610,25 @@ mod tests {
         assert!(calls.is_empty());
     }
 
+    // ==================== Iteration Context Tests ====================
+
+    #[test]
+    fn flags_call_in_list_comprehension() {
+        let calls =
+            parse_and_summarize_http("pages = [requests.get(url, timeout=5) for url in urls]");
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].in_comprehension);
+        assert!(!calls[0].in_loop);
+    }
+
+    #[test]
+    fn flags_call_in_for_loop() {
+        let calls = parse_and_summarize_http("for url in urls:\n    requests.get(url)\n");
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].in_loop);
+        assert!(!calls[0].in_comprehension);
+    }
+
     // ==================== Method Name Tests ====================
 
     #[test]
@@ -1180,4 +1246,69 @@ async def fetch_starter():
         assert_eq!(calls[0].method_name, "get");
         assert!(!calls[0].has_timeout);
     }
+
+    // ==================== Client-Level Timeouts ====================
+
+    #[test]
+    fn detects_timeout_object_keyword_argument() {
+        let calls = parse_and_summarize_http(
+            "httpx.get('https://example.com', timeout=httpx.Timeout(10.0, connect=5.0))",
+        );
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_timeout_configured_on_client_constructor() {
+        let src = r#"
+def fetch():
+    client = httpx.Client(timeout=httpx.Timeout(5.0))
+    return client.get('https://example.com')
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_timeout_configured_on_async_with_client() {
+        let src = r#"
+async def fetch():
+    async with httpx.AsyncClient(timeout=10) as client:
+        return await client.get('https://example.com')
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn client_timeout_does_not_leak_into_other_functions() {
+        let src = r#"
+shared = httpx.Client(timeout=3)
+
+def fetch():
+    client = httpx.Client(timeout=5)
+    return client.get('https://example.com/a')
+
+def fetch_other(client):
+    shared.get('https://example.com/c')
+    return client.get('https://example.com/b')
+"#;
+        let calls = parse_and_summarize_http(src);
+        let timeouts: Vec<_> = calls.iter().map(|c| c.has_timeout).collect();
+        assert_eq!(timeouts, vec![true, true, false]);
+    }
+
+    #[test]
+    fn client_without_timeout_reports_missing_timeout() {
+        let src = r#"
+def fetch():
+    session = requests.Session()
+    return session.get('https://example.com')
+"#;
+        let calls = parse_and_summarize_http(src);
+        assert_eq!(calls.len(), 1);
+        assert!(!calls[0].has_timeout);
+    }
 }
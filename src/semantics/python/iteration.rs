@@ -0,0 +1,132 @@
+//! Loop and comprehension context shared by the Python sub-analyzers.
+//!
+//! `for`/`while` statements and comprehensions (list, dict, set and generator
+//! expressions) are tracked separately. Neither extends into nested function
+//! or lambda bodies, which don't run once per iteration.
+
+use tree_sitter::Node;
+
+/// Whether a node runs repeatedly inside a loop or comprehension.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IterationContext {
+    /// Inside a `for` or `while` statement
+    pub in_loop: bool,
+    /// Inside a list/dict/set comprehension or generator expression
+    pub in_comprehension: bool,
+}
+
+impl IterationContext {
+    /// Inside either a loop or a comprehension
+    pub fn is_iterating(&self) -> bool {
+        self.in_loop || self.in_comprehension
+    }
+
+    /// Context for the children of `node`, given the context `node` is in.
+    ///
+    /// Use this when walking the tree top-down.
+    pub fn enter(self, node: &Node) -> Self {
+        match node.kind() {
+            kind if is_loop(kind) => Self {
+                in_loop: true,
+                ..self
+            },
+            kind if is_comprehension(kind) => Self {
+                in_comprehension: true,
+                ..self
+            },
+            kind if is_scope_boundary(kind) => Self::default(),
+            _ => self,
+        }
+    }
+
+    /// Context of `node`, found by walking up its ancestors.
+    ///
+    /// Use this when a detector finds nodes without tracking context.
+    pub fn of(node: &Node) -> Self {
+        let mut ctx = Self::default();
+        let mut current = node.parent();
+        while let Some(ancestor) = current {
+            let kind = ancestor.kind();
+            if is_scope_boundary(kind) {
+                break;
+            }
+            ctx.in_loop |= is_loop(kind);
+            ctx.in_comprehension |= is_comprehension(kind);
+            current = ancestor.parent();
+        }
+        ctx
+    }
+}
+
+fn is_loop(kind: &str) -> bool {
+    matches!(kind, "for_statement" | "while_statement")
+}
+
+fn is_comprehension(kind: &str) -> bool {
+    matches!(
+        kind,
+        "list_comprehension"
+            | "dictionary_comprehension"
+            | "set_comprehension"
+            | "generator_expression"
+    )
+}
+
+fn is_scope_boundary(kind: &str) -> bool {
+    matches!(kind, "function_definition" | "lambda")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ast::{FileId, ParsedFile};
+    use crate::parse::python::parse_python_file;
+    use crate::types::context::{Language, SourceFile};
+
+    fn parse(src: &str) -> ParsedFile {
+        let sf = SourceFile {
+            path: "a.py".to_string(),
+            language: Language::Python,
+            content: src.to_string(),
+        };
+        parse_python_file(FileId(1), &sf).unwrap()
+    }
+
+    /// Context of the first call to `name` in the file.
+    fn context_of_call(parsed: &ParsedFile, name: &str) -> IterationContext {
+        let mut stack = vec![parsed.tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "call"
+                && node
+                    .child_by_field_name("function")
+                    .is_some_and(|f| parsed.text_for_node(&f) == name)
+            {
+                return IterationContext::of(&node);
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        panic!("no call to {name}");
+    }
+
+    #[test]
+    fn distinguishes_loops_from_comprehensions() {
+        let parsed = parse(
+            "for x in xs:\n    a(x)\nys = [b(y) for y in xs]\nwhile True:\n    zs = (c(z) for z in xs)\nd()\n",
+        );
+        let a = context_of_call(&parsed, "a");
+        assert!(a.in_loop && !a.in_comprehension);
+        let b = context_of_call(&parsed, "b");
+        assert!(!b.in_loop && b.in_comprehension);
+        let c = context_of_call(&parsed, "c");
+        assert!(c.in_loop && c.in_comprehension);
+        assert!(!context_of_call(&parsed, "d").is_iterating());
+    }
+
+    #[test]
+    fn nested_function_body_is_not_iterating() {
+        let parsed = parse("for x in xs:\n    def inner():\n        a()\n    f = lambda: b()\n");
+        assert!(!context_of_call(&parsed, "a").is_iterating());
+        assert!(!context_of_call(&parsed, "b").is_iterating());
+    }
+}
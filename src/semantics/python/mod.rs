@@ -5,13 +5,16 @@ pub mod django;
 pub mod fastapi;
 pub mod flask;
 pub mod http;
+pub mod iteration;
 pub mod model;
 pub mod orm;
+pub mod stdlib;
 
 use anyhow::Result;
 
 use crate::parse::ast::ParsedFile;
 use model::PyFileSemantics;
+use stdlib::PythonStdlib;
 
 /// Build the semantic model for a single Python file.
 ///
@@ -24,6 +27,18 @@ pub fn build_python_semantics(parsed: &ParsedFile) -> Result<PyFileSemantics> {
     Ok(sem)
 }
 
+/// Build the semantic model for a Python file, classifying imports against
+/// the given stdlib module set (e.g. one pinned to the project's Python version).
+pub fn build_python_semantics_with_stdlib(
+    parsed: &ParsedFile,
+    stdlib: &PythonStdlib,
+) -> Result<PyFileSemantics> {
+    let mut sem = PyFileSemantics::from_parsed(parsed);
+    sem.classify_imports(stdlib);
+    sem.analyze_frameworks(parsed)?;
+    Ok(sem)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,6 +88,51 @@ x = 42
         assert!(!sem.assignments.is_empty());
     }
 
+    #[test]
+    fn imports_are_classified_against_stdlib_list() {
+        use model::ImportCategory;
+
+        let src = r#"
+import concurrent.futures
+from email.mime.text import MIMEText
+import requests
+from . import views
+import mycorp_runtime
+"#;
+        let category_of = |sem: &PyFileSemantics, module: &str| {
+            sem.imports
+                .iter()
+                .find(|i| i.module == module)
+                .map(|i| i.category)
+                .unwrap()
+        };
+
+        let sem = parse_and_build_full_semantics(src);
+        assert_eq!(
+            category_of(&sem, "concurrent.futures"),
+            ImportCategory::Stdlib
+        );
+        assert_eq!(category_of(&sem, "email.mime.text"), ImportCategory::Stdlib);
+        assert_eq!(category_of(&sem, "requests"), ImportCategory::ThirdParty);
+        assert_eq!(category_of(&sem, "."), ImportCategory::Local);
+
+        let sf = SourceFile {
+            path: "test.py".to_string(),
+            language: Language::Python,
+            content: src.to_string(),
+        };
+        let parsed = parse_python_file(FileId(1), &sf).expect("parsing should succeed");
+        let stdlib = PythonStdlib::from_modules(["concurrent", "mycorp_runtime"]);
+        let sem = build_python_semantics_with_stdlib(&parsed, &stdlib)
+            .expect("semantics building should succeed");
+        assert_eq!(category_of(&sem, "mycorp_runtime"), ImportCategory::Stdlib);
+        assert_eq!(
+            category_of(&sem, "email.mime.text"),
+            ImportCategory::ThirdParty
+        );
+        assert_eq!(category_of(&sem, "."), ImportCategory::Local);
+    }
+
     #[test]
     fn build_python_semantics_runs_framework_analysis() {
         let src = r#"
@@ -1,15 +1,23 @@
+use std::collections::BTreeSet;
+
 use serde::{Deserialize, Serialize};
 
 use crate::parse::ast::{AstLocation, FileId, ParsedFile};
-use crate::semantics::common::CommonLocation;
+use crate::semantics::common::async_ops::AsyncOperation as CommonAsyncOperation;
 use crate::semantics::common::calls::FunctionCall;
+use crate::semantics::common::db::DbOperation;
+use crate::semantics::common::{CommonLocation, CommonSemantics};
+use crate::semantics::metrics::{PYTHON_RULES, cyclomatic_complexity, max_nesting_depth};
 use crate::semantics::python::http::HttpCallSite;
+use crate::semantics::python::iteration::IterationContext;
 use crate::semantics::python::orm::OrmQueryCall;
+use crate::semantics::references::{self, referenced_identifiers};
 use crate::types::context::Language;
 
 use super::django::DjangoFileSummary;
 use super::fastapi::FastApiFileSummary;
 use super::flask::FlaskFileSummary;
+use super::stdlib::{PythonStdlib, STDLIB_MODULES};
 
 /// Information about a bare except clause found in the code.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +49,13 @@ pub struct PyFileSemantics {
     pub file_id: FileId,
     pub path: String,
     pub language: Language,
+    /// Number of lines in the source file
+    #[serde(default)]
+    pub line_count: usize,
+
+    /// Names referenced outside import statements (for unused-import detection)
+    #[serde(default)]
+    pub identifiers: BTreeSet<String>,
 
     /// Raw imports like `import fastapi as fa` or `from fastapi import FastAPI`.
     pub imports: Vec<PyImport>,
@@ -129,6 +144,20 @@ pub struct AsyncOperation {
     /// Bound/semaphore limit (if determinable)
     pub bound_limit: Option<u32>,
 
+    /// Whether this operation occurs inside a `for`/`while` loop
+    pub in_loop: bool,
+
+    /// Whether this operation occurs inside a comprehension or generator expression
+    #[serde(default)]
+    pub in_comprehension: bool,
+
+    /// Whether this operation runs under `async with` or `try/finally` teardown
+    pub has_cleanup: bool,
+
+    /// Whether the result is dropped: the call is a statement on its own
+    #[serde(default)]
+    pub is_discarded: bool,
+
     /// Full text of the operation
     pub operation_text: String,
 
@@ -228,6 +257,8 @@ impl PyFileSemantics {
             file_id: parsed.file_id,
             path: parsed.path.clone(),
             language: parsed.language,
+            line_count: parsed.source.lines().count(),
+            identifiers: referenced_identifiers(parsed, &references::PYTHON_RULES),
             imports: Vec::new(),
             functions: Vec::new(),
             classes: Vec::new(),
@@ -286,6 +317,24 @@ impl PyFileSemantics {
         Ok(())
     }
 
+    /// Re-classify absolute imports against a specific stdlib module set.
+    ///
+    /// Imports are classified against the version-agnostic baked-in list while
+    /// building; use this to pin a Python version or supply a custom list.
+    /// Relative imports stay local.
+    pub fn classify_imports(&mut self, stdlib: &PythonStdlib) {
+        for imp in &mut self.imports {
+            if imp.category == ImportCategory::Local {
+                continue;
+            }
+            imp.category = if stdlib.contains(&imp.module) {
+                ImportCategory::Stdlib
+            } else {
+                ImportCategory::ThirdParty
+            };
+        }
+    }
+
     /// Returns the 1-based line number where new imports should be inserted.
     ///
     /// This is a simple method that places imports after the last existing import
@@ -511,6 +560,70 @@ impl PyFileSemantics {
                 || module.starts_with("peewee.")
         })
     }
+
+    /// Database calls inside `async def` functions that return a coroutine but
+    /// are never awaited, e.g. `session.execute(stmt)` on an `AsyncSession`.
+    ///
+    /// Calls in sync functions are not reported. When an un-awaited call is
+    /// part of a longer un-awaited chain, only the outermost call is returned.
+    pub fn orm_calls_missing_await(&self) -> Vec<DbOperation> {
+        let missing: Vec<&OrmQueryCall> = self
+            .orm_queries
+            .iter()
+            .filter(|q| !q.has_await && q.is_awaitable() && self.in_async_function(q.start_byte))
+            .collect();
+        let outermost: Vec<&OrmQueryCall> = missing
+            .iter()
+            .filter(|q| {
+                !missing.iter().any(|outer| {
+                    outer.start_byte <= q.start_byte
+                        && q.end_byte <= outer.end_byte
+                        && (outer.end_byte - outer.start_byte) > (q.end_byte - q.start_byte)
+                })
+            })
+            .copied()
+            .collect();
+
+        self.db_operations()
+            .into_iter()
+            .filter(|op| {
+                outermost
+                    .iter()
+                    .any(|q| q.start_byte == op.start_byte && q.end_byte == op.end_byte)
+            })
+            .collect()
+    }
+
+    /// `asyncio.create_task`/`ensure_future` calls whose task is dropped:
+    /// neither assigned, awaited, returned nor passed anywhere.
+    ///
+    /// The event loop only keeps a weak reference to tasks, so an orphaned
+    /// task can be garbage-collected (and silently cancelled) mid-flight.
+    pub fn orphan_tasks(&self) -> Vec<CommonAsyncOperation> {
+        let orphans: Vec<&AsyncOperation> = self
+            .async_operations
+            .iter()
+            .filter(|op| op.operation_type == AsyncOperationType::TaskSpawn && op.is_discarded)
+            .collect();
+
+        self.async_operations()
+            .into_iter()
+            .filter(|op| {
+                orphans
+                    .iter()
+                    .any(|o| o.start_byte == op.start_byte && o.end_byte == op.end_byte)
+            })
+            .collect()
+    }
+
+    /// Whether the innermost function containing `byte` is `async`.
+    fn in_async_function(&self, byte: usize) -> bool {
+        self.functions
+            .iter()
+            .filter(|f| f.start_byte <= byte && byte < f.end_byte)
+            .min_by_key(|f| f.end_byte - f.start_byte)
+            .is_some_and(|f| f.is_async)
+    }
 }
 
 /// The style of an import statement.
@@ -597,6 +710,21 @@ pub struct PyImport {
     /// True if this import is at module level (not inside a function, class, if-block, etc.).
     pub is_module_level: bool,
 
+    /// True for runtime imports with a literal module name, such as
+    /// `importlib.import_module("x")` or `__import__("x")`.
+    #[serde(default)]
+    pub is_dynamic: bool,
+
+    /// Byte range of the whole import statement in the source.
+    #[serde(default)]
+    pub start_byte: usize,
+    #[serde(default)]
+    pub end_byte: usize,
+
+    /// Source text of the import statement, e.g. "from fastapi import FastAPI".
+    #[serde(default)]
+    pub raw_text: String,
+
     pub location: AstLocation,
 }
 
@@ -617,207 +745,6 @@ impl PyImport {
     }
 }
 
-/// Known Python standard library modules (for categorizing imports).
-const STDLIB_MODULES: &[&str] = &[
-    // Frequently used in production code
-    "abc",
-    "argparse",
-    "asyncio",
-    "base64",
-    "bisect",
-    "builtins",
-    "calendar",
-    "codecs",
-    "collections",
-    "concurrent",
-    "contextlib",
-    "copy",
-    "csv",
-    "dataclasses",
-    "datetime",
-    "decimal",
-    "difflib",
-    "dis",
-    "email",
-    "enum",
-    "errno",
-    "faulthandler",
-    "functools",
-    "gc",
-    "getpass",
-    "glob",
-    "gzip",
-    "hashlib",
-    "heapq",
-    "hmac",
-    "html",
-    "http",
-    "importlib",
-    "inspect",
-    "io",
-    "ipaddress",
-    "itertools",
-    "json",
-    "locale",
-    "logging",
-    "math",
-    "mimetypes",
-    "multiprocessing",
-    "numbers",
-    "operator",
-    "os",
-    "pathlib",
-    "pickle",
-    "platform",
-    "pprint",
-    "queue",
-    "random",
-    "re",
-    "secrets",
-    "select",
-    "shutil",
-    "signal",
-    "socket",
-    "sqlite3",
-    "ssl",
-    "stat",
-    "statistics",
-    "string",
-    "struct",
-    "subprocess",
-    "sys",
-    "tempfile",
-    "textwrap",
-    "threading",
-    "time",
-    "timeit",
-    "traceback",
-    "typing",
-    "unittest",
-    "urllib",
-    "uuid",
-    "warnings",
-    "weakref",
-    "xml",
-    "zipfile",
-    "zlib",
-    // Testing and debugging
-    "doctest",
-    "pdb",
-    "profile",
-    "trace",
-    "unittest",
-    // Less common but still stdlib
-    "aifc",
-    "array",
-    "ast",
-    "atexit",
-    "audioop",
-    "bdb",
-    "binascii",
-    "binhex",
-    "cgi",
-    "cgitb",
-    "chunk",
-    "cmath",
-    "cmd",
-    "code",
-    "codeop",
-    "colorsys",
-    "compileall",
-    "configparser",
-    "cProfile",
-    "crypt",
-    "ctypes",
-    "curses",
-    "dbm",
-    "filecmp",
-    "fileinput",
-    "fnmatch",
-    "formatter",
-    "fractions",
-    "ftplib",
-    "getopt",
-    "gettext",
-    "graphlib",
-    "grp",
-    "imaplib",
-    "imp",
-    "keyword",
-    "lib2to3",
-    "linecache",
-    "lzma",
-    "mailbox",
-    "mailcap",
-    "marshal",
-    "mmap",
-    "modulefinder",
-    "netrc",
-    "nis",
-    "nntplib",
-    "ntpath",
-    "optparse",
-    "ossaudiodev",
-    "parser",
-    "pickletools",
-    "pipes",
-    "pkgutil",
-    "poplib",
-    "posix",
-    "posixpath",
-    "pow",
-    "pstats",
-    "pty",
-    "pwd",
-    "py_compile",
-    "pyclbr",
-    "pydoc",
-    "quopri",
-    "readline",
-    "reprlib",
-    "resource",
-    "rlcompleter",
-    "runpy",
-    "sched",
-    "selectors",
-    "shelve",
-    "shlex",
-    "smtpd",
-    "smtplib",
-    "sndhdr",
-    "spwd",
-    "stringprep",
-    "sunau",
-    "symbol",
-    "symtable",
-    "sysconfig",
-    "syslog",
-    "tabnanny",
-    "tarfile",
-    "telnetlib",
-    "termios",
-    "test",
-    "token",
-    "tokenize",
-    "tomllib",
-    "tty",
-    "turtle",
-    "turtledemo",
-    "types",
-    "unicodedata",
-    "uu",
-    "venv",
-    "wave",
-    "webbrowser",
-    "winreg",
-    "winsound",
-    "wsgiref",
-    "xdrlib",
-    "xmlrpc",
-    "zipapp",
-    "zipimport",
-];
-
 /// Check if a module name is a Python standard library module.
 pub fn is_stdlib_module(module: &str) -> bool {
     // Get the top-level module name (e.g., "os.path" -> "os")
@@ -831,6 +758,12 @@ pub struct PyFunction {
     pub name: String,
     pub is_method: bool,
     pub class_name: Option<String>,
+    /// Name of the enclosing function for inner `def`s and lambdas
+    #[serde(default)]
+    pub parent_function: Option<String>,
+    /// Whether this is a `lambda` expression
+    #[serde(default)]
+    pub is_lambda: bool,
     pub params: Vec<PyParam>,
     pub is_async: bool,
     /// Return type annotation if present, e.g. "bool", "dict[str, Any]", "None"
@@ -843,6 +776,10 @@ pub struct PyFunction {
     pub start_byte: usize,
     /// End byte offset of the function definition
     pub end_byte: usize,
+    /// Cyclomatic complexity (1 for straight-line code)
+    pub cyclomatic_complexity: u32,
+    /// Deepest nesting of control blocks (1 for straight-line code)
+    pub max_nesting_depth: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -851,6 +788,12 @@ pub struct PyParam {
     pub default: Option<String>,
     /// Type annotation if present, e.g. "int", "str", "dict", "SessionRunRequest"
     pub type_annotation: Option<String>,
+    /// Whether the parameter follows a bare `*` or `*args` separator
+    #[serde(default)]
+    pub is_keyword_only: bool,
+    /// Whether the parameter precedes a `/` separator
+    #[serde(default)]
+    pub is_positional_only: bool,
 }
 
 /// Representation of a Python class definition.
@@ -930,8 +873,7 @@ impl PyRange {
 /// Context for tracking loop/comprehension nesting during AST traversal.
 #[derive(Default, Clone)]
 struct TraversalContext {
-    in_loop: bool,
-    in_comprehension: bool,
+    iteration: IterationContext,
     /// True if we're inside a function, class, if-block, or any non-module scope.
     /// Used to filter out non-module-level assignments and imports.
     in_nested_scope: bool,
@@ -963,6 +905,10 @@ fn walk_nodes_with_context(
     ctx: TraversalContext,
 ) {
     // Update context based on current node
+    let ctx = TraversalContext {
+        iteration: ctx.iteration.enter(&node),
+        ..ctx
+    };
     let new_ctx = match node.kind() {
         "class_definition" => {
             let class_name = node
@@ -992,17 +938,6 @@ fn walk_nodes_with_context(
                 ..ctx.clone()
             }
         }
-        "for_statement" | "while_statement" => TraversalContext {
-            in_loop: true,
-            ..ctx.clone()
-        },
-        "list_comprehension"
-        | "dictionary_comprehension"
-        | "set_comprehension"
-        | "generator_expression" => TraversalContext {
-            in_comprehension: true,
-            ..ctx.clone()
-        },
         // Track when we enter a lambda or if-block - these create new scopes
         "lambda" | "if_statement" => TraversalContext {
             in_nested_scope: true,
@@ -1028,6 +963,10 @@ fn walk_nodes_with_context(
             }
         }
         "call" => {
+            if let Some(mut imp) = build_dynamic_import(parsed, &node) {
+                imp.is_module_level = !ctx.in_nested_scope;
+                sem.imports.push(imp);
+            }
             if let Some(call) = build_callsite(parsed, &node, &new_ctx, sem) {
                 sem.calls.push(call);
             }
@@ -1037,6 +976,12 @@ fn walk_nodes_with_context(
                 sem.functions.push(fun);
             }
         }
+        // The `lambda` keyword token shares the expression's kind
+        "lambda" if node.is_named() => {
+            if let Some(fun) = build_lambda(parsed, &node) {
+                sem.functions.push(fun);
+            }
+        }
         "class_definition" => {
             if let Some(cls) = build_class(parsed, &node) {
                 sem.classes.push(cls);
@@ -1307,10 +1252,71 @@ fn build_import(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<PyImpor
         style,
         category,
         is_module_level: true, // Will be set properly by the caller
+        is_dynamic: false,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        raw_text: text,
         location,
     })
 }
 
+/// Build a PyImport from a dynamic import call such as
+/// `importlib.import_module("os")` or `__import__("os")`.
+///
+/// String-literal modules are unquoted; any other expression is kept as written.
+fn build_dynamic_import(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<PyImport> {
+    let func_node = node.child_by_field_name("function")?;
+    let callee = parsed.text_for_node(&func_node);
+    if !matches!(
+        callee.as_str(),
+        "importlib.import_module" | "import_module" | "__import__"
+    ) {
+        return None;
+    }
+
+    // Only literal module names can be resolved; `__import__(name)` and
+    // f-strings are skipped rather than recorded under the variable's name.
+    let args = node.child_by_field_name("arguments")?;
+    let module_node = args.named_child(0)?;
+    let mut cursor = module_node.walk();
+    if module_node.kind() != "string"
+        || module_node
+            .named_children(&mut cursor)
+            .any(|child| child.kind() == "interpolation")
+    {
+        return None;
+    }
+    let module = parsed
+        .text_for_node(&module_node)
+        .trim_matches(|c| c == '\'' || c == '"')
+        .to_string();
+    if module.is_empty() {
+        return None;
+    }
+
+    let category = if is_stdlib_module(&module) {
+        ImportCategory::Stdlib
+    } else if module.starts_with('.') {
+        ImportCategory::Local
+    } else {
+        ImportCategory::ThirdParty
+    };
+
+    Some(PyImport {
+        module,
+        names: Vec::new(),
+        alias: None,
+        style: ImportStyle::Import,
+        category,
+        is_module_level: true, // Will be set properly by the caller
+        is_dynamic: true,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        raw_text: parsed.text_for_node(node),
+        location: parsed.location_for_node(node),
+    })
+}
+
 /// Build a PyAssignment from an assignment node.
 fn build_assignment(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<PyAssignment> {
     let location = parsed.location_for_node(node);
@@ -1484,8 +1490,8 @@ fn build_callsite(
         function_call,
         args,
         args_repr,
-        in_loop: ctx.in_loop,
-        in_comprehension: ctx.in_comprehension,
+        in_loop: ctx.iteration.in_loop,
+        in_comprehension: ctx.iteration.in_comprehension,
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
     })
@@ -1550,10 +1556,14 @@ fn build_function(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<PyFun
         .child_by_field_name("body")
         .map(|body_node| compute_body_hash(parsed, &body_node));
 
+    let class_name = enclosing_class_name(parsed, node);
+
     Some(PyFunction {
         name,
-        is_method: false, // we'll refine later if we want to track methods vs functions
-        class_name: None, // same here
+        is_method: class_name.is_some(),
+        class_name,
+        parent_function: enclosing_function_name(parsed, node),
+        is_lambda: false,
         params,
         is_async,
         return_type,
@@ -1561,9 +1571,80 @@ fn build_function(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<PyFun
         location,
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &PYTHON_RULES),
+        max_nesting_depth: max_nesting_depth(node, &PYTHON_RULES),
+    })
+}
+
+/// Build a PyFunction for a lambda nested inside a function.
+///
+/// Lambdas bound by assignment (`key = lambda x: ...`) take the variable's
+/// name; others are named `<lambda>`, as Python itself does. Module-level
+/// lambdas are not collected.
+fn build_lambda(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<PyFunction> {
+    let parent_function = enclosing_function_name(parsed, node)?;
+
+    let name = node
+        .parent()
+        .filter(|p| p.kind() == "assignment")
+        .and_then(|p| p.child_by_field_name("left"))
+        .filter(|left| left.kind() == "identifier")
+        .map(|left| parsed.text_for_node(&left))
+        .unwrap_or_else(|| "<lambda>".to_string());
+
+    Some(PyFunction {
+        name,
+        is_method: false,
+        class_name: None,
+        parent_function: Some(parent_function),
+        is_lambda: true,
+        params: extract_function_params(parsed, node),
+        is_async: false,
+        return_type: None,
+        body_hash: node
+            .child_by_field_name("body")
+            .map(|body_node| compute_body_hash(parsed, &body_node)),
+        location: parsed.location_for_node(node),
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &PYTHON_RULES),
+        max_nesting_depth: max_nesting_depth(node, &PYTHON_RULES),
     })
 }
 
+/// Name of the nearest enclosing `def`, if any.
+fn enclosing_function_name(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if matches!(
+            ancestor.kind(),
+            "function_definition" | "async_function_definition"
+        ) {
+            return ancestor
+                .child_by_field_name("name")
+                .map(|n| parsed.text_for_node(&n));
+        }
+        current = ancestor.parent();
+    }
+    None
+}
+
+/// Name of the class whose body directly defines this function, if any.
+fn enclosing_class_name(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<String> {
+    let mut definition = *node;
+    if let Some(parent) = definition.parent()
+        && parent.kind() == "decorated_definition"
+    {
+        definition = parent;
+    }
+
+    let block = definition.parent().filter(|p| p.kind() == "block")?;
+    let class = block.parent().filter(|p| p.kind() == "class_definition")?;
+    class
+        .child_by_field_name("name")
+        .map(|n| parsed.text_for_node(&n))
+}
+
 /// Compute a hash of the function body for duplication detection.
 ///
 /// The body is normalized to ignore:
@@ -1606,11 +1687,24 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
         None => return params,
     };
 
+    // Parameters after a bare `*` or `*args` are keyword-only
+    let mut keyword_only = false;
+
     // Iterate through children of the parameters node
     let child_count = params_node.named_child_count();
     for i in 0..child_count {
         if let Some(param_node) = params_node.named_child(i) {
+            let first_new = params.len();
             match param_node.kind() {
+                "keyword_separator" => {
+                    keyword_only = true;
+                }
+                "positional_separator" => {
+                    // Everything before `/` is positional-only
+                    for param in &mut params {
+                        param.is_positional_only = true;
+                    }
+                }
                 "identifier" => {
                     // Simple parameter like `x` (no type annotation)
                     let name = parsed.text_for_node(&param_node);
@@ -1618,6 +1712,8 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                         name,
                         default: None,
                         type_annotation: None,
+                        is_keyword_only: false,
+                        is_positional_only: false,
                     });
                 }
                 "typed_parameter" => {
@@ -1635,6 +1731,8 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                             name,
                             default: None,
                             type_annotation,
+                            is_keyword_only: false,
+                            is_positional_only: false,
                         });
                     } else {
                         // Fallback: parse the text "name: type" directly
@@ -1644,6 +1742,8 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                                 name,
                                 default: None,
                                 type_annotation: Some(type_ann),
+                                is_keyword_only: false,
+                                is_positional_only: false,
                             });
                         } else {
                             // Ultimate fallback: try to get the first identifier child
@@ -1655,6 +1755,8 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                                             name,
                                             default: None,
                                             type_annotation: None,
+                                            is_keyword_only: false,
+                                            is_positional_only: false,
                                         });
                                         break;
                                     }
@@ -1674,6 +1776,8 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                             name,
                             default,
                             type_annotation: None,
+                            is_keyword_only: false,
+                            is_positional_only: false,
                         });
                     }
                 }
@@ -1694,6 +1798,8 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                             name,
                             default,
                             type_annotation,
+                            is_keyword_only: false,
+                            is_positional_only: false,
                         });
                     } else {
                         // Fallback: parse the text "name: type = value" directly
@@ -1705,6 +1811,8 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                                 name,
                                 default: def_val,
                                 type_annotation: Some(type_ann),
+                                is_keyword_only: false,
+                                is_positional_only: false,
                             });
                         }
                     }
@@ -1716,6 +1824,8 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                         name: text,
                         default: None,
                         type_annotation: None,
+                        is_keyword_only: false,
+                        is_positional_only: false,
                     });
                 }
                 _ => {
@@ -1726,10 +1836,23 @@ fn extract_function_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec
                             name: text,
                             default: None,
                             type_annotation: None,
+                            is_keyword_only: false,
+                            is_positional_only: false,
                         });
                     }
                 }
             }
+
+            for param in &mut params[first_new..] {
+                if param.name.starts_with("**") {
+                    continue;
+                }
+                if param.name.starts_with('*') {
+                    keyword_only = true;
+                    continue;
+                }
+                param.is_keyword_only = keyword_only;
+            }
         }
     }
 
@@ -2017,6 +2140,28 @@ def hello():
         assert!(!sem.functions[0].is_method);
     }
 
+    #[test]
+    fn records_owning_class_of_methods() {
+        let src = r#"
+class Worker:
+    @staticmethod
+    def build():
+        def helper():
+            pass
+
+    def run(self):
+        pass
+"#;
+        let sem = parse_and_build_semantics(src);
+        let class_of = |name: &str| {
+            let f = sem.functions.iter().find(|f| f.name == name).unwrap();
+            (f.is_method, f.class_name.clone())
+        };
+        assert_eq!(class_of("build"), (true, Some("Worker".to_string())));
+        assert_eq!(class_of("run"), (true, Some("Worker".to_string())));
+        assert_eq!(class_of("helper"), (false, None));
+    }
+
     #[test]
     fn collects_async_function() {
         let src = r#"
@@ -2808,6 +2953,77 @@ def to_dict(self) -> dict[str, Any]:
         assert_eq!(func.return_type, Some("dict[str, Any]".to_string()));
     }
 
+    #[test]
+    fn nested_generic_and_union_annotations_round_trip_verbatim() {
+        let src = r#"
+def load(rows: Optional[List[Dict[str, Tuple[int, ...]]]], key: str | None = None) -> Dict[str, List[int | None]]:
+    return {}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let func = &sem.functions[0];
+        assert_eq!(
+            func.params[0].type_annotation.as_deref(),
+            Some("Optional[List[Dict[str, Tuple[int, ...]]]]")
+        );
+        assert_eq!(
+            func.params[1].type_annotation.as_deref(),
+            Some("str | None")
+        );
+        assert_eq!(func.params[1].default.as_deref(), Some("None"));
+        assert_eq!(
+            func.return_type.as_deref(),
+            Some("Dict[str, List[int | None]]")
+        );
+    }
+
+    #[test]
+    fn flags_keyword_only_params_after_bare_star() {
+        let src = r#"
+def fetch(url, *, timeout: float = 5.0, retries=3):
+    pass
+"#;
+        let sem = parse_and_build_semantics(src);
+        let func = &sem.functions[0];
+        let names: Vec<&str> = func.params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["url", "timeout", "retries"]);
+        assert!(!func.params[0].is_keyword_only);
+        assert!(func.params[1].is_keyword_only);
+        assert!(func.params[2].is_keyword_only);
+    }
+
+    #[test]
+    fn flags_keyword_only_params_after_var_args() {
+        let src = r#"
+def log(msg, *args, level="info", **kwargs):
+    pass
+"#;
+        let sem = parse_and_build_semantics(src);
+        let func = &sem.functions[0];
+        assert!(!func.params[0].is_keyword_only);
+        assert_eq!(func.params[1].name, "*args");
+        assert!(!func.params[1].is_keyword_only);
+        assert_eq!(func.params[2].name, "level");
+        assert!(func.params[2].is_keyword_only);
+        assert!(!func.params[3].is_keyword_only);
+    }
+
+    #[test]
+    fn flags_positional_only_params_before_slash() {
+        let src = r#"
+def clamp(value, lo, /, hi, *, strict=False):
+    pass
+"#;
+        let sem = parse_and_build_semantics(src);
+        let func = &sem.functions[0];
+        let names: Vec<&str> = func.params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["value", "lo", "hi", "strict"]);
+        assert!(func.params[0].is_positional_only);
+        assert!(func.params[1].is_positional_only);
+        assert!(!func.params[2].is_positional_only);
+        assert!(!func.params[2].is_keyword_only);
+        assert!(func.params[3].is_keyword_only);
+    }
+
     #[test]
     fn extracts_none_return_type() {
         let src = r#"
@@ -2914,4 +3130,93 @@ import SQLAlchemy
         // but our detection should be case-insensitive
         assert!(sem.has_orm_imports());
     }
+
+    fn parse_and_analyze(source: &str) -> PyFileSemantics {
+        let sf = SourceFile {
+            path: "test.py".to_string(),
+            language: Language::Python,
+            content: source.to_string(),
+        };
+        let parsed = parse_python_file(FileId(1), &sf).expect("parsing should succeed");
+        let mut sem = PyFileSemantics::from_parsed(&parsed);
+        sem.analyze_frameworks(&parsed).unwrap();
+        sem
+    }
+
+    #[test]
+    fn awaited_orm_call_is_not_missing_await() {
+        let sem = parse_and_analyze(
+            r#"
+async def get_user(session, user_id):
+    result = await session.execute(select(User).where(User.id == user_id))
+    return result.scalar_one()
+"#,
+        );
+        assert!(sem.orm_calls_missing_await().is_empty());
+    }
+
+    #[test]
+    fn unawaited_orm_call_in_async_def_is_flagged() {
+        let sem = parse_and_analyze(
+            r#"
+async def delete_user(session, user):
+    session.delete(user)
+    await session.commit()
+
+def sync_cleanup(session, user):
+    session.delete(user)
+"#,
+        );
+        let missing = sem.orm_calls_missing_await();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].operation_text, "session.delete(user)");
+        assert_eq!(
+            missing[0].enclosing_function.as_deref(),
+            Some("delete_user")
+        );
+    }
+
+    #[test]
+    fn discarded_create_task_is_orphaned() {
+        let sem = parse_and_analyze(
+            r#"
+import asyncio
+
+async def main():
+    asyncio.create_task(refresh())
+    asyncio.ensure_future(notify())
+    task = asyncio.create_task(work())
+    await asyncio.create_task(other())
+    background.add(asyncio.create_task(tracked()))
+    await task
+"#,
+        );
+        let orphans = sem.orphan_tasks();
+        let texts: Vec<&str> = orphans
+            .iter()
+            .map(|op| op.operation_text.as_str())
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "asyncio.create_task(refresh())",
+                "asyncio.ensure_future(notify())"
+            ]
+        );
+        assert_eq!(orphans[0].enclosing_function.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn assigned_create_task_is_not_orphaned() {
+        let sem = parse_and_analyze(
+            r#"
+import asyncio
+
+async def main():
+    t = asyncio.create_task(x())
+    await t
+"#,
+        );
+        assert!(sem.orphan_tasks().is_empty());
+    }
 }
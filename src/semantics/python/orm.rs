@@ -6,6 +6,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::parse::ast::{AstLocation, ParsedFile};
+use crate::semantics::common::db::{
+    DbOperationType, is_mongo_collection_receiver, is_redis_receiver,
+};
+
+use super::iteration::IterationContext;
 
 /// Represents an ORM query call site
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,10 +23,16 @@ pub struct OrmQueryCall {
     pub in_loop: bool,
     /// Whether this query is inside a comprehension
     pub in_comprehension: bool,
+    /// Whether this query is inside a transaction block
+    /// (`with session.begin():`, `transaction.atomic()`, etc.)
+    pub in_transaction: bool,
     /// Whether eager loading is used (select_related, prefetch_related, joinedload, etc.)
     pub has_eager_loading: bool,
     /// The model/table being queried (if detectable)
     pub model_name: Option<String>,
+    /// Whether the SQL string is built via f-string, `%`, `+` or `.format()`
+    /// with non-literal values (likely SQL injection)
+    pub has_string_interpolation: bool,
     /// Line number (1-based)
     pub line: u32,
     /// Column number (1-based)
@@ -38,6 +49,44 @@ pub struct OrmQueryCall {
     pub loop_variable: Option<String>,
     /// The outer query that provides the collection being iterated (if detectable)
     pub outer_query: Option<Box<OuterQueryInfo>>,
+    /// Name of the enclosing function (if any)
+    #[serde(default)]
+    pub enclosing_function: Option<String>,
+    /// Whether the call is the operand of an `await` expression
+    #[serde(default)]
+    pub has_await: bool,
+}
+
+/// `AsyncSession` methods that return a coroutine and must be awaited
+const ASYNC_SESSION_METHODS: &[&str] = &[
+    "execute", "scalars", "scalar", "get", "delete", "commit", "rollback", "flush", "refresh",
+    "merge", "stream",
+];
+
+impl OrmQueryCall {
+    /// Whether this call returns a coroutine when used from async code.
+    ///
+    /// Tortoise queries are always awaitable; SQLAlchemy/SQLModel calls are
+    /// awaitable when they invoke an `AsyncSession` method such as
+    /// `session.execute(...)`. Django and sync-only APIs like `session.add`
+    /// are never reported, nor are MongoDB/Redis calls, whose sync and async
+    /// clients (PyMongo/Motor, redis/redis.asyncio) share method names.
+    pub fn is_awaitable(&self) -> bool {
+        match self.orm_kind {
+            OrmKind::Tortoise => true,
+            OrmKind::SqlAlchemy | OrmKind::SqlModel | OrmKind::Unknown => {
+                let Some(text) = self.query_text.as_deref() else {
+                    return false;
+                };
+                let callee = text.split('(').next().unwrap_or_default();
+                callee.rsplit_once('.').is_some_and(|(receiver, method)| {
+                    receiver.to_lowercase().ends_with("session")
+                        && ASYNC_SESSION_METHODS.contains(&method)
+                })
+            }
+            OrmKind::Django | OrmKind::Peewee | OrmKind::Mongo | OrmKind::Redis => false,
+        }
+    }
 }
 
 /// Information about the outer query that provides the collection being iterated
@@ -74,6 +123,10 @@ pub enum OrmKind {
     SqlModel,
     /// Peewee ORM
     Peewee,
+    /// PyMongo / Motor MongoDB drivers
+    Mongo,
+    /// redis-py client
+    Redis,
     /// Unknown/generic ORM
     Unknown,
 }
@@ -86,6 +139,8 @@ impl OrmKind {
             OrmKind::Tortoise => "Tortoise ORM",
             OrmKind::SqlModel => "SQLModel",
             OrmKind::Peewee => "Peewee",
+            OrmKind::Mongo => "MongoDB",
+            OrmKind::Redis => "Redis",
             OrmKind::Unknown => "Unknown ORM",
         }
     }
@@ -104,6 +159,16 @@ pub enum QueryType {
     Delete,
     /// Relationship access (lazy loading)
     RelationshipAccess,
+    /// Raw SQL executed directly (cursor.execute, etc.)
+    RawSql,
+    /// MongoDB document query (find, find_one)
+    Find,
+    /// MongoDB aggregation pipeline
+    Aggregate,
+    /// Redis read (get, hget, ...)
+    CacheGet,
+    /// Redis write (set, hset, ...)
+    CacheSet,
     /// Unknown query type
     Unknown,
 }
@@ -124,23 +189,26 @@ pub struct NPlusOnePattern {
 /// Context for tracking loop information during traversal
 #[derive(Debug, Clone)]
 struct LoopContext {
-    /// Whether we're inside a loop
-    in_loop: bool,
-    /// Whether we're inside a comprehension
-    in_comprehension: bool,
+    /// Whether we're inside a loop or comprehension
+    iteration: IterationContext,
+    /// Whether we're inside a transaction block
+    in_transaction: bool,
     /// The loop variable name
     loop_variable: Option<String>,
     /// The outer query that provides the collection being iterated
     outer_query: Option<OuterQueryInfo>,
+    /// Name of the innermost enclosing function
+    enclosing_function: Option<String>,
 }
 
 impl Default for LoopContext {
     fn default() -> Self {
         Self {
-            in_loop: false,
-            in_comprehension: false,
+            iteration: IterationContext::default(),
+            in_transaction: false,
             loop_variable: None,
             outer_query: None,
+            enclosing_function: None,
         }
     }
 }
@@ -263,11 +331,10 @@ fn walk_for_orm_queries(
 ) {
     // Update context based on current node
     let mut new_ctx = ctx.clone();
+    new_ctx.iteration = ctx.iteration.enter(&node);
 
     // Handle for loops - extract loop variable and try to find outer query
     if node.kind() == "for_statement" {
-        new_ctx.in_loop = true;
-
         // Extract loop variable
         if let Some(left) = node.child_by_field_name("left") {
             new_ctx.loop_variable = Some(parsed.text_for_node(&left));
@@ -310,7 +377,7 @@ fn walk_for_orm_queries(
                         let query_text = parsed.text_for_node(&right);
                         new_ctx.outer_query = Some(OuterQueryInfo {
                             line: location.range.start_line + 1,
-                            column: location.range.start_col + 1,
+                            column: location.range.start_char_col + 1,
                             start_byte: right.start_byte(),
                             end_byte: right.end_byte(),
                             query_text,
@@ -324,8 +391,16 @@ fn walk_for_orm_queries(
         }
     }
 
-    if node.kind() == "while_statement" {
-        new_ctx.in_loop = true;
+    if node.kind() == "function_definition"
+        && let Some(name) = node.child_by_field_name("name")
+    {
+        new_ctx.enclosing_function = Some(parsed.text_for_node(&name));
+    }
+
+    // Handle transaction blocks - `with session.begin():`, `with transaction.atomic():`
+    // and functions decorated with `@transaction.atomic`
+    if is_transaction_block(node, parsed) {
+        new_ctx.in_transaction = true;
     }
 
     if matches!(
@@ -335,8 +410,6 @@ fn walk_for_orm_queries(
             | "set_comprehension"
             | "generator_expression"
     ) {
-        new_ctx.in_comprehension = true;
-
         // Try to extract the loop variable from comprehension
         // Comprehensions have a "for_in_clause" child
         let child_count = node.child_count();
@@ -400,6 +473,38 @@ fn walk_for_orm_queries(
     }
 }
 
+/// Check whether a node opens a transaction scope for its children.
+fn is_transaction_block(node: tree_sitter::Node, parsed: &ParsedFile) -> bool {
+    match node.kind() {
+        "with_statement" => {
+            let mut cursor = node.walk();
+            node.children(&mut cursor)
+                .filter(|child| child.kind() == "with_clause")
+                .any(|clause| is_transaction_manager(&parsed.text_for_node(&clause)))
+        }
+        "decorated_definition" => {
+            let mut cursor = node.walk();
+            node.children(&mut cursor)
+                .filter(|child| child.kind() == "decorator")
+                .any(|decorator| {
+                    let text = parsed.text_for_node(&decorator);
+                    let name = text.trim_start_matches('@').trim();
+                    name.starts_with("transaction.atomic") || name.starts_with("atomic")
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Check whether a `with` clause enters a database transaction.
+fn is_transaction_manager(clause_text: &str) -> bool {
+    clause_text.contains("transaction.atomic")
+        || clause_text.contains(".begin(")
+        || clause_text.contains(".begin_nested(")
+        || clause_text.contains(".transaction")
+        || clause_text.contains("atomic()")
+}
+
 fn analyze_orm_call(
     node: tree_sitter::Node,
     parsed: &ParsedFile,
@@ -423,6 +528,12 @@ fn analyze_orm_call(
     // Extract model name if possible
     let model_name = extract_model_name(&callee);
 
+    // Check whether the SQL string is assembled from non-literal values
+    let has_string_interpolation = node
+        .child_by_field_name("arguments")
+        .and_then(|args| args.named_child(0))
+        .is_some_and(|arg| is_interpolated_sql(arg, parsed));
+
     // Get the full query text
     let query_text = Some(parsed.text_for_node(&node));
 
@@ -431,28 +542,45 @@ fn analyze_orm_call(
     Some(OrmQueryCall {
         orm_kind,
         query_type,
-        in_loop: ctx.in_loop,
-        in_comprehension: ctx.in_comprehension,
+        in_loop: ctx.iteration.in_loop,
+        in_comprehension: ctx.iteration.in_comprehension,
+        in_transaction: ctx.in_transaction,
         has_eager_loading,
         model_name,
+        has_string_interpolation,
         line: location.range.start_line + 1,
-        column: location.range.start_col + 1,
+        column: location.range.start_char_col + 1,
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
         location,
         query_text,
         loop_variable: None,
         outer_query: None,
+        enclosing_function: ctx.enclosing_function.clone(),
+        has_await: is_awaited(node),
     })
 }
 
+/// Whether a call is directly awaited, looking through parentheses.
+fn is_awaited(node: tree_sitter::Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "await" => return true,
+            "parenthesized_expression" => current = parent.parent(),
+            _ => return false,
+        }
+    }
+    false
+}
+
 fn analyze_attribute_access(
     node: tree_sitter::Node,
     parsed: &ParsedFile,
     ctx: &LoopContext,
 ) -> Option<OrmQueryCall> {
     // Only flag attribute access in loops/comprehensions as potential lazy loading
-    if !ctx.in_loop && !ctx.in_comprehension {
+    if !ctx.iteration.is_iterating() {
         return None;
     }
 
@@ -473,22 +601,40 @@ fn analyze_attribute_access(
     Some(OrmQueryCall {
         orm_kind: OrmKind::Unknown,
         query_type: QueryType::RelationshipAccess,
-        in_loop: ctx.in_loop,
-        in_comprehension: ctx.in_comprehension,
+        in_loop: ctx.iteration.in_loop,
+        in_comprehension: ctx.iteration.in_comprehension,
+        in_transaction: ctx.in_transaction,
         has_eager_loading: false,
         model_name: None,
+        has_string_interpolation: false,
         line: location.range.start_line + 1,
-        column: location.range.start_col + 1,
+        column: location.range.start_char_col + 1,
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
         location,
         query_text: Some(parsed.text_for_node(&node)),
         loop_variable: None,
         outer_query: None,
+        enclosing_function: ctx.enclosing_function.clone(),
+        has_await: false,
     })
 }
 
 fn detect_orm_pattern(callee: &str, _args_text: &str) -> Option<(OrmKind, QueryType)> {
+    // DB-API raw SQL (sqlite3, psycopg2, mysqlclient, etc.)
+    if callee.ends_with("cursor.execute")
+        || callee.ends_with("cursor.executemany")
+        || callee.ends_with("cur.execute")
+        || callee.ends_with("cur.executemany")
+    {
+        return Some((OrmKind::Unknown, QueryType::RawSql));
+    }
+
+    // NoSQL clients, recognized by the receiver name (`redis.get`, `db.users.find`)
+    if let Some(found) = detect_nosql_pattern(callee) {
+        return Some(found);
+    }
+
     // SQLAlchemy patterns
     if callee.contains("session.query") || callee.contains("Session.query") {
         return Some((OrmKind::SqlAlchemy, QueryType::Select));
@@ -569,6 +715,82 @@ fn detect_orm_pattern(callee: &str, _args_text: &str) -> Option<(OrmKind, QueryT
     None
 }
 
+fn detect_nosql_pattern(callee: &str) -> Option<(OrmKind, QueryType)> {
+    let (receiver, method) = callee.rsplit_once('.')?;
+
+    if is_redis_receiver(receiver) {
+        let query_type = match DbOperationType::from_cache_method(method)? {
+            DbOperationType::CacheGet => QueryType::CacheGet,
+            DbOperationType::CacheSet => QueryType::CacheSet,
+            _ => QueryType::Delete,
+        };
+        return Some((OrmKind::Redis, query_type));
+    }
+
+    if is_mongo_collection_receiver(receiver) {
+        let query_type = match DbOperationType::from_document_method(method)? {
+            DbOperationType::Find => QueryType::Find,
+            DbOperationType::Aggregate => QueryType::Aggregate,
+            DbOperationType::Insert => QueryType::Insert,
+            DbOperationType::Update => QueryType::Update,
+            _ => QueryType::Delete,
+        };
+        return Some((OrmKind::Mongo, query_type));
+    }
+
+    None
+}
+
+/// Check whether a SQL argument is built from non-literal values.
+///
+/// Flags f-strings with placeholders, `%` formatting, `+` concatenation with a
+/// non-literal operand and `.format()` calls. `text(...)` wrappers are looked through.
+fn is_interpolated_sql(node: tree_sitter::Node, parsed: &ParsedFile) -> bool {
+    match node.kind() {
+        "string" => {
+            let mut cursor = node.walk();
+            node.children(&mut cursor)
+                .any(|child| child.kind() == "interpolation")
+        }
+        "binary_operator" => {
+            let operator = node
+                .child_by_field_name("operator")
+                .map(|op| parsed.text_for_node(&op))
+                .unwrap_or_default();
+            let left = node.child_by_field_name("left");
+            let right = node.child_by_field_name("right");
+            match operator.as_str() {
+                "%" => true,
+                "+" => [left, right].into_iter().flatten().any(|operand| {
+                    !matches!(operand.kind(), "string" | "concatenated_string")
+                        || is_interpolated_sql(operand, parsed)
+                }),
+                _ => false,
+            }
+        }
+        "call" => {
+            let Some(func) = node.child_by_field_name("function") else {
+                return false;
+            };
+            let callee = parsed.text_for_node(&func);
+            if callee.ends_with(".format") {
+                return true;
+            }
+            if callee == "text" || callee.ends_with(".text") {
+                return node
+                    .child_by_field_name("arguments")
+                    .and_then(|args| args.named_child(0))
+                    .is_some_and(|arg| is_interpolated_sql(arg, parsed));
+            }
+            false
+        }
+        "parenthesized_expression" => node
+            .named_child(0)
+            .is_some_and(|inner| is_interpolated_sql(inner, parsed)),
+        _ => false,
+    }
+}
+
 fn check_eager_loading(callee: &str, args_text: &str) -> bool {
     // SQLAlchemy eager loading
     if callee.contains("joinedload") || args_text.contains("joinedload") {
@@ -666,6 +888,51 @@ mod tests {
         summarize_orm_queries(&parsed)
     }
 
+    #[test]
+    fn captures_enclosing_function_name() {
+        let src = r#"
+def list_users(request):
+    for user in User.objects.all():
+        print(user.profile)
+
+User.objects.count()
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        let all = queries
+            .iter()
+            .find(|q| q.query_text.as_deref() == Some("User.objects.all()"))
+            .expect("query inside list_users");
+        assert_eq!(all.enclosing_function.as_deref(), Some("list_users"));
+
+        let count = queries
+            .iter()
+            .find(|q| q.query_text.as_deref() == Some("User.objects.count()"))
+            .expect("module-level query");
+        assert_eq!(count.enclosing_function, None);
+    }
+
+    #[test]
+    fn records_await_on_async_session_calls() {
+        let src = r#"
+async def load(session):
+    result = await session.execute(select(User))
+    session.execute(select(Order))
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        let awaited = queries
+            .iter()
+            .find(|q| q.query_text.as_deref() == Some("session.execute(select(User))"))
+            .expect("awaited query");
+        assert!(awaited.has_await);
+        assert!(awaited.is_awaitable());
+
+        let bare = queries
+            .iter()
+            .find(|q| q.query_text.as_deref() == Some("session.execute(select(Order))"))
+            .expect("un-awaited query");
+        assert!(!bare.has_await);
+    }
+
     #[test]
     fn detects_sqlalchemy_query_in_loop() {
         let src = r#"
@@ -760,4 +1027,91 @@ for user in users:
         assert_eq!(OrmKind::Peewee.as_str(), "Peewee");
         assert_eq!(OrmKind::Unknown.as_str(), "Unknown ORM");
     }
+
+    #[test]
+    fn flags_fstring_raw_sql_as_interpolated() {
+        let src = r#"
+def get_user(cursor, x):
+    cursor.execute(f"SELECT * FROM users WHERE id={x}")
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].query_type, QueryType::RawSql);
+        assert!(queries[0].has_string_interpolation);
+    }
+
+    #[test]
+    fn flags_percent_and_concat_raw_sql_as_interpolated() {
+        let src = r#"
+def find(cursor, name, table):
+    cursor.execute("SELECT * FROM users WHERE name = '%s'" % name)
+    cursor.execute("SELECT * FROM " + table)
+    session.execute(text("DELETE FROM {}".format(table)))
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        assert_eq!(queries.len(), 3);
+        assert!(queries.iter().all(|q| q.has_string_interpolation));
+    }
+
+    #[test]
+    fn parameterized_raw_sql_not_interpolated() {
+        let src = r#"
+def get_user(cursor, x):
+    cursor.execute("SELECT * FROM users WHERE id = %s", (x,))
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].query_type, QueryType::RawSql);
+        assert!(!queries[0].has_string_interpolation);
+    }
+
+    #[test]
+    fn detects_query_inside_django_atomic_block() {
+        let src = r#"
+def transfer():
+    with transaction.atomic():
+        Account.objects.filter(id=1).update(balance=0)
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        assert!(!queries.is_empty());
+        assert!(queries.iter().all(|q| q.in_transaction));
+    }
+
+    #[test]
+    fn detects_query_inside_sqlalchemy_begin_block() {
+        let src = r#"
+async def create_user():
+    async with session.begin():
+        session.add(User(name="a"))
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        assert_eq!(queries.len(), 1);
+        assert!(queries[0].in_transaction);
+    }
+
+    #[test]
+    fn detects_query_in_atomic_decorated_function() {
+        let src = r#"
+@transaction.atomic
+def transfer():
+    Account.objects.create(balance=0)
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        assert_eq!(queries.len(), 1);
+        assert!(queries[0].in_transaction);
+    }
+
+    #[test]
+    fn query_outside_transaction_block_not_in_transaction() {
+        let src = r#"
+def transfer():
+    with transaction.atomic():
+        Account.objects.create(balance=0)
+    Account.objects.get(id=1)
+"#;
+        let queries = parse_and_get_orm_queries(src);
+        assert_eq!(queries.len(), 2);
+        assert!(queries[0].in_transaction);
+        assert!(!queries[1].in_transaction);
+    }
 }
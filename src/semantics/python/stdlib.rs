@@ -0,0 +1,358 @@
+//! Python standard library module list used to classify imports.
+//!
+//! The baked-in list covers Python 3; modules that appeared or disappeared in
+//! a given release are tagged so the set can be narrowed to one interpreter
+//! version. Callers that know better (vendored interpreters, custom builds)
+//! can supply their own set instead.
+
+use std::collections::HashSet;
+
+/// A Python `(major, minor)` version, e.g. `(3, 12)`.
+pub type PythonVersion = (u32, u32);
+
+/// Version assumed when the caller does not specify one.
+pub const DEFAULT_PYTHON_VERSION: PythonVersion = (3, 12);
+
+/// Top-level modules shipped with any Python 3 release (for categorizing imports).
+///
+/// This is the union across versions; [`STDLIB_VERSION_CHANGES`] records the
+/// modules that were added or removed along the way.
+pub(crate) const STDLIB_MODULES: &[&str] = &[
+    // Frequently used in production code
+    "abc",
+    "argparse",
+    "asyncio",
+    "base64",
+    "bisect",
+    "builtins",
+    "calendar",
+    "codecs",
+    "collections",
+    "concurrent",
+    "contextlib",
+    "copy",
+    "csv",
+    "dataclasses",
+    "datetime",
+    "decimal",
+    "difflib",
+    "dis",
+    "email",
+    "enum",
+    "errno",
+    "faulthandler",
+    "functools",
+    "gc",
+    "getpass",
+    "glob",
+    "gzip",
+    "hashlib",
+    "heapq",
+    "hmac",
+    "html",
+    "http",
+    "importlib",
+    "inspect",
+    "io",
+    "ipaddress",
+    "itertools",
+    "json",
+    "locale",
+    "logging",
+    "math",
+    "mimetypes",
+    "multiprocessing",
+    "numbers",
+    "operator",
+    "os",
+    "pathlib",
+    "pickle",
+    "platform",
+    "pprint",
+    "queue",
+    "random",
+    "re",
+    "secrets",
+    "select",
+    "shutil",
+    "signal",
+    "socket",
+    "sqlite3",
+    "ssl",
+    "stat",
+    "statistics",
+    "string",
+    "struct",
+    "subprocess",
+    "sys",
+    "tempfile",
+    "textwrap",
+    "threading",
+    "time",
+    "timeit",
+    "traceback",
+    "typing",
+    "unittest",
+    "urllib",
+    "uuid",
+    "warnings",
+    "weakref",
+    "xml",
+    "zipfile",
+    "zlib",
+    // Testing and debugging
+    "doctest",
+    "pdb",
+    "profile",
+    "trace",
+    "unittest",
+    // Less common but still stdlib
+    "aifc",
+    "array",
+    "ast",
+    "atexit",
+    "audioop",
+    "bdb",
+    "binascii",
+    "binhex",
+    "cgi",
+    "cgitb",
+    "chunk",
+    "cmath",
+    "cmd",
+    "code",
+    "codeop",
+    "colorsys",
+    "compileall",
+    "configparser",
+    "cProfile",
+    "crypt",
+    "ctypes",
+    "curses",
+    "dbm",
+    "filecmp",
+    "fileinput",
+    "fnmatch",
+    "formatter",
+    "fractions",
+    "ftplib",
+    "getopt",
+    "gettext",
+    "graphlib",
+    "grp",
+    "imaplib",
+    "imp",
+    "keyword",
+    "lib2to3",
+    "linecache",
+    "lzma",
+    "mailbox",
+    "mailcap",
+    "marshal",
+    "mmap",
+    "modulefinder",
+    "netrc",
+    "nis",
+    "nntplib",
+    "ntpath",
+    "optparse",
+    "ossaudiodev",
+    "parser",
+    "pickletools",
+    "pipes",
+    "pkgutil",
+    "poplib",
+    "posix",
+    "posixpath",
+    "pow",
+    "pstats",
+    "pty",
+    "pwd",
+    "py_compile",
+    "pyclbr",
+    "pydoc",
+    "quopri",
+    "readline",
+    "reprlib",
+    "resource",
+    "rlcompleter",
+    "runpy",
+    "sched",
+    "selectors",
+    "shelve",
+    "shlex",
+    "smtpd",
+    "smtplib",
+    "sndhdr",
+    "spwd",
+    "stringprep",
+    "sunau",
+    "symbol",
+    "symtable",
+    "sysconfig",
+    "syslog",
+    "tabnanny",
+    "tarfile",
+    "telnetlib",
+    "termios",
+    "test",
+    "token",
+    "tokenize",
+    "tomllib",
+    "tty",
+    "turtle",
+    "turtledemo",
+    "types",
+    "unicodedata",
+    "uu",
+    "venv",
+    "wave",
+    "webbrowser",
+    "winreg",
+    "winsound",
+    "wsgiref",
+    "xdrlib",
+    "xmlrpc",
+    "zipapp",
+    "zipimport",
+    // Interpreter internals and packaging
+    "__future__",
+    "_thread",
+    "asynchat",
+    "asyncore",
+    "contextvars",
+    "copyreg",
+    "distutils",
+    "encodings",
+    "ensurepip",
+    "fcntl",
+    "imghdr",
+    "msilib",
+    "msvcrt",
+    "plistlib",
+    "site",
+    "tkinter",
+    "tracemalloc",
+    "zoneinfo",
+];
+
+/// Stdlib modules whose availability depends on the Python version:
+/// `(module, added in, removed in)`.
+pub(crate) const STDLIB_VERSION_CHANGES: &[(&str, Option<PythonVersion>, Option<PythonVersion>)] =
+    &[
+        ("contextvars", Some((3, 7)), None),
+        ("dataclasses", Some((3, 7)), None),
+        ("graphlib", Some((3, 9)), None),
+        ("zoneinfo", Some((3, 9)), None),
+        ("tomllib", Some((3, 11)), None),
+        ("formatter", None, Some((3, 10))),
+        ("parser", None, Some((3, 10))),
+        ("symbol", None, Some((3, 10))),
+        ("binhex", None, Some((3, 11))),
+        ("asynchat", None, Some((3, 12))),
+        ("asyncore", None, Some((3, 12))),
+        ("distutils", None, Some((3, 12))),
+        ("imp", None, Some((3, 12))),
+        ("smtpd", None, Some((3, 12))),
+        ("aifc", None, Some((3, 13))),
+        ("audioop", None, Some((3, 13))),
+        ("cgi", None, Some((3, 13))),
+        ("cgitb", None, Some((3, 13))),
+        ("chunk", None, Some((3, 13))),
+        ("crypt", None, Some((3, 13))),
+        ("imghdr", None, Some((3, 13))),
+        ("lib2to3", None, Some((3, 13))),
+        ("mailcap", None, Some((3, 13))),
+        ("msilib", None, Some((3, 13))),
+        ("nis", None, Some((3, 13))),
+        ("nntplib", None, Some((3, 13))),
+        ("ossaudiodev", None, Some((3, 13))),
+        ("pipes", None, Some((3, 13))),
+        ("sndhdr", None, Some((3, 13))),
+        ("spwd", None, Some((3, 13))),
+        ("sunau", None, Some((3, 13))),
+        ("telnetlib", None, Some((3, 13))),
+        ("uu", None, Some((3, 13))),
+        ("xdrlib", None, Some((3, 13))),
+    ];
+
+/// Set of top-level standard library module names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonStdlib {
+    modules: HashSet<String>,
+}
+
+impl PythonStdlib {
+    /// The baked-in stdlib modules available in the given Python version.
+    pub fn for_version(version: PythonVersion) -> Self {
+        let modules = STDLIB_MODULES
+            .iter()
+            .filter(|name| {
+                STDLIB_VERSION_CHANGES
+                    .iter()
+                    .find(|(module, _, _)| module == *name)
+                    .is_none_or(|(_, added, removed)| {
+                        added.is_none_or(|v| version >= v) && removed.is_none_or(|v| version < v)
+                    })
+            })
+            .map(|name| name.to_string())
+            .collect();
+        Self { modules }
+    }
+
+    /// A caller-supplied set of top-level module names, replacing the baked-in list.
+    pub fn from_modules<I, S>(modules: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            modules: modules.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Check if a (possibly dotted) module belongs to the standard library.
+    ///
+    /// Only the top-level package is consulted, so `concurrent.futures` and
+    /// `email.mime.text` match `concurrent` and `email`.
+    pub fn contains(&self, module: &str) -> bool {
+        let top_level = module.split('.').next().unwrap_or(module);
+        self.modules.contains(top_level)
+    }
+}
+
+impl Default for PythonStdlib {
+    fn default() -> Self {
+        Self::for_version(DEFAULT_PYTHON_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_stdlib_modules_match_top_level() {
+        let stdlib = PythonStdlib::default();
+        assert!(stdlib.contains("concurrent.futures"));
+        assert!(stdlib.contains("email.mime.text"));
+        assert!(stdlib.contains("dataclasses"));
+        assert!(!stdlib.contains("requests"));
+        assert!(!stdlib.contains("concurrentx"));
+    }
+
+    #[test]
+    fn version_narrows_the_set() {
+        assert!(PythonStdlib::for_version((3, 11)).contains("distutils.core"));
+        assert!(!PythonStdlib::for_version((3, 12)).contains("distutils.core"));
+        assert!(!PythonStdlib::for_version((3, 10)).contains("tomllib"));
+        assert!(PythonStdlib::for_version((3, 11)).contains("tomllib"));
+    }
+
+    #[test]
+    fn caller_supplied_set_replaces_baked_in_list() {
+        let stdlib = PythonStdlib::from_modules(["os", "mycorp_runtime"]);
+        assert!(stdlib.contains("mycorp_runtime.config"));
+        assert!(!stdlib.contains("json"));
+    }
+}
@@ -0,0 +1,95 @@
+//! Identifier references collected from the syntax tree.
+//!
+//! Each language supplies the node kinds that name things and the statements
+//! that bring names into scope; the traversal itself is shared. The resulting
+//! set backs unused-import detection.
+
+use std::collections::BTreeSet;
+
+use crate::parse::ast::ParsedFile;
+
+/// Node kinds a language contributes to reference collection.
+pub(crate) struct ReferenceRules {
+    /// Node kinds whose text is a referenced name
+    pub identifier_kinds: &'static [&'static str],
+    /// Import statements, whose names are declarations rather than uses
+    pub import_kinds: &'static [&'static str],
+}
+
+pub(crate) const PYTHON_RULES: ReferenceRules = ReferenceRules {
+    identifier_kinds: &["identifier"],
+    import_kinds: &[
+        "import_statement",
+        "import_from_statement",
+        "future_import_statement",
+    ],
+};
+
+pub(crate) const GO_RULES: ReferenceRules = ReferenceRules {
+    identifier_kinds: &["identifier", "package_identifier", "type_identifier"],
+    import_kinds: &["import_declaration", "package_clause"],
+};
+
+pub(crate) const RUST_RULES: ReferenceRules = ReferenceRules {
+    identifier_kinds: &["identifier", "type_identifier"],
+    import_kinds: &["use_declaration"],
+};
+
+pub(crate) const TYPESCRIPT_RULES: ReferenceRules = ReferenceRules {
+    identifier_kinds: &[
+        "identifier",
+        "type_identifier",
+        "shorthand_property_identifier",
+    ],
+    import_kinds: &["import_statement"],
+};
+
+pub(crate) const JAVA_RULES: ReferenceRules = ReferenceRules {
+    identifier_kinds: &["identifier", "type_identifier"],
+    import_kinds: &["import_declaration", "package_declaration"],
+};
+
+/// Every name referenced in the file outside its import statements.
+pub(crate) fn referenced_identifiers(
+    parsed: &ParsedFile,
+    rules: &ReferenceRules,
+) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut stack = vec![parsed.tree.root_node()];
+
+    while let Some(node) = stack.pop() {
+        if rules.import_kinds.contains(&node.kind()) {
+            continue;
+        }
+        if rules.identifier_kinds.contains(&node.kind()) {
+            names.insert(parsed.text_for_node(&node));
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ast::FileId;
+    use crate::parse::python::parse_python_file;
+    use crate::types::context::{Language, SourceFile};
+
+    #[test]
+    fn skips_names_inside_import_statements() {
+        let sf = SourceFile {
+            path: "a.py".to_string(),
+            language: Language::Python,
+            content: "import os\nfrom json import dumps\n\nprint(dumps({}))\n".to_string(),
+        };
+        let parsed = parse_python_file(FileId(1), &sf).unwrap();
+        let names = referenced_identifiers(&parsed, &PYTHON_RULES);
+        assert!(names.contains("dumps"));
+        assert!(names.contains("print"));
+        assert!(!names.contains("os"));
+        assert!(!names.contains("json"));
+    }
+}
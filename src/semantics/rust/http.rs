@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::parse::ast::{AstLocation, ParsedFile};
+use crate::semantics::common::Confidence;
+use crate::semantics::common::http::HttpDetectionConfig;
 
 /// Rust HTTP client library classification
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -58,6 +60,10 @@ pub struct HttpCallSite {
     /// Whether this call uses `.await`
     pub has_await: bool,
 
+    /// Low when the client was recognized only by a receiver named like `client`
+    #[serde(default)]
+    pub confidence: Confidence,
+
     /// Byte range in original source
     pub start_byte: usize,
     pub end_byte: usize,
@@ -65,9 +71,18 @@ pub struct HttpCallSite {
 
 /// Build a list of HTTP client calls in this Rust file.
 pub fn summarize_http_clients(file: &ParsedFile) -> Vec<HttpCallSite> {
+    summarize_http_clients_with(file, &HttpDetectionConfig::default())
+}
+
+/// Like [`summarize_http_clients`], also reporting calls through the custom
+/// clients registered in `config`.
+pub fn summarize_http_clients_with(
+    file: &ParsedFile,
+    config: &HttpDetectionConfig,
+) -> Vec<HttpCallSite> {
     let root = file.tree.root_node();
     let mut calls = Vec::new();
-    collect_http_calls(file, root, &mut calls, None, false, None);
+    collect_http_calls(file, root, &mut calls, config, None, false, None);
     calls
 }
 
@@ -82,6 +97,7 @@ fn collect_http_calls(
     file: &ParsedFile,
     node: tree_sitter::Node,
     out: &mut Vec<HttpCallSite>,
+    config: &HttpDetectionConfig,
     ctx: Option<HttpCallContext>,
     has_await: bool,
     _parent_fn: Option<String>,
@@ -103,22 +119,23 @@ fn collect_http_calls(
         new_ctx.current_function = name;
         new_ctx.in_async_fn = is_async;
 
-        walk_http_calls(file, node, out, &new_ctx, false);
+        walk_http_calls(file, node, out, config, &new_ctx, false);
         return;
     }
 
     if node.kind() == "await_expression" {
-        walk_http_calls(file, node, out, &ctx, true);
+        walk_http_calls(file, node, out, config, &ctx, true);
         return;
     }
 
-    walk_http_calls(file, node, out, &ctx, has_await);
+    walk_http_calls(file, node, out, config, &ctx, has_await);
 }
 
 fn walk_http_calls(
     file: &ParsedFile,
     node: tree_sitter::Node,
     out: &mut Vec<HttpCallSite>,
+    config: &HttpDetectionConfig,
     ctx: &HttpCallContext,
     has_await: bool,
 ) {
@@ -127,7 +144,7 @@ fn walk_http_calls(
     }
 
     if node.kind() == "call_expression" {
-        if let Some(call) = extract_http_call(file, &node, ctx, has_await) {
+        if let Some(call) = extract_http_call(file, &node, config, ctx, has_await) {
             out.push(call);
             return;
         }
@@ -147,7 +164,7 @@ fn walk_http_calls(
         let child_count = node.child_count();
         for i in 0..child_count {
             if let Some(child) = node.child(i) {
-                walk_http_calls(file, child, out, &new_ctx, false);
+                walk_http_calls(file, child, out, config, &new_ctx, false);
             }
         }
         return;
@@ -157,7 +174,7 @@ fn walk_http_calls(
         let child_count = node.child_count();
         for i in 0..child_count {
             if let Some(child) = node.child(i) {
-                walk_http_calls(file, child, out, ctx, true);
+                walk_http_calls(file, child, out, config, ctx, true);
             }
         }
         return;
@@ -166,7 +183,7 @@ fn walk_http_calls(
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
-            walk_http_calls(file, child, out, ctx, has_await);
+            walk_http_calls(file, child, out, config, ctx, has_await);
         }
     }
 }
@@ -175,6 +192,7 @@ fn walk_http_calls(
 fn extract_http_call(
     file: &ParsedFile,
     node: &tree_sitter::Node,
+    config: &HttpDetectionConfig,
     ctx: &HttpCallContext,
     has_await: bool,
 ) -> Option<HttpCallSite> {
@@ -190,13 +208,30 @@ fn extract_http_call(
         let location = file.location_for_node(node);
         let byte_range = node.byte_range();
 
-        let (http_method, client_kind) = if value_node.kind() == "call_expression" {
-            extract_http_method_and_client(file, &value_node)
-        } else {
-            let object = file.text_for_node(&value_node);
-            let client = detect_client_kind(&object, &callee_expr)?;
-            (method_name.clone(), client)
-        };
+        let object = file.text_for_node(&value_node);
+        // Registered custom clients take precedence over the built-in libraries
+        let (http_method, client_kind, confidence) =
+            if let Some(name) = config.match_call(&object, &method_name) {
+                (
+                    method_name.clone(),
+                    HttpClientKind::Other(name.to_string()),
+                    Confidence::High,
+                )
+            } else if value_node.kind() == "call_expression" {
+                let (http_method, client) = extract_http_method_and_client(file, &value_node);
+                (
+                    http_method,
+                    client,
+                    client_confidence(&object, &callee_expr),
+                )
+            } else {
+                let client = detect_client_kind(&object, &callee_expr)?;
+                (
+                    method_name.clone(),
+                    client,
+                    client_confidence(&object, &callee_expr),
+                )
+            };
 
         if http_method.is_empty() {
             return None;
@@ -215,6 +250,7 @@ fn extract_http_call(
             function_name: ctx.current_function.clone(),
             in_async_function: ctx.in_async_fn,
             has_await,
+            confidence,
             start_byte: byte_range.start,
             end_byte: byte_range.end,
         });
@@ -223,6 +259,28 @@ fn extract_http_call(
     if func_node.kind() == "path_expression" || func_node.kind() == "scoped_identifier" {
         let path_text = file.text_for_node(&func_node);
 
+        if let Some((name, method_name)) = config.match_callee(&path_text) {
+            let call_text = file.text_for_node(node);
+            let location = file.location_for_node(node);
+            let byte_range = node.byte_range();
+            let (has_timeout, timeout_value) = detect_timeout(&call_text);
+
+            return Some(HttpCallSite {
+                client_kind: HttpClientKind::Other(name.to_string()),
+                method_name: method_name.to_string(),
+                call_text,
+                has_timeout,
+                timeout_value,
+                location,
+                function_name: ctx.current_function.clone(),
+                in_async_function: ctx.in_async_fn,
+                has_await,
+                confidence: Confidence::High,
+                start_byte: byte_range.start,
+                end_byte: byte_range.end,
+            });
+        }
+
         if path_text.contains("reqwest::blocking::") {
             let method_name = extract_method_from_blocking_call(&path_text);
             let call_text = file.text_for_node(node);
@@ -240,6 +298,7 @@ fn extract_http_call(
                 function_name: ctx.current_function.clone(),
                 in_async_function: false,
                 has_await,
+                confidence: Confidence::High,
                 start_byte: byte_range.start,
                 end_byte: byte_range.end,
             });
@@ -268,6 +327,7 @@ fn extract_http_call(
                     function_name: ctx.current_function.clone(),
                     in_async_function: false,
                     has_await,
+                    confidence: Confidence::High,
                     start_byte: byte_range.start,
                     end_byte: byte_range.end,
                 });
@@ -379,6 +439,20 @@ fn detect_client_kind(object: &str, callee_expr: &str) -> Option<HttpClientKind>
     None
 }
 
+/// How certain a [`detect_client_kind`] match is: library paths and crate
+/// names are literal matches, anything else was matched on the receiver's name.
+fn client_confidence(object: &str, callee_expr: &str) -> Confidence {
+    const LIBRARIES: &[&str] = &["reqwest", "ureq", "hyper", "surf", "awc", "isahc"];
+    let explicit = LIBRARIES
+        .iter()
+        .any(|lib| object == *lib || callee_expr.contains(&format!("{lib}::")));
+    if explicit {
+        Confidence::High
+    } else {
+        Confidence::Low
+    }
+}
+
 /// Check if a string is an HTTP method name
 fn is_http_method(s: &str) -> bool {
     matches!(
@@ -16,9 +16,12 @@ pub use model::RustFileSemantics;
 use anyhow::Result;
 
 use crate::parse::ast::ParsedFile;
-use crate::semantics::common::CommonLocation;
+use crate::semantics::common::async_ops::AsyncRuntime;
 use crate::semantics::common::calls::FunctionCall;
 use crate::semantics::common::db::{DbLibrary, DbOperation, DbOperationType};
+use crate::semantics::common::http::HttpDetectionConfig;
+use crate::semantics::common::{CommonLocation, Confidence};
+use crate::semantics::metrics::{RUST_RULES, cyclomatic_complexity, max_nesting_depth};
 
 /// Build the semantic model for a single Rust file.
 ///
@@ -30,7 +33,7 @@ pub fn build_rust_semantics(parsed: &ParsedFile) -> Result<RustFileSemantics> {
     analyze_error_handling(parsed, &mut sem);
     analyze_unsafe_patterns(parsed, &mut sem);
     analyze_frameworks(parsed, &mut sem);
-    analyze_http_calls(parsed, &mut sem);
+    analyze_http_calls(parsed, &mut sem, &HttpDetectionConfig::default());
     Ok(sem)
 }
 
@@ -42,9 +45,14 @@ fn analyze_frameworks(parsed: &ParsedFile, sem: &mut RustFileSemantics) {
     }
 }
 
-/// Analyze HTTP client calls (reqwest, ureq, hyper, etc.).
-fn analyze_http_calls(parsed: &ParsedFile, sem: &mut RustFileSemantics) {
-    let http_calls = http::summarize_http_clients(parsed);
+/// Analyze HTTP client calls (reqwest, ureq, hyper, etc.), including calls
+/// through the custom clients registered in `config`.
+pub(crate) fn analyze_http_calls(
+    parsed: &ParsedFile,
+    sem: &mut RustFileSemantics,
+    config: &HttpDetectionConfig,
+) {
+    let http_calls = http::summarize_http_clients_with(parsed, config);
     sem.http_calls = http_calls
         .into_iter()
         .map(|call_site| convert_http_call_site(call_site, parsed))
@@ -91,11 +99,14 @@ fn convert_http_call_site(
     let location = CommonLocation {
         file_id: site.location.file_id,
         line: site.location.range.start_line + 1,
-        column: site.location.range.start_col + 1,
+        column: site.location.range.start_char_col + 1,
         start_byte: site.start_byte,
         end_byte: site.end_byte,
     };
 
+    let (has_auth_header, custom_headers) =
+        crate::semantics::common::http::scan_headers(&site.call_text);
+
     crate::semantics::common::http::HttpCall {
         library,
         method,
@@ -108,6 +119,10 @@ fn convert_http_call_site(
         enclosing_function: site.function_name,
         in_async_context: site.in_async_function,
         in_loop: false,
+        in_comprehension: false,
+        has_auth_header,
+        custom_headers,
+        confidence: site.confidence,
         start_byte: site.start_byte,
         end_byte: site.end_byte,
     }
@@ -163,6 +178,9 @@ fn walk_nodes(
                 if use_stmt.path.contains("async_std") {
                     sem.async_info.uses_async_std = true;
                 }
+                if sem.async_info.runtime.is_none() {
+                    sem.async_info.runtime = runtime_from_use_path(&use_stmt.path);
+                }
                 sem.uses.push(use_stmt);
             }
         }
@@ -174,6 +192,15 @@ fn walk_nodes(
                     if func.is_async {
                         sem.async_info.async_fn_count += 1;
                     }
+                    if let Some(runtime) = func
+                        .attributes
+                        .iter()
+                        .find_map(|a| runtime_from_attribute(a))
+                    {
+                        sem.async_info.uses_tokio |= runtime == AsyncRuntime::Tokio;
+                        sem.async_info.uses_async_std |= runtime == AsyncRuntime::AsyncStd;
+                        sem.async_info.runtime = Some(runtime);
+                    }
                     sem.functions.push(func);
                 }
             }
@@ -207,6 +234,10 @@ fn walk_nodes(
             if let Some(m) = build_macro_invocation(parsed, &node, &new_ctx) {
                 sem.macro_invocations.push(m);
             }
+            // Check for compile-time checked queries (sqlx::query!, etc.)
+            if let Some(db_op) = detect_db_operation_from_macro(parsed, &node, &new_ctx) {
+                sem.db_operations.push(db_op);
+            }
         }
         "call_expression" => {
             if let Some(call) = build_call_site(parsed, &node, &new_ctx) {
@@ -268,6 +299,12 @@ fn detect_db_operation_from_call(
         s if s.contains("schema::") && s.contains(".delete") => {
             (DbLibrary::Diesel, DbOperationType::Delete)
         }
+        s if s.contains("::table.") && s.contains(".load") => {
+            (DbLibrary::Diesel, DbOperationType::Select)
+        }
+        "diesel::insert_into" => (DbLibrary::Diesel, DbOperationType::Insert),
+        "diesel::update" => (DbLibrary::Diesel, DbOperationType::Update),
+        "diesel::delete" => (DbLibrary::Diesel, DbOperationType::Delete),
         s if s.contains(".insert_into") => (DbLibrary::Diesel, DbOperationType::Insert),
         s if s.contains("select(") && s.contains("::") => {
             (DbLibrary::Diesel, DbOperationType::Select)
@@ -289,6 +326,9 @@ fn detect_db_operation_from_call(
         }
 
         // sqlx patterns
+        s if s.starts_with("sqlx::query") && !s.contains('(') => {
+            (DbLibrary::Sqlx, DbOperationType::RawSql)
+        }
         s if s.contains("query_as") && s.contains("PgPool") => {
             (DbLibrary::Sqlx, DbOperationType::Select)
         }
@@ -309,6 +349,16 @@ fn detect_db_operation_from_call(
 
     let text = parsed.text_for_node(node);
     let ast_location = parsed.location_for_node(node);
+    let model_name = if library == DbLibrary::Diesel {
+        extract_diesel_table(&text)
+    } else {
+        None
+    };
+    let confidence = if callee_expr.starts_with("diesel::") || callee_expr.starts_with("sqlx::") {
+        Confidence::High
+    } else {
+        Confidence::Medium
+    };
 
     Some(DbOperation {
         library,
@@ -319,13 +369,67 @@ fn detect_db_operation_from_call(
         eager_loading: None,
         in_loop: ctx.in_loop,
         in_iteration: false,
-        model_name: None,
+        model_name,
+        has_string_interpolation: is_interpolated_sql(parsed, node),
         relationship_field: None,
+        sql: None,
+        confidence,
         operation_text: text,
         location: CommonLocation {
             file_id: ast_location.file_id,
             line: ast_location.range.start_line + 1,
-            column: ast_location.range.start_col + 1,
+            column: ast_location.range.start_char_col + 1,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+        },
+        enclosing_function: ctx.current_function.clone(),
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+    })
+}
+
+/// Detect sqlx query macros (`sqlx::query!`, `query_as!`, `query_scalar!`).
+///
+/// The operation type is inferred from the SQL literal passed to the macro.
+fn detect_db_operation_from_macro(
+    parsed: &ParsedFile,
+    node: &tree_sitter::Node,
+    ctx: &TraversalContext,
+) -> Option<DbOperation> {
+    let macro_node = node.child_by_field_name("macro")?;
+    let name = parsed.text_for_node(&macro_node);
+    let query_macro = name.strip_prefix("sqlx::")?;
+    if !query_macro.starts_with("query") || query_macro.starts_with("query_file") {
+        return None;
+    }
+
+    let sql = first_string_literal(parsed, node);
+    let operation_type = sql
+        .as_deref()
+        .map(DbOperationType::from_sql)
+        .unwrap_or(DbOperationType::RawSql);
+    let ast_location = parsed.location_for_node(node);
+
+    Some(DbOperation {
+        library: DbLibrary::Sqlx,
+        operation_type,
+        has_timeout: false,
+        timeout_value: None,
+        in_transaction: false,
+        eager_loading: None,
+        in_loop: ctx.in_loop,
+        in_iteration: false,
+        model_name: None,
+        // Query macros only accept literals, so the SQL is never interpolated
+        has_string_interpolation: false,
+        relationship_field: None,
+        sql,
+        confidence: Confidence::High,
+        operation_text: parsed.text_for_node(node),
+        location: CommonLocation {
+            file_id: ast_location.file_id,
+            line: ast_location.range.start_line + 1,
+            column: ast_location.range.start_char_col + 1,
             start_byte: node.start_byte(),
             end_byte: node.end_byte(),
         },
@@ -335,6 +439,75 @@ fn detect_db_operation_from_call(
     })
 }
 
+/// Contents of the first string or raw string literal under `node`.
+fn first_string_literal(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<String> {
+    if matches!(node.kind(), "string_literal" | "raw_string_literal") {
+        let text = parsed.text_for_node(node);
+        let inner = text
+            .trim_start_matches('r')
+            .trim_matches('#')
+            .strip_prefix('"')?
+            .strip_suffix('"')?;
+        return Some(inner.to_string());
+    }
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .find_map(|child| first_string_literal(parsed, &child))
+}
+
+/// Extract the table a Diesel query operates on, from `users::table`
+/// or `schema::users::...` paths.
+fn extract_diesel_table(call_text: &str) -> Option<String> {
+    let segment_before = |end: usize| -> Option<String> {
+        let name: String = call_text[..end]
+            .chars()
+            .rev()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect();
+        (!name.is_empty()).then_some(name)
+    };
+
+    let is_table_path = |idx: usize| {
+        !call_text[idx + "::table".len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+    };
+    if let Some(table) = call_text
+        .match_indices("::table")
+        .filter(|(idx, _)| is_table_path(*idx))
+        .find_map(|(idx, _)| segment_before(idx))
+    {
+        return Some(table);
+    }
+
+    let start = call_text.find("schema::")? + "schema::".len();
+    let table: String = call_text[start..]
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    (!table.is_empty()).then_some(table)
+}
+
+/// Check whether a database call passes SQL built with `format!`.
+fn is_interpolated_sql(parsed: &ParsedFile, node: &tree_sitter::Node) -> bool {
+    fn contains_format_macro(parsed: &ParsedFile, node: tree_sitter::Node) -> bool {
+        if node.kind() == "macro_invocation"
+            && node
+                .child_by_field_name("macro")
+                .is_some_and(|m| parsed.text_for_node(&m) == "format")
+        {
+            return true;
+        }
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .any(|child| contains_format_macro(parsed, child))
+    }
+
+    node.child_by_field_name("arguments")
+        .is_some_and(|args| contains_format_macro(parsed, args))
+}
+
 /// Update the traversal context based on the current node.
 fn update_context(
     node: &tree_sitter::Node,
@@ -485,10 +658,40 @@ fn build_use(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<model::Rus
         is_glob,
         is_pub,
         items: extract_use_items(parsed, node),
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        raw_text: text,
         location: parsed.location_for_node(node),
     })
 }
 
+/// Async runtime a `use` path brings in, e.g. `smol::Timer` or `futures::executor::block_on`.
+fn runtime_from_use_path(path: &str) -> Option<AsyncRuntime> {
+    let root = path.trim_start_matches("::").split("::").next()?;
+    match root {
+        "tokio" => Some(AsyncRuntime::Tokio),
+        "async_std" => Some(AsyncRuntime::AsyncStd),
+        "smol" => Some(AsyncRuntime::Smol),
+        "glommio" => Some(AsyncRuntime::Glommio),
+        "futures" if path.contains("executor") => Some(AsyncRuntime::FuturesExecutor),
+        _ => None,
+    }
+}
+
+/// Async runtime an entry-point attribute starts, e.g. `#[tokio::main(flavor = "current_thread")]`.
+fn runtime_from_attribute(attribute: &str) -> Option<AsyncRuntime> {
+    let name = attribute
+        .trim_start_matches("#[")
+        .split(['(', ']'])
+        .next()?
+        .trim();
+    match name {
+        "tokio::main" => Some(AsyncRuntime::Tokio),
+        "async_std::main" => Some(AsyncRuntime::AsyncStd),
+        _ => None,
+    }
+}
+
 /// Extract the path from a use declaration.
 fn extract_use_path(parsed: &ParsedFile, node: &tree_sitter::Node) -> String {
     for i in 0..node.child_count() {
@@ -537,38 +740,18 @@ fn extract_use_items(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<Strin
     items
 }
 
-/// Extract generics parameters from a node.
+/// Extract generic parameters from a node's `<...>` list, bounds included.
 fn extract_generics(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<String> {
-    let text = parsed.text_for_node(node);
-
-    if let Some(start) = text.find('<') {
-        let after = &text[start + 1..];
-        let mut depth = 0;
-        let mut end = 0;
-        for (i, c) in after.char_indices() {
-            match c {
-                '<' => depth += 1,
-                '>' => {
-                    if depth == 0 {
-                        end = i;
-                        break;
-                    }
-                    depth -= 1;
-                }
-                _ => {}
-            }
-        }
-        if end > 0 {
-            let generics_text = &after[..end];
-            return generics_text
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
-        }
-    }
+    let Some(type_params) = node.child_by_field_name("type_parameters") else {
+        return Vec::new();
+    };
 
-    Vec::new()
+    let mut cursor = type_params.walk();
+    type_params
+        .named_children(&mut cursor)
+        .filter(|child| !child.kind().contains("comment"))
+        .map(|child| parsed.text_for_node(&child))
+        .collect()
 }
 
 /// Extract attributes from preceding attribute items.
@@ -649,6 +832,8 @@ fn build_function(
         location: parsed.location_for_node(node),
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &RUST_RULES),
+        max_nesting_depth: max_nesting_depth(node, &RUST_RULES),
     })
 }
 
@@ -678,6 +863,12 @@ fn extract_return_type(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<
     None
 }
 
+/// Whether `node` has a direct `mut` child (`mut x` binding, `&mut T` type).
+fn has_mutable_specifier(node: &tree_sitter::Node) -> bool {
+    node.children(&mut node.walk())
+        .any(|c| c.kind() == "mutable_specifier")
+}
+
 /// Extract parameters from function.
 fn extract_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<model::RustParam> {
     let mut params = Vec::new();
@@ -685,29 +876,36 @@ fn extract_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<model::R
     if let Some(params_node) = node.child_by_field_name("parameters") {
         for i in 0..params_node.child_count() {
             if let Some(child) = params_node.child(i) {
-                if child.kind() == "parameter" || child.kind() == "self_parameter" {
-                    let text = parsed.text_for_node(&child);
-                    let is_self = text.contains("self");
-                    let is_mut = text.contains("mut ");
-                    let is_ref = text.contains('&');
-
-                    // Extract name and type
-                    let (name, param_type) = if is_self {
-                        ("self".to_string(), text.clone())
-                    } else {
-                        let parts: Vec<&str> = text.splitn(2, ':').collect();
-                        if parts.len() == 2 {
-                            (parts[0].trim().to_string(), parts[1].trim().to_string())
-                        } else {
-                            (text.clone(), String::new())
-                        }
-                    };
+                if child.kind() == "self_parameter" {
+                    // `&`, a lifetime and `mut` are anonymous children before `self`
+                    let kinds: Vec<&str> = child
+                        .children(&mut child.walk())
+                        .map(|c| c.kind())
+                        .collect();
+                    params.push(model::RustParam {
+                        name: "self".to_string(),
+                        param_type: parsed.text_for_node(&child),
+                        is_self: true,
+                        is_mut: kinds.contains(&"mutable_specifier"),
+                        is_ref: kinds.contains(&"&"),
+                    });
+                } else if child.kind() == "parameter" {
+                    let pattern = child.child_by_field_name("pattern");
+                    let param_type = child.child_by_field_name("type");
+                    let is_ref = param_type.is_some_and(|t| t.kind() == "reference_type");
+                    let is_mut_binding = has_mutable_specifier(&child);
+                    let is_mut_ref =
+                        param_type.is_some_and(|t| is_ref && has_mutable_specifier(&t));
 
                     params.push(model::RustParam {
-                        name,
-                        param_type,
-                        is_self,
-                        is_mut,
+                        name: pattern
+                            .map(|p| parsed.text_for_node(&p))
+                            .unwrap_or_else(|| parsed.text_for_node(&child)),
+                        param_type: param_type
+                            .map(|t| parsed.text_for_node(&t))
+                            .unwrap_or_default(),
+                        is_self: pattern.is_some_and(|p| p.kind() == "self"),
+                        is_mut: is_mut_binding || is_mut_ref,
                         is_ref,
                     });
                 }
@@ -1077,7 +1275,7 @@ fn build_call_site(
         location: CommonLocation {
             file_id: parsed.file_id,
             line: location.range.start_line + 1,
-            column: location.range.start_col + 1,
+            column: location.range.start_char_col + 1,
             start_byte: node.start_byte(),
             end_byte: node.end_byte(),
         },
@@ -1485,10 +1683,27 @@ fn detect_spawn_call(
             || (callee.ends_with("::spawn") && callee.contains("async_std"))
         {
             model::SpawnType::AsyncStdSpawn
+        } else if callee.ends_with("::spawn")
+            && (callee.starts_with("smol::") || callee.starts_with("glommio::"))
+        {
+            model::SpawnType::Other(callee.clone())
+        } else if callee.ends_with(".spawn")
+            && enclosing_function_text(parsed, node).is_some_and(|f| f.contains("JoinSet"))
+        {
+            model::SpawnType::TokioSpawn
         } else {
             return None;
         };
 
+    let func_text = enclosing_function_text(parsed, node).unwrap_or_default();
+    let semaphore_guarded = is_semaphore_guarded(parsed, node);
+    let is_bounded = semaphore_guarded
+        || func_text.contains("JoinSet")
+        || func_text.contains("FuturesUnordered");
+    let bound_limit = semaphore_guarded
+        .then(|| semaphore_permits(&func_text))
+        .flatten();
+
     // Check if handle is captured
     let parent = node.parent();
     let handle_captured = parent
@@ -1501,6 +1716,9 @@ fn detect_spawn_call(
         handle_captured,
         has_error_handling,
         spawned_expr: parsed.text_for_node(node),
+        in_loop: ctx.in_loop,
+        is_bounded,
+        bound_limit,
         function_name: ctx.current_function.clone(),
         location: parsed.location_for_node(node),
         start_byte: node.start_byte(),
@@ -1508,6 +1726,57 @@ fn detect_spawn_call(
     })
 }
 
+/// Check whether a spawn holds a semaphore permit: the spawned future
+/// acquires one, or a preceding statement in an enclosing block does.
+fn is_semaphore_guarded(parsed: &ParsedFile, spawn_node: &tree_sitter::Node) -> bool {
+    if parsed.text_for_node(spawn_node).contains(".acquire") {
+        return true;
+    }
+
+    let mut current = *spawn_node;
+    while let Some(parent) = current.parent() {
+        match parent.kind() {
+            "block" => {
+                let mut sibling = current.prev_named_sibling();
+                while let Some(stmt) = sibling {
+                    if parsed.text_for_node(&stmt).contains(".acquire") {
+                        return true;
+                    }
+                    sibling = stmt.prev_named_sibling();
+                }
+            }
+            "function_item" | "closure_expression" => return false,
+            _ => {}
+        }
+        current = parent;
+    }
+    false
+}
+
+/// Source text of the function item containing `node`.
+fn enclosing_function_text(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "function_item" {
+            return Some(parsed.text_for_node(&parent));
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+/// Permit count of the first `Semaphore::new(n)` with a literal `n`.
+fn semaphore_permits(text: &str) -> Option<u32> {
+    let start = text.find("Semaphore::new(")? + "Semaphore::new(".len();
+    text[start..]
+        .split(')')
+        .next()?
+        .trim()
+        .replace('_', "")
+        .parse()
+        .ok()
+}
+
 /// Analyze if JoinHandle is properly awaited or error is handled.
 fn analyze_join_handle_error_handling(
     parsed: &ParsedFile,
@@ -2248,6 +2517,51 @@ use tokio::sync::mpsc;
         assert!(sem.async_info.uses_tokio);
     }
 
+    #[test]
+    fn smol_spawns_report_smol_runtime() {
+        use crate::semantics::common::CommonSemantics;
+
+        let src = r#"
+use smol::Timer;
+
+fn main() {
+    smol::block_on(async {
+        let task = smol::spawn(async { Timer::after(std::time::Duration::from_secs(1)).await });
+        task.await;
+    });
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.async_info.runtime, Some(AsyncRuntime::Smol));
+
+        let ops = sem.async_operations();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].runtime, AsyncRuntime::Smol);
+    }
+
+    #[test]
+    fn detects_runtime_from_main_attribute_and_executor_use() {
+        let tokio = parse_and_build_semantics(
+            "#[tokio::main(flavor = \"current_thread\")]\nasync fn main() {}\n",
+        );
+        assert_eq!(tokio.async_info.runtime, Some(AsyncRuntime::Tokio));
+        assert!(tokio.async_info.uses_tokio);
+
+        let async_std = parse_and_build_semantics("#[async_std::main]\nasync fn main() {}\n");
+        assert_eq!(async_std.async_info.runtime, Some(AsyncRuntime::AsyncStd));
+
+        let executor = parse_and_build_semantics(
+            "use futures::executor::block_on;\n\nfn main() {\n    block_on(async {});\n}\n",
+        );
+        assert_eq!(
+            executor.async_info.runtime,
+            Some(AsyncRuntime::FuturesExecutor)
+        );
+
+        let glommio = parse_and_build_semantics("use glommio::LocalExecutor;\n");
+        assert_eq!(glommio.async_info.runtime, Some(AsyncRuntime::Glommio));
+    }
+
     #[test]
     fn collects_functions() {
         let src = r#"
@@ -2260,6 +2574,104 @@ pub fn public_fn() {}
         assert_eq!(sem.async_info.async_fn_count, 1);
     }
 
+    #[test]
+    fn flags_format_macro_raw_sql_as_interpolated() {
+        let src = r#"
+async fn get_user(pool: &PgPool, id: &str) {
+    sqlx::query(&format!("SELECT * FROM users WHERE id = {}", id))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let raw: Vec<_> = sem
+            .db_operations
+            .iter()
+            .filter(|op| op.operation_type.as_str() == "RAW_SQL")
+            .collect();
+        assert_eq!(raw.len(), 1);
+        assert!(raw[0].has_string_interpolation);
+    }
+
+    #[test]
+    fn parameterized_raw_sql_not_interpolated() {
+        let src = r#"
+async fn get_user(pool: &PgPool, id: i64) {
+    sqlx::query("SELECT * FROM users WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert!(
+            sem.db_operations
+                .iter()
+                .any(|op| op.operation_type.as_str() == "RAW_SQL")
+        );
+        assert!(
+            sem.db_operations
+                .iter()
+                .all(|op| !op.has_string_interpolation)
+        );
+    }
+
+    #[test]
+    fn sqlx_query_macro_yields_db_operation() {
+        let src = r##"
+async fn get_user(pool: &PgPool, id: i64) {
+    let user = sqlx::query!("SELECT id, name FROM users WHERE id = $1", id)
+        .fetch_one(pool)
+        .await;
+    sqlx::query_as!(User, r#"DELETE FROM users WHERE id = $1"#, id)
+        .execute(pool)
+        .await;
+}
+"##;
+        let sem = parse_and_build_semantics(src);
+        let select = sem
+            .db_operations
+            .iter()
+            .find(|op| op.operation_type.as_str() == "SELECT")
+            .expect("select from query!");
+        assert_eq!(select.library, DbLibrary::Sqlx);
+        assert_eq!(
+            select.sql.as_deref(),
+            Some("SELECT id, name FROM users WHERE id = $1")
+        );
+        assert!(!select.has_string_interpolation);
+
+        let delete = sem
+            .db_operations
+            .iter()
+            .find(|op| op.operation_type.as_str() == "DELETE")
+            .expect("delete from query_as!");
+        assert_eq!(
+            delete.sql.as_deref(),
+            Some("DELETE FROM users WHERE id = $1")
+        );
+    }
+
+    #[test]
+    fn extracts_diesel_table_name() {
+        let src = r#"
+fn load_users(conn: &mut PgConnection) {
+    let all = users::table.filter(users::active.eq(true)).load::<User>(conn);
+    diesel::insert_into(posts::table).values(&new_post).execute(conn);
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let tables: Vec<_> = sem
+            .db_operations
+            .iter()
+            .filter_map(|op| op.model_name.as_deref())
+            .collect();
+        assert!(tables.contains(&"users"));
+        assert!(tables.contains(&"posts"));
+    }
+
     #[test]
     fn ignores_inline_test_modules() {
         let src = r#"
@@ -4,21 +4,52 @@
 //! understanding of Rust source files, including functions, types,
 //! async operations, error handling patterns, and more.
 
+use std::collections::BTreeSet;
+
 use serde::{Deserialize, Serialize};
 
 use crate::parse::ast::{AstLocation, FileId, ParsedFile};
+use crate::semantics::common::async_ops::AsyncRuntime;
 use crate::semantics::common::calls::FunctionCall;
 use crate::semantics::common::db::DbOperation;
 use crate::semantics::common::http::HttpCall;
+use crate::semantics::references::{self, referenced_identifiers};
 use crate::semantics::rust::frameworks::RustFrameworkSummary;
 use crate::types::context::Language;
 
+/// Path prefixes of calls that block the current thread.
+///
+/// A call matches when its callee equals a pattern or continues it with
+/// `::` (so `std::fs` matches `std::fs::read_to_string`).
+pub const DEFAULT_BLOCKING_PATTERNS: &[&str] = &[
+    "std::thread::sleep",
+    "thread::sleep",
+    "std::fs",
+    "std::net::TcpStream::connect",
+    "std::io::stdin",
+    "reqwest::blocking",
+    "ureq",
+];
+
+/// Callees that move blocking work off the async executor.
+const BLOCKING_WRAPPERS: &[&str] = &["spawn_blocking", "block_in_place"];
+
+/// Callees that construct a new HTTP client (and with it a new connection pool).
+const CLIENT_CONSTRUCTORS: &[&str] = &["Client::new", "Client::builder", "ClientBuilder::new"];
+
 /// Semantic model for a single Rust file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RustFileSemantics {
     pub file_id: FileId,
     pub path: String,
     pub language: Language,
+    /// Number of lines in the source file
+    #[serde(default)]
+    pub line_count: usize,
+
+    /// Names referenced outside import statements (for unused-import detection)
+    #[serde(default)]
+    pub identifiers: BTreeSet<String>,
 
     /// Module path (from mod statements or file path)
     pub mod_path: Vec<String>,
@@ -96,6 +127,14 @@ pub struct RustUse {
     pub is_pub: bool,
     /// Items if grouped: use std::{io, fs}
     pub items: Vec<String>,
+    /// Byte range of the use declaration in the source
+    #[serde(default)]
+    pub start_byte: usize,
+    #[serde(default)]
+    pub end_byte: usize,
+    /// Source text of the use declaration
+    #[serde(default)]
+    pub raw_text: String,
     /// Location in source
     pub location: AstLocation,
 }
@@ -138,6 +177,10 @@ pub struct RustFunction {
     pub start_byte: usize,
     /// End byte for patching
     pub end_byte: usize,
+    /// Cyclomatic complexity (1 for straight-line code)
+    pub cyclomatic_complexity: u32,
+    /// Deepest nesting of control blocks (1 for straight-line code)
+    pub max_nesting_depth: u32,
 }
 
 /// Function parameter.
@@ -147,7 +190,9 @@ pub struct RustParam {
     pub param_type: String,
     /// Whether this is &self, &mut self, or self
     pub is_self: bool,
+    /// Whether this is a `mut` binding or a `&mut` borrow
     pub is_mut: bool,
+    /// Whether this is borrowed (`&T`, `&self`)
     pub is_ref: bool,
 }
 
@@ -259,6 +304,9 @@ pub struct AsyncInfo {
     pub uses_tokio: bool,
     /// Whether file uses async-std
     pub uses_async_std: bool,
+    /// Runtime named by a `#[...::main]` attribute, else by the first `use` of one
+    #[serde(default)]
+    pub runtime: Option<AsyncRuntime>,
 }
 
 /// Information about a spawn call.
@@ -271,6 +319,14 @@ pub struct SpawnCall {
     pub has_error_handling: bool,
     /// The spawned expression text
     pub spawned_expr: String,
+    /// Whether the spawn happens inside a loop
+    pub in_loop: bool,
+    /// Whether a semaphore permit is acquired around the spawn, or the task is
+    /// tracked by a `JoinSet`/`FuturesUnordered`
+    pub is_bounded: bool,
+    /// Permit count of a literal `Semaphore::new(n)` in the enclosing function
+    #[serde(default)]
+    pub bound_limit: Option<u32>,
     /// Enclosing function name
     pub function_name: Option<String>,
     pub location: AstLocation,
@@ -647,6 +703,8 @@ impl RustFileSemantics {
             file_id: parsed.file_id,
             path: parsed.path.clone(),
             language: Language::Rust,
+            line_count: parsed.source.lines().count(),
+            identifiers: referenced_identifiers(parsed, &references::RUST_RULES),
             mod_path: Vec::new(),
             uses: Vec::new(),
             functions: Vec::new(),
@@ -671,6 +729,113 @@ impl RustFileSemantics {
             http_calls: Vec::new(),
         }
     }
+
+    /// Calls to known-blocking functions made inside `async fn` bodies
+    /// without a `spawn_blocking`/`block_in_place` wrapper.
+    pub fn blocking_in_async(&self) -> Vec<FunctionCall> {
+        self.blocking_in_async_with(DEFAULT_BLOCKING_PATTERNS)
+    }
+
+    /// Like [`Self::blocking_in_async`], with a custom set of blocking patterns.
+    pub fn blocking_in_async_with(&self, patterns: &[&str]) -> Vec<FunctionCall> {
+        let async_fns: Vec<&RustFunction> = self
+            .functions
+            .iter()
+            .chain(self.impls.iter().flat_map(|i| i.methods.iter()))
+            .filter(|f| f.is_async)
+            .collect();
+
+        let wrappers: Vec<(usize, usize)> = self
+            .calls
+            .iter()
+            .filter(|c| {
+                BLOCKING_WRAPPERS
+                    .iter()
+                    .any(|w| c.function_call.callee_expr.ends_with(w))
+            })
+            .map(|c| {
+                (
+                    c.function_call.location.start_byte,
+                    c.function_call.location.end_byte,
+                )
+            })
+            .collect();
+
+        self.calls
+            .iter()
+            .map(|c| &c.function_call)
+            .filter(|call| {
+                let callee = call.callee_expr.as_str();
+                patterns.iter().any(|p| {
+                    callee == *p
+                        || callee
+                            .strip_prefix(p)
+                            .is_some_and(|rest| rest.starts_with("::"))
+                })
+            })
+            .filter(|call| {
+                let (start, end) = (call.location.start_byte, call.location.end_byte);
+                async_fns
+                    .iter()
+                    .any(|f| start >= f.start_byte && end <= f.end_byte)
+                    && !wrappers.iter().any(|&(ws, we)| start > ws && end <= we)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// HTTP calls in functions that construct their own client instead of
+    /// reusing a shared one, when that function is a route handler or builds
+    /// the client inside a loop.
+    ///
+    /// Each new `reqwest::Client` starts with an empty connection pool, so
+    /// per-request construction pays a fresh TCP/TLS handshake every time.
+    pub fn client_constructed_in_loop_or_handler(&self) -> Vec<HttpCall> {
+        let handlers: Vec<&str> = self
+            .rust_framework
+            .iter()
+            .flat_map(|fw| fw.routes.iter())
+            .map(|route| route.handler_name.as_str())
+            .collect();
+
+        let offending: Vec<&RustFunction> = self
+            .functions
+            .iter()
+            .chain(self.impls.iter().flat_map(|i| i.methods.iter()))
+            .filter(|f| {
+                let is_handler = handlers.contains(&f.name.as_str());
+                self.calls
+                    .iter()
+                    .filter(|c| is_client_constructor(&c.function_call.callee_expr))
+                    .filter(|c| {
+                        let location = &c.function_call.location;
+                        location.start_byte >= f.start_byte && location.end_byte <= f.end_byte
+                    })
+                    .any(|c| is_handler || c.in_loop)
+            })
+            .collect();
+
+        self.http_calls
+            .iter()
+            .filter(|call| {
+                offending
+                    .iter()
+                    .any(|f| call.start_byte >= f.start_byte && call.end_byte <= f.end_byte)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Whether `callee` constructs an HTTP client, e.g. `reqwest::Client::new`
+/// (but not `MyClient::new`).
+fn is_client_constructor(callee: &str) -> bool {
+    CLIENT_CONSTRUCTORS.iter().any(|ctor| {
+        callee == *ctor
+            || callee
+                .strip_suffix(ctor)
+                .is_some_and(|prefix| prefix.ends_with("::"))
+    })
 }
 
 #[cfg(test)]
@@ -688,6 +853,109 @@ mod tests {
         }
     }
 
+    fn build_full_semantics(content: &str) -> RustFileSemantics {
+        let sf = make_rust_source_file(content);
+        let parsed = parse_rust_file(FileId(1), &sf).unwrap();
+        crate::semantics::rust::build_rust_semantics(&parsed).unwrap()
+    }
+
+    #[test]
+    fn blocking_in_async_flags_thread_sleep() {
+        let sem = build_full_semantics(
+            r#"
+async fn poll() {
+    std::thread::sleep(Duration::from_secs(1));
+    tokio::time::sleep(Duration::from_secs(1)).await;
+}
+
+fn sync_wait() {
+    std::thread::sleep(Duration::from_secs(1));
+}
+"#,
+        );
+
+        let blocking = sem.blocking_in_async();
+        assert_eq!(blocking.len(), 1);
+        assert_eq!(blocking[0].callee_expr, "std::thread::sleep");
+        assert_eq!(blocking[0].caller_function, "poll");
+    }
+
+    #[test]
+    fn flags_client_built_per_request_in_handler() {
+        let sem = build_full_semantics(
+            r#"
+use axum::{Router, routing::get};
+
+async fn proxy() -> String {
+    let client = reqwest::Client::new();
+    client.get("https://api.example.com/data").send().await;
+    String::new()
+}
+
+async fn shared(state: AppState) -> String {
+    state.client.get("https://api.example.com/data").send().await;
+    String::new()
+}
+
+fn main() {
+    let app = Router::new()
+        .route("/proxy", get(proxy))
+        .route("/shared", get(shared));
+}
+"#,
+        );
+        assert!(!sem.http_calls.is_empty());
+        let flagged = sem.client_constructed_in_loop_or_handler();
+        assert!(!flagged.is_empty());
+        assert!(
+            flagged
+                .iter()
+                .all(|c| c.enclosing_function.as_deref() == Some("proxy"))
+        );
+        assert!(
+            sem.http_calls
+                .iter()
+                .any(|c| c.enclosing_function.as_deref() == Some("shared"))
+        );
+    }
+
+    #[test]
+    fn flags_client_built_inside_loop() {
+        let sem = build_full_semantics(
+            r#"
+async fn sync_all(ids: Vec<u64>) {
+    for id in ids {
+        let client = reqwest::Client::builder().build().unwrap();
+        client.post("https://api.example.com/sync").send().await;
+    }
+}
+"#,
+        );
+        assert!(!sem.client_constructed_in_loop_or_handler().is_empty());
+        assert!(!is_client_constructor("MyClient::new"));
+    }
+
+    #[test]
+    fn blocking_in_async_skips_spawn_blocking_and_custom_patterns() {
+        let sem = build_full_semantics(
+            r#"
+async fn load() {
+    let data = tokio::task::spawn_blocking(|| std::fs::read("a.bin")).await;
+    let text = std::fs::read_to_string("b.txt");
+    legacy::query("select 1");
+}
+"#,
+        );
+
+        let blocking = sem.blocking_in_async();
+        assert_eq!(blocking.len(), 1);
+        assert_eq!(blocking[0].callee_expr, "std::fs::read_to_string");
+
+        let custom = sem.blocking_in_async_with(&["legacy::query"]);
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[0].callee_expr, "legacy::query");
+    }
+
     #[test]
     fn from_parsed_creates_empty_semantics() {
         let sf = make_rust_source_file("fn main() {}");
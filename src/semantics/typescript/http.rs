@@ -1,8 +1,11 @@
 //! HTTP client detection for TypeScript/JavaScript code.
 
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 
 use crate::parse::ast::{AstLocation, ParsedFile};
+use crate::semantics::common::http::{HttpDetectionConfig, scan_headers};
 
 /// Represents an HTTP client call in TypeScript code.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +26,12 @@ pub struct HttpCallSite {
     pub function_name: Option<String>,
     /// Whether this call is in an async context
     pub in_async_context: bool,
+    /// Whether an `Authorization` header or `auth` config is passed
+    #[serde(default)]
+    pub has_auth_header: bool,
+    /// Header names set in the request config
+    #[serde(default)]
+    pub custom_headers: Vec<String>,
     /// Location in the source file
     pub location: AstLocation,
     /// Start byte offset
@@ -32,7 +41,7 @@ pub struct HttpCallSite {
 }
 
 /// Known HTTP client libraries in the TypeScript/JavaScript ecosystem.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum HttpClientKind {
     /// Native fetch API
     Fetch,
@@ -50,16 +59,36 @@ pub enum HttpClientKind {
     Ky,
     /// Superagent
     Superagent,
+    /// Custom client registered through `HttpDetectionConfig`
+    Other(String),
     /// Unknown HTTP client
     Unknown,
 }
 
 /// Summarize HTTP client calls in a TypeScript file.
 pub fn summarize_http_clients(parsed: &ParsedFile) -> Vec<HttpCallSite> {
+    summarize_http_clients_with(parsed, &HttpDetectionConfig::default())
+}
+
+/// Like [`summarize_http_clients`], also reporting calls through the custom
+/// clients registered in `config`.
+pub fn summarize_http_clients_with(
+    parsed: &ParsedFile,
+    config: &HttpDetectionConfig,
+) -> Vec<HttpCallSite> {
     let mut calls = Vec::new();
 
     let root = parsed.tree.root_node();
-    walk_for_http_calls(root, parsed, &mut calls, None, false);
+    let timeout_clients = collect_timeout_clients(root, parsed);
+    walk_for_http_calls(
+        root,
+        parsed,
+        &mut calls,
+        &timeout_clients,
+        config,
+        None,
+        false,
+    );
 
     calls
 }
@@ -68,6 +97,8 @@ fn walk_for_http_calls(
     node: tree_sitter::Node,
     parsed: &ParsedFile,
     calls: &mut Vec<HttpCallSite>,
+    timeout_clients: &HashSet<String>,
+    config: &HttpDetectionConfig,
     current_function: Option<&str>,
     in_async: bool,
 ) {
@@ -94,7 +125,14 @@ fn walk_for_http_calls(
 
     // Check for HTTP calls
     if node.kind() == "call_expression" {
-        if let Some(call) = detect_http_call(parsed, &node, effective_function, effective_async) {
+        if let Some(call) = detect_http_call(
+            parsed,
+            &node,
+            timeout_clients,
+            config,
+            effective_function,
+            effective_async,
+        ) {
             calls.push(call);
         }
     }
@@ -103,14 +141,81 @@ fn walk_for_http_calls(
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
-            walk_for_http_calls(child, parsed, calls, effective_function, effective_async);
+            walk_for_http_calls(
+                child,
+                parsed,
+                calls,
+                timeout_clients,
+                config,
+                effective_function,
+                effective_async,
+            );
+        }
+    }
+}
+
+/// Collect client receivers whose timeout is configured once for every call:
+/// - instances like `const api = axios.create({ timeout: 5000 })`
+/// - the global axios instance after `axios.defaults.timeout = 5000`
+fn collect_timeout_clients(node: tree_sitter::Node, parsed: &ParsedFile) -> HashSet<String> {
+    let mut clients = HashSet::new();
+    let mut stack = vec![node];
+
+    while let Some(current) = stack.pop() {
+        match current.kind() {
+            "variable_declarator" => {
+                let name = current.child_by_field_name("name");
+                let value = current.child_by_field_name("value");
+                if let (Some(name), Some(value)) = (name, value)
+                    && is_client_factory_with_timeout(parsed, &value)
+                {
+                    clients.insert(parsed.text_for_node(&name));
+                }
+            }
+            "assignment_expression" => {
+                if let Some(left) = current.child_by_field_name("left") {
+                    let left_text = parsed.text_for_node(&left);
+                    if let Some(client) = left_text.strip_suffix(".defaults.timeout") {
+                        clients.insert(client.to_string());
+                    }
+                }
+            }
+            _ => {}
         }
+
+        let mut cursor = current.walk();
+        stack.extend(current.children(&mut cursor));
     }
+
+    clients
+}
+
+/// Check for `axios.create({ timeout })`, `got.extend({ timeout })` or `ky.create({ timeout })`.
+fn is_client_factory_with_timeout(parsed: &ParsedFile, value: &tree_sitter::Node) -> bool {
+    if value.kind() != "call_expression" {
+        return false;
+    }
+    let Some(func_node) = value.child_by_field_name("function") else {
+        return false;
+    };
+    let callee = parsed.text_for_node(&func_node);
+    if !matches!(
+        callee.as_str(),
+        "axios.create" | "got.extend" | "ky.create" | "ky.extend"
+    ) {
+        return false;
+    }
+
+    value
+        .child_by_field_name("arguments")
+        .is_some_and(|args| parsed.text_for_node(&args).contains("timeout"))
 }
 
 fn detect_http_call(
     parsed: &ParsedFile,
     node: &tree_sitter::Node,
+    timeout_clients: &HashSet<String>,
+    config: &HttpDetectionConfig,
     function_name: Option<&str>,
     in_async: bool,
 ) -> Option<HttpCallSite> {
@@ -119,7 +224,19 @@ fn detect_http_call(
     let location = parsed.location_for_node(node);
 
     // Detect HTTP client and method
-    let (client_kind, method) = detect_client_and_method(&callee)?;
+    // Registered custom clients take precedence over the built-in libraries
+    let (client_kind, method) = match config.match_callee(&callee) {
+        Some((name, method)) => (HttpClientKind::Other(name.to_string()), method.to_string()),
+        None => detect_client_and_method(&callee)?,
+    };
+
+    // Instance factories (e.g., axios.create()) configure a client, they don't send a request
+    if matches!(
+        callee.as_str(),
+        "axios.create" | "got.extend" | "ky.create" | "ky.extend"
+    ) {
+        return None;
+    }
 
     // Exclude route handlers (e.g., app.get('/path'), router.post('/path'))
     if client_kind == HttpClientKind::Unknown && is_route_handler(parsed, node) {
@@ -137,7 +254,7 @@ fn detect_http_call(
         });
 
     // Check for timeout configuration
-    let has_timeout = check_timeout(parsed, node, &callee);
+    let has_timeout = check_timeout(parsed, node, &callee, timeout_clients);
 
     // Check for error handling
     let has_error_handling = check_error_handling(node);
@@ -145,6 +262,12 @@ fn detect_http_call(
     // Check for retry logic
     let has_retry = check_retry(parsed, node);
 
+    // Check for headers and credentials in the request config
+    let (has_auth_header, custom_headers) = node
+        .child_by_field_name("arguments")
+        .map(|args| scan_headers(&parsed.text_for_node(&args)))
+        .unwrap_or_default();
+
     Some(HttpCallSite {
         client_kind,
         method,
@@ -154,6 +277,8 @@ fn detect_http_call(
         has_retry,
         function_name: function_name.map(|s| s.to_string()),
         in_async_context: in_async,
+        has_auth_header,
+        custom_headers,
         location,
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
@@ -296,7 +421,20 @@ fn is_route_handler(parsed: &ParsedFile, node: &tree_sitter::Node) -> bool {
     false
 }
 
-fn check_timeout(parsed: &ParsedFile, node: &tree_sitter::Node, callee: &str) -> bool {
+fn check_timeout(
+    parsed: &ParsedFile,
+    node: &tree_sitter::Node,
+    callee: &str,
+    timeout_clients: &HashSet<String>,
+) -> bool {
+    // Client instance configured with a default timeout
+    let receiver = callee
+        .rsplit_once('.')
+        .map_or(callee, |(receiver, _)| receiver);
+    if timeout_clients.contains(receiver) {
+        return true;
+    }
+
     // Check arguments for timeout configuration
     if let Some(args_node) = node.child_by_field_name("arguments") {
         let args_text = parsed.text_for_node(&args_node);
@@ -425,6 +563,58 @@ axios.get('https://api.example.com', { timeout: 5000 });
         assert!(!calls[0].has_timeout);
     }
 
+    #[test]
+    fn detects_timeout_via_abort_controller_signal() {
+        let src = r#"
+const controller = new AbortController();
+setTimeout(() => controller.abort(), 5000);
+fetch('https://api.example.com', { signal: controller.signal });
+"#;
+        let calls = parse_and_summarize(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_timeout_on_axios_instance() {
+        let src = r#"
+const apiClient = axios.create({ baseURL: 'https://api.example.com', timeout: 5000 });
+apiClient.get('users');
+"#;
+        let calls = parse_and_summarize(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_missing_timeout_on_axios_instance() {
+        let src = r#"
+const apiClient = axios.create({ baseURL: 'https://api.example.com' });
+apiClient.get('users');
+"#;
+        let calls = parse_and_summarize(src);
+        assert_eq!(calls.len(), 1);
+        assert!(!calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_axios_defaults_timeout() {
+        let src = r#"
+axios.defaults.timeout = 5000;
+axios.get('https://api.example.com');
+"#;
+        let calls = parse_and_summarize(src);
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].has_timeout);
+    }
+
+    #[test]
+    fn detects_missing_timeout_in_axios() {
+        let calls = parse_and_summarize("axios.get('https://api.example.com');");
+        assert_eq!(calls.len(), 1);
+        assert!(!calls[0].has_timeout);
+    }
+
     #[test]
     fn detects_async_context() {
         let src = r#"
@@ -7,6 +7,8 @@ pub mod http;
 pub mod model;
 pub mod nestjs;
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 
 use crate::parse::ast::ParsedFile;
@@ -21,6 +23,18 @@ pub fn build_typescript_semantics(parsed: &ParsedFile) -> Result<TsFileSemantics
     Ok(sem)
 }
 
+/// Build the semantic model for a TypeScript file, resolving `tsconfig.json`
+/// path aliases (e.g. `@app/*` → `./src/*`) in its imports.
+pub fn build_typescript_semantics_with_aliases(
+    parsed: &ParsedFile,
+    aliases: &BTreeMap<String, String>,
+) -> Result<TsFileSemantics> {
+    let mut sem = TsFileSemantics::from_parsed(parsed);
+    sem.resolve_path_aliases(aliases);
+    sem.analyze_frameworks(parsed)?;
+    Ok(sem)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,6 +85,44 @@ const x = 42;
         assert!(!sem.variables.is_empty());
     }
 
+    #[test]
+    fn path_aliases_resolve_to_local_imports() {
+        use crate::semantics::common::CommonSemantics;
+
+        let sf = SourceFile {
+            path: "src/index.ts".to_string(),
+            language: Language::Typescript,
+            content: r#"
+import { helper } from '@app/util';
+import { Button } from '@app/ui/button';
+import express from 'express';
+import { x } from '@application/x';
+"#
+            .to_string(),
+        };
+        let parsed = parse_typescript_file(FileId(1), &sf).expect("parsing should succeed");
+        let aliases = BTreeMap::from([
+            ("@app".to_string(), "./src".to_string()),
+            ("@app/ui/*".to_string(), "packages/ui/*".to_string()),
+        ]);
+        let sem = build_typescript_semantics_with_aliases(&parsed, &aliases)
+            .expect("semantics building should succeed");
+
+        assert_eq!(sem.imports[0].module, "@app/util");
+        assert_eq!(sem.imports[0].resolved_path.as_deref(), Some("src/util"));
+        assert_eq!(sem.imports[2].resolved_path, None);
+
+        let imports = sem.imports();
+        assert_eq!(imports[0].module_path, "src/util");
+        assert!(imports[0].is_local());
+        assert_eq!(imports[1].module_path, "packages/ui/button");
+        assert!(imports[1].is_local());
+        assert_eq!(imports[2].module_path, "express");
+        assert!(imports[2].is_external());
+        assert_eq!(imports[3].module_path, "@application/x");
+        assert!(imports[3].is_external());
+    }
+
     #[test]
     fn build_typescript_semantics_runs_framework_analysis() {
         let src = r#"
@@ -1,9 +1,16 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use serde::{Deserialize, Serialize};
 
 use crate::parse::ast::{AstLocation, FileId, ParsedFile};
-use crate::semantics::common::CommonLocation;
+use crate::semantics::common::async_ops::{AsyncOperation, AsyncOperationType, AsyncRuntime};
 use crate::semantics::common::calls::FunctionCall;
-use crate::semantics::common::db::{DbLibrary, DbOperation, DbOperationType};
+use crate::semantics::common::db::{
+    DbLibrary, DbOperation, DbOperationType, is_mongo_collection_receiver, is_redis_receiver,
+};
+use crate::semantics::common::{CommonLocation, Confidence};
+use crate::semantics::metrics::{TYPESCRIPT_RULES, cyclomatic_complexity, max_nesting_depth};
+use crate::semantics::references::{self, referenced_identifiers};
 use crate::types::context::Language;
 
 use super::http::HttpCallSite;
@@ -82,6 +89,13 @@ pub struct TsFileSemantics {
     pub file_id: FileId,
     pub path: String,
     pub language: Language,
+    /// Number of lines in the source file
+    #[serde(default)]
+    pub line_count: usize,
+
+    /// Names referenced outside import statements (for unused-import detection)
+    #[serde(default)]
+    pub identifiers: BTreeSet<String>,
 
     /// Original source content (for suppression comment checking)
     pub source: String,
@@ -95,6 +109,12 @@ pub struct TsFileSemantics {
     /// Classes defined in the file
     pub classes: Vec<TsClass>,
 
+    /// Interfaces defined in the file
+    pub interfaces: Vec<TsInterface>,
+
+    /// Type aliases defined in the file
+    pub type_aliases: Vec<TsTypeAlias>,
+
     /// Top-level variable declarations
     pub variables: Vec<TsVariable>,
 
@@ -228,12 +248,34 @@ pub struct TsImport {
     /// Named imports, e.g. ["Router", "Request"] in `import { Router, Request } from 'express'`
     pub named_imports: Vec<String>,
 
+    /// Local aliases of renamed named imports, e.g. `Router` → `R` in `import { Router as R }`
+    #[serde(default)]
+    pub named_import_aliases: BTreeMap<String, String>,
+
     /// Namespace import, e.g. "fs" in `import * as fs from 'fs'`
     pub namespace_import: Option<String>,
 
     /// Whether this is a type-only import
     pub is_type_only: bool,
 
+    /// Whether this is a dynamic `import(...)` expression
+    #[serde(default)]
+    pub is_dynamic: bool,
+
+    /// Byte range of the import statement in the source
+    #[serde(default)]
+    pub start_byte: usize,
+    #[serde(default)]
+    pub end_byte: usize,
+
+    /// Source text of the import statement
+    #[serde(default)]
+    pub raw_text: String,
+
+    /// Project-relative path a `tsconfig.json` path alias resolved `module` to
+    #[serde(default)]
+    pub resolved_path: Option<String>,
+
     pub location: AstLocation,
 }
 
@@ -245,7 +287,13 @@ pub struct TsFunction {
     pub is_generator: bool,
     pub is_exported: bool,
     pub params: Vec<TsParam>,
+    /// Generic type parameters as written, e.g. `T extends Base`
+    #[serde(default)]
+    pub type_params: Vec<String>,
     pub return_type: Option<String>,
+    /// Name of the enclosing function for nested functions and closures
+    #[serde(default)]
+    pub parent_function: Option<String>,
     pub location: AstLocation,
     /// Whether this function has a try-catch block
     pub has_try_catch: bool,
@@ -257,6 +305,10 @@ pub struct TsFunction {
     pub start_byte: usize,
     /// End byte offset for patching
     pub end_byte: usize,
+    /// Cyclomatic complexity (1 for straight-line code)
+    pub cyclomatic_complexity: u32,
+    /// Deepest nesting of control blocks (1 for straight-line code)
+    pub max_nesting_depth: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -282,6 +334,41 @@ pub struct TsClass {
     pub location: AstLocation,
 }
 
+/// Representation of a TypeScript interface declaration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsInterface {
+    pub name: String,
+    pub is_exported: bool,
+    /// Extended interfaces, e.g. ["Base", "Timestamped<Date>"]
+    pub extends: Vec<String>,
+    /// Property signatures
+    pub fields: Vec<TsInterfaceField>,
+    /// Names of method signatures
+    pub methods: Vec<String>,
+    pub location: AstLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsInterfaceField {
+    pub name: String,
+    /// Declared type without the leading colon, e.g. "string"
+    pub type_annotation: Option<String>,
+    pub is_optional: bool,
+    pub is_readonly: bool,
+}
+
+/// Representation of a TypeScript `type` alias declaration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsTypeAlias {
+    pub name: String,
+    pub is_exported: bool,
+    /// Full text of the aliased type
+    pub value: String,
+    /// Members when the alias is a union, e.g. ["'active'", "'disabled'"]
+    pub union_members: Vec<String>,
+    pub location: AstLocation,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TsMethod {
     pub name: String,
@@ -291,12 +378,19 @@ pub struct TsMethod {
     pub is_protected: bool,
     pub decorators: Vec<String>,
     pub params: Vec<TsParam>,
+    /// Generic type parameters as written, e.g. `T extends Base`
+    #[serde(default)]
+    pub type_params: Vec<String>,
     pub return_type: Option<String>,
     pub location: AstLocation,
     /// Start byte offset for patching
     pub start_byte: usize,
     /// End byte offset for patching
     pub end_byte: usize,
+    /// Cyclomatic complexity (1 for straight-line code)
+    pub cyclomatic_complexity: u32,
+    /// Deepest nesting of control blocks (1 for straight-line code)
+    pub max_nesting_depth: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -345,6 +439,10 @@ pub struct TsCallSite {
 
     /// Whether this call is awaited
     pub is_awaited: bool,
+
+    /// Whether the call's result is discarded (the call is a bare expression statement)
+    #[serde(default)]
+    pub is_discarded: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -499,10 +597,14 @@ impl TsFileSemantics {
             file_id: parsed.file_id,
             path: parsed.path.clone(),
             language: parsed.language,
+            line_count: parsed.source.lines().count(),
+            identifiers: referenced_identifiers(parsed, &references::TYPESCRIPT_RULES),
             source: (*parsed.source).clone(),
             imports: Vec::new(),
             functions: Vec::new(),
             classes: Vec::new(),
+            interfaces: Vec::new(),
+            type_aliases: Vec::new(),
             variables: Vec::new(),
             calls: Vec::new(),
             http_calls: Vec::new(),
@@ -518,7 +620,7 @@ impl TsFileSemantics {
             try_catches: Vec::new(),
         };
 
-        if parsed.language == Language::Typescript {
+        if matches!(parsed.language, Language::Typescript | Language::Javascript) {
             collect_semantics(parsed, &mut sem);
         }
 
@@ -554,12 +656,132 @@ impl TsFileSemantics {
 
         Ok(())
     }
+
+    /// Record the path each import that uses a `tsconfig.json` `paths` alias
+    /// resolves to.
+    ///
+    /// Keys and targets may be written with or without the trailing `/*`
+    /// (`"@app/*": "./src/*"` and `"@app": "./src"` are equivalent). Targets
+    /// are relative to the project root, as with a `baseUrl` of `.`. The
+    /// longest matching alias wins. `module` keeps the specifier as written;
+    /// the project-relative result goes to `resolved_path`, which also marks
+    /// the import as local.
+    pub fn resolve_path_aliases(&mut self, aliases: &BTreeMap<String, String>) {
+        let mut aliases: Vec<(&str, &str)> = aliases
+            .iter()
+            .map(|(alias, target)| {
+                (
+                    alias.trim_end_matches("/*"),
+                    target.trim_end_matches("/*").trim_end_matches('/'),
+                )
+            })
+            .collect();
+        aliases.sort_by_key(|(alias, _)| std::cmp::Reverse(alias.len()));
+
+        for imp in &mut self.imports {
+            let resolved = aliases.iter().find_map(|(alias, target)| {
+                let rest = imp.module.strip_prefix(alias)?;
+                if !rest.is_empty() && !rest.starts_with('/') {
+                    return None;
+                }
+                let target = match target.trim_start_matches("./") {
+                    "." => "",
+                    target => target,
+                };
+                Some(
+                    format!("{}{}", target, rest)
+                        .trim_start_matches('/')
+                        .to_string(),
+                )
+            });
+            if resolved.is_some() {
+                imp.resolved_path = resolved;
+            }
+        }
+    }
+
+    /// Look up an interface declared in this file by name.
+    pub fn interface(&self, name: &str) -> Option<&TsInterface> {
+        self.interfaces.iter().find(|i| i.name == name)
+    }
+
+    /// Promise-returning calls whose result is dropped: not awaited,
+    /// returned, assigned, or handled with `.catch()`.
+    ///
+    /// A call is considered promise-returning when it targets an `async`
+    /// function or method declared in this file, or `fetch`. A bare
+    /// `.then()`/`.finally()` chain on such a call is also flagged since it
+    /// still swallows rejections.
+    pub fn floating_promises(&self) -> Vec<AsyncOperation> {
+        let mut async_names: Vec<&str> = self
+            .functions
+            .iter()
+            .filter(|f| f.is_async)
+            .map(|f| f.name.as_str())
+            .chain(
+                self.classes
+                    .iter()
+                    .flat_map(|c| c.methods.iter())
+                    .filter(|m| m.is_async)
+                    .map(|m| m.name.as_str()),
+            )
+            .collect();
+        async_names.push("fetch");
+
+        let returns_promise = |part: &str| {
+            let name = part.split('(').next().unwrap_or(part);
+            async_names.contains(&name)
+        };
+
+        self.calls
+            .iter()
+            .filter(|call| call.is_discarded && !call.is_awaited)
+            .filter(|call| {
+                let parts = &call.function_call.callee_parts;
+                match parts.last().map(String::as_str) {
+                    Some("then" | "finally") if parts.len() >= 2 => {
+                        parts[parts.len() - 2].contains('(')
+                            && returns_promise(&parts[parts.len() - 2])
+                    }
+                    Some(name) => returns_promise(name),
+                    None => false,
+                }
+            })
+            .map(|call| {
+                let fc = &call.function_call;
+                AsyncOperation {
+                    runtime: AsyncRuntime::PromiseNative,
+                    operation_type: AsyncOperationType::TaskSpawn,
+                    has_error_handling: false,
+                    error_handling: None,
+                    has_timeout: false,
+                    timeout_value: None,
+                    has_cancellation: false,
+                    cancellation_handling: None,
+                    is_bounded: false,
+                    bound_limit: None,
+                    in_loop: call.in_loop,
+                    in_comprehension: false,
+                    has_cleanup: false,
+                    confidence: Confidence::High,
+                    operation_text: format!("{}{}", fc.callee_expr, call.args_repr),
+                    location: fc.location.clone(),
+                    enclosing_function: Some(fc.caller_function.clone())
+                        .filter(|name| !name.is_empty()),
+                    start_byte: fc.location.start_byte,
+                    end_byte: fc.location.end_byte,
+                }
+            })
+            .collect()
+    }
 }
 
 /// Context for tracking loop nesting during AST traversal.
 #[derive(Default, Clone)]
 struct TraversalContext {
     in_loop: bool,
+    /// Inside a Prisma `$transaction` or TypeORM/Sequelize/Knex `.transaction(...)` call
+    in_transaction: bool,
     current_function: Option<String>,
     current_class: Option<String>,
     current_qualified_name: Option<String>,
@@ -616,7 +838,14 @@ fn walk_nodes_with_context(
             }
         }
         "lexical_declaration" | "variable_declaration" => {
-            if node.parent().map(|p| p.kind()) == Some("program") {
+            let is_top_level = match node.parent() {
+                Some(p) if p.kind() == "program" => true,
+                Some(p) if p.kind() == "export_statement" => {
+                    p.parent().is_some_and(|gp| gp.kind() == "program")
+                }
+                _ => false,
+            };
+            if is_top_level {
                 for i in 0..node.child_count() {
                     if let Some(child) = node.child(i) {
                         if child.kind() == "variable_declarator" {
@@ -664,12 +893,35 @@ fn walk_nodes_with_context(
                 sem.functions.push(fun);
             }
         }
+        // Closures and callbacks nested in a function; top-level arrow
+        // functions are collected with their declaration above
+        "arrow_function" | "function_expression"
+            if enclosing_function_name(parsed, &node).is_some() =>
+        {
+            let name = function_name(parsed, &node).unwrap_or_else(|| "<anonymous>".to_string());
+            if let Some(fun) = build_arrow_function(parsed, &node, &name) {
+                sem.functions.push(fun);
+            }
+        }
         "class_declaration" => {
             if let Some(class) = build_class(parsed, &node) {
                 sem.classes.push(class);
             }
         }
+        "interface_declaration" => {
+            if let Some(interface) = build_interface(parsed, &node) {
+                sem.interfaces.push(interface);
+            }
+        }
+        "type_alias_declaration" => {
+            if let Some(alias) = build_type_alias(parsed, &node) {
+                sem.type_aliases.push(alias);
+            }
+        }
         "call_expression" => {
+            if let Some(imp) = build_dynamic_import(parsed, &node) {
+                sem.imports.push(imp);
+            }
             if let Some(call) = build_callsite(parsed, &node, &new_ctx) {
                 sem.calls.push(call);
             }
@@ -703,15 +955,38 @@ fn walk_nodes_with_context(
         _ => {}
     }
 
+    // Queries batched into or issued from a transaction callback run inside it
+    let child_ctx = if node.kind() == "call_expression" && is_transaction_call(parsed, &node) {
+        TraversalContext {
+            in_transaction: true,
+            ..new_ctx
+        }
+    } else {
+        new_ctx
+    };
+
     // Recurse into children
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
-            walk_nodes_with_context(child, parsed, sem, new_ctx.clone());
+            walk_nodes_with_context(child, parsed, sem, child_ctx.clone());
         }
     }
 }
 
+/// Whether a call opens a database transaction: `prisma.$transaction([...])`,
+/// `dataSource.manager.transaction(async (em) => ...)`, `sequelize.transaction(...)`.
+fn is_transaction_call(parsed: &ParsedFile, node: &tree_sitter::Node) -> bool {
+    node.child_by_field_name("function")
+        .and_then(|func| func.child_by_field_name("property"))
+        .is_some_and(|property| {
+            matches!(
+                parsed.text_for_node(&property).as_str(),
+                "$transaction" | "transaction"
+            )
+        })
+}
+
 fn build_import(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsImport> {
     let location = parsed.location_for_node(node);
     let text = parsed.text_for_node(node);
@@ -720,6 +995,7 @@ fn build_import(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsImpor
     let mut module = String::new();
     let mut default_import = None;
     let mut named_imports = Vec::new();
+    let mut named_import_aliases = BTreeMap::new();
     let mut namespace_import = None;
     let is_type_only = text.contains("import type");
 
@@ -743,7 +1019,8 @@ fn build_import(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsImpor
                                     default_import = Some(parsed.text_for_node(&import_child));
                                 }
                                 "named_imports" => {
-                                    named_imports = extract_named_imports(parsed, &import_child);
+                                    (named_imports, named_import_aliases) =
+                                        extract_named_imports(parsed, &import_child);
                                 }
                                 "namespace_import" => {
                                     // import * as name
@@ -783,21 +1060,83 @@ fn build_import(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsImpor
         module,
         default_import,
         named_imports,
+        named_import_aliases,
         namespace_import,
         is_type_only,
+        is_dynamic: false,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        raw_text: text,
+        resolved_path: None,
         location,
     })
 }
 
-fn extract_named_imports(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<String> {
+/// Build a TsImport from a dynamic `import(...)` call expression.
+///
+/// Only literal specifiers are recorded; computed ones such as `import(path)`
+/// cannot be resolved.
+fn build_dynamic_import(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsImport> {
+    let func_node = node.child_by_field_name("function")?;
+    if func_node.kind() != "import" {
+        return None;
+    }
+
+    let args = node.child_by_field_name("arguments")?;
+    let module_node = args.named_child(0)?;
+    let mut cursor = module_node.walk();
+    let is_literal = match module_node.kind() {
+        "string" => true,
+        "template_string" => !module_node
+            .named_children(&mut cursor)
+            .any(|child| child.kind() == "template_substitution"),
+        _ => false,
+    };
+    if !is_literal {
+        return None;
+    }
+    let module = parsed
+        .text_for_node(&module_node)
+        .trim_matches(|c| c == '\'' || c == '"' || c == '`')
+        .to_string();
+    if module.is_empty() {
+        return None;
+    }
+
+    Some(TsImport {
+        module,
+        default_import: None,
+        named_imports: Vec::new(),
+        named_import_aliases: BTreeMap::new(),
+        namespace_import: None,
+        is_type_only: false,
+        is_dynamic: true,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        raw_text: parsed.text_for_node(node),
+        resolved_path: None,
+        location: parsed.location_for_node(node),
+    })
+}
+
+/// Extract imported names and, for renamed imports, their local aliases.
+fn extract_named_imports(
+    parsed: &ParsedFile,
+    node: &tree_sitter::Node,
+) -> (Vec<String>, BTreeMap<String, String>) {
     let mut imports = Vec::new();
+    let mut aliases = BTreeMap::new();
 
     for i in 0..node.named_child_count() {
         if let Some(child) = node.named_child(i) {
             if child.kind() == "import_specifier" {
                 // Get the name being imported
                 if let Some(name_node) = child.child_by_field_name("name") {
-                    imports.push(parsed.text_for_node(&name_node));
+                    let name = parsed.text_for_node(&name_node);
+                    if let Some(alias_node) = child.child_by_field_name("alias") {
+                        aliases.insert(name.clone(), parsed.text_for_node(&alias_node));
+                    }
+                    imports.push(name);
                 } else {
                     // Fallback: get text of first identifier child
                     for j in 0..child.child_count() {
@@ -813,7 +1152,7 @@ fn extract_named_imports(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<S
         }
     }
 
-    imports
+    (imports, aliases)
 }
 
 fn build_variable(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsVariable> {
@@ -910,16 +1249,54 @@ fn build_function(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsFun
         is_generator,
         is_exported,
         params,
+        type_params: extract_type_params(parsed, node),
         return_type,
+        parent_function: enclosing_function_name(parsed, node),
         location,
         has_try_catch,
         decorators: Vec::new(),
         inner_calls: Vec::new(),
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &TYPESCRIPT_RULES),
+        max_nesting_depth: max_nesting_depth(node, &TYPESCRIPT_RULES),
     })
 }
 
+/// Name of a function node: its own name, or the variable it is assigned to.
+fn function_name(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<String> {
+    if let Some(name) = node.child_by_field_name("name") {
+        return Some(parsed.text_for_node(&name));
+    }
+    node.parent()
+        .filter(|p| p.kind() == "variable_declarator")
+        .and_then(|p| p.child_by_field_name("name"))
+        .map(|n| parsed.text_for_node(&n))
+}
+
+/// Name of the nearest named function or method enclosing `node`.
+///
+/// Anonymous callbacks are skipped, so a function nested in one reports the
+/// named function around it.
+fn enclosing_function_name(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if matches!(
+            ancestor.kind(),
+            "function_declaration"
+                | "generator_function_declaration"
+                | "function_expression"
+                | "arrow_function"
+                | "method_definition"
+        ) && let Some(name) = function_name(parsed, &ancestor)
+        {
+            return Some(name);
+        }
+        current = ancestor.parent();
+    }
+    None
+}
+
 fn build_arrow_function(
     parsed: &ParsedFile,
     node: &tree_sitter::Node,
@@ -952,16 +1329,34 @@ fn build_arrow_function(
         is_generator: false,
         is_exported,
         params,
+        type_params: extract_type_params(parsed, node),
         return_type,
+        parent_function: enclosing_function_name(parsed, node),
         location,
         has_try_catch,
         decorators: Vec::new(),
         inner_calls: Vec::new(),
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &TYPESCRIPT_RULES),
+        max_nesting_depth: max_nesting_depth(node, &TYPESCRIPT_RULES),
     })
 }
 
+/// Extract the `<...>` type parameters of a function or method.
+fn extract_type_params(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<String> {
+    let Some(type_params) = node.child_by_field_name("type_parameters") else {
+        return Vec::new();
+    };
+
+    let mut cursor = type_params.walk();
+    type_params
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "type_parameter")
+        .map(|child| parsed.text_for_node(&child))
+        .collect()
+}
+
 fn extract_params(parsed: &ParsedFile, params_node: &tree_sitter::Node) -> Vec<TsParam> {
     let mut params = Vec::new();
 
@@ -999,6 +1394,22 @@ fn extract_params(parsed: &ParsedFile, params_node: &tree_sitter::Node) -> Vec<T
                 "identifier" => {
                     name = parsed.text_for_node(&param_node);
                 }
+                // JavaScript grammar: `x = 1` and `...rest`
+                "assignment_pattern" => {
+                    is_optional = true;
+                    if let Some(left) = param_node.child_by_field_name("left") {
+                        name = parsed.text_for_node(&left);
+                    }
+                    if let Some(right) = param_node.child_by_field_name("right") {
+                        default_value = Some(parsed.text_for_node(&right));
+                    }
+                }
+                "rest_pattern" => {
+                    is_rest = true;
+                    if let Some(pattern) = param_node.named_child(0) {
+                        name = parsed.text_for_node(&pattern);
+                    }
+                }
                 _ => {
                     name = parsed.text_for_node(&param_node);
                 }
@@ -1103,6 +1514,116 @@ fn build_class(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsClass>
     })
 }
 
+fn build_interface(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsInterface> {
+    let location = parsed.location_for_node(node);
+
+    let name_node = node.child_by_field_name("name")?;
+    let name = parsed.text_for_node(&name_node);
+
+    let is_exported = node
+        .parent()
+        .map(|p| p.kind() == "export_statement")
+        .unwrap_or(false);
+
+    let mut extends = Vec::new();
+    for i in 0..node.named_child_count() {
+        if let Some(child) = node.named_child(i) {
+            if child.kind() != "extends_type_clause" {
+                continue;
+            }
+            for j in 0..child.named_child_count() {
+                if let Some(type_node) = child.named_child(j) {
+                    extends.push(parsed.text_for_node(&type_node));
+                }
+            }
+        }
+    }
+
+    let mut fields = Vec::new();
+    let mut methods = Vec::new();
+    if let Some(body) = node.child_by_field_name("body") {
+        for i in 0..body.named_child_count() {
+            let Some(member) = body.named_child(i) else {
+                continue;
+            };
+            let Some(member_name) = member.child_by_field_name("name") else {
+                continue;
+            };
+            match member.kind() {
+                "property_signature" => {
+                    let text = parsed.text_for_node(&member);
+                    let type_annotation = member.child_by_field_name("type").map(|t| {
+                        parsed
+                            .text_for_node(&t)
+                            .trim_start_matches(':')
+                            .trim()
+                            .to_string()
+                    });
+                    let is_optional = (0..member.child_count())
+                        .filter_map(|j| member.child(j))
+                        .any(|c| c.kind() == "?");
+                    fields.push(TsInterfaceField {
+                        name: parsed.text_for_node(&member_name),
+                        type_annotation,
+                        is_optional,
+                        is_readonly: text.starts_with("readonly "),
+                    });
+                }
+                "method_signature" => methods.push(parsed.text_for_node(&member_name)),
+                _ => {}
+            }
+        }
+    }
+
+    Some(TsInterface {
+        name,
+        is_exported,
+        extends,
+        fields,
+        methods,
+        location,
+    })
+}
+
+fn build_type_alias(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsTypeAlias> {
+    let location = parsed.location_for_node(node);
+
+    let name_node = node.child_by_field_name("name")?;
+    let name = parsed.text_for_node(&name_node);
+    let value_node = node.child_by_field_name("value")?;
+
+    let is_exported = node
+        .parent()
+        .map(|p| p.kind() == "export_statement")
+        .unwrap_or(false);
+
+    let mut union_members = Vec::new();
+    if value_node.kind() == "union_type" {
+        collect_union_members(parsed, &value_node, &mut union_members);
+    }
+
+    Some(TsTypeAlias {
+        name,
+        is_exported,
+        value: parsed.text_for_node(&value_node),
+        union_members,
+        location,
+    })
+}
+
+/// Flatten a (left-nested) union_type node into its member types.
+fn collect_union_members(parsed: &ParsedFile, node: &tree_sitter::Node, out: &mut Vec<String>) {
+    for i in 0..node.named_child_count() {
+        if let Some(child) = node.named_child(i) {
+            if child.kind() == "union_type" {
+                collect_union_members(parsed, &child, out);
+            } else {
+                out.push(parsed.text_for_node(&child));
+            }
+        }
+    }
+}
+
 fn extract_decorators(parsed: &ParsedFile, node: &tree_sitter::Node) -> Vec<String> {
     let mut decorators = Vec::new();
 
@@ -1201,10 +1722,13 @@ fn build_method(parsed: &ParsedFile, node: &tree_sitter::Node) -> Option<TsMetho
         is_protected,
         decorators,
         params,
+        type_params: extract_type_params(parsed, node),
         return_type,
         location,
         start_byte: node.start_byte(),
         end_byte: node.end_byte(),
+        cyclomatic_complexity: cyclomatic_complexity(parsed, node, &TYPESCRIPT_RULES),
+        max_nesting_depth: max_nesting_depth(node, &TYPESCRIPT_RULES),
     })
 }
 
@@ -1269,7 +1793,7 @@ fn build_callsite(
         location: CommonLocation {
             file_id: parsed.file_id,
             line: location.range.start_line + 1,
-            column: location.range.start_col + 1,
+            column: location.range.start_char_col + 1,
             start_byte: node.start_byte(),
             end_byte: node.end_byte(),
         },
@@ -1303,6 +1827,9 @@ fn build_callsite(
         .parent()
         .map(|p| p.kind() == "await_expression")
         .unwrap_or(false);
+    let is_discarded = node
+        .parent()
+        .is_some_and(|p| p.kind() == "expression_statement");
 
     Some(TsCallSite {
         function_call,
@@ -1310,6 +1837,7 @@ fn build_callsite(
         args_repr,
         in_loop: ctx.in_loop,
         is_awaited,
+        is_discarded,
     })
 }
 
@@ -1336,7 +1864,7 @@ fn build_new_expression(
         location: CommonLocation {
             file_id: parsed.file_id,
             line: location.range.start_line + 1,
-            column: location.range.start_col + 1,
+            column: location.range.start_char_col + 1,
             start_byte: node.start_byte(),
             end_byte: node.end_byte(),
         },
@@ -1370,6 +1898,9 @@ fn build_new_expression(
         .parent()
         .map(|p| p.kind() == "await_expression")
         .unwrap_or(false);
+    let is_discarded = node
+        .parent()
+        .is_some_and(|p| p.kind() == "expression_statement");
 
     Some(TsCallSite {
         function_call,
@@ -1377,6 +1908,7 @@ fn build_new_expression(
         args_repr,
         in_loop: ctx.in_loop,
         is_awaited,
+        is_discarded,
     })
 }
 
@@ -1470,7 +2002,7 @@ fn detect_async_operation_from_call(
             TsAsyncOperationType::PromiseCombinator
         }
         "setTimeout" | "setInterval" | "setImmediate" => TsAsyncOperationType::Timeout,
-        "AbortController" => TsAsyncOperationType::Cancellation,
+        "AbortController" | "AbortSignal.timeout" => TsAsyncOperationType::Cancellation,
         _ => {
             // Check for promise method chains
             if callee.contains(".then(")
@@ -1486,8 +2018,12 @@ fn detect_async_operation_from_call(
 
     let text = parsed.text_for_node(node);
     let has_error_handling = has_try_catch_around(node) || text.contains(".catch(");
-    let (has_timeout, timeout_value) = extract_timeout_from_args(parsed, node);
-    let has_cancellation = text.contains("AbortController") || text.contains("signal");
+    let (has_timeout, timeout_value) = match timer_delay_seconds(parsed, node, &callee) {
+        Some(seconds) => (true, Some(seconds)),
+        None => extract_timeout_from_args(parsed, node),
+    };
+    let has_cancellation =
+        text.contains("AbortController") || text.contains("AbortSignal") || text.contains("signal");
 
     Some(TsAsyncOperation {
         operation_type,
@@ -1569,6 +2105,25 @@ fn has_try_catch_around(node: &tree_sitter::Node) -> bool {
 }
 
 /// Extract timeout value from call arguments.
+/// Delay in seconds of a `setTimeout(cb, ms)` or `AbortSignal.timeout(ms)` call.
+///
+/// Only numeric literals are read (numeric separators allowed); computed
+/// delays return `None`.
+fn timer_delay_seconds(parsed: &ParsedFile, node: &tree_sitter::Node, callee: &str) -> Option<f64> {
+    let delay_idx = match callee {
+        "setTimeout" => 1,
+        "AbortSignal.timeout" => 0,
+        _ => return None,
+    };
+    let args = node.child_by_field_name("arguments")?;
+    let delay = args.named_child(delay_idx)?;
+    if delay.kind() != "number" {
+        return None;
+    }
+    let millis: f64 = parsed.text_for_node(&delay).replace('_', "").parse().ok()?;
+    Some(millis / 1000.0)
+}
+
 fn extract_timeout_from_args(parsed: &ParsedFile, node: &tree_sitter::Node) -> (bool, Option<f64>) {
     if let Some(args_node) = node.child_by_field_name("arguments") {
         let text = parsed.text_for_node(&args_node);
@@ -1606,8 +2161,13 @@ fn detect_db_operation_from_call(
 ) -> Option<DbOperation> {
     let func_node = node.child_by_field_name("function")?;
     let callee = parsed.text_for_node(&func_node);
+    let nosql = detect_nosql_operation(parsed, &callee);
 
     let (library, operation_type) = match callee.as_str() {
+        // MongoDB/Redis clients - checked first since Mongoose models share
+        // the `Model.find` shape matched as Sequelize below
+        _ if nosql.is_some() => nosql?,
+
         // Prisma patterns (case-insensitive) - check first since they use prisma prefix
         s if s.to_lowercase().starts_with("prisma") => {
             let op_type = if s.to_lowercase().contains("find")
@@ -1728,23 +2288,38 @@ fn detect_db_operation_from_call(
 
     let text = parsed.text_for_node(node);
     let ast_location = parsed.location_for_node(node);
+    let model_name = if library == DbLibrary::Prisma {
+        extract_prisma_model(&callee)
+    } else {
+        None
+    };
+    // Prisma and Knex are matched on the client name; Sequelize models can have
+    // any name, so those calls are matched on method names alone.
+    let confidence = match library {
+        DbLibrary::Prisma | DbLibrary::Knex => Confidence::High,
+        DbLibrary::Sequelize => Confidence::Low,
+        _ => Confidence::Medium,
+    };
 
     Some(DbOperation {
         library,
         operation_type,
         has_timeout: false,
         timeout_value: None,
-        in_transaction: false,
+        in_transaction: ctx.in_transaction,
         eager_loading: None,
         in_loop: ctx.in_loop,
         in_iteration: false,
-        model_name: None,
+        model_name,
+        has_string_interpolation: is_interpolated_sql(parsed, node),
         relationship_field: None,
+        sql: None,
+        confidence,
         operation_text: text,
         location: CommonLocation {
             file_id: ast_location.file_id,
             line: ast_location.range.start_line + 1,
-            column: ast_location.range.start_col + 1,
+            column: ast_location.range.start_char_col + 1,
             start_byte: node.start_byte(),
             end_byte: node.end_byte(),
         },
@@ -1754,6 +2329,84 @@ fn detect_db_operation_from_call(
     })
 }
 
+/// Detect MongoDB and Redis client calls (`User.find`, `collection.aggregate`, `redis.get`).
+///
+/// Mongoose models are plain identifiers, so a bare `Model.method` call only
+/// counts in files that mention mongoose.
+fn detect_nosql_operation(
+    parsed: &ParsedFile,
+    callee: &str,
+) -> Option<(DbLibrary, DbOperationType)> {
+    let (receiver, method) = callee.rsplit_once('.')?;
+
+    if is_redis_receiver(receiver) {
+        return Some((
+            DbLibrary::Redis,
+            DbOperationType::from_cache_method(method)?,
+        ));
+    }
+
+    let operation_type = DbOperationType::from_document_method(method)?;
+    let is_collection = is_mongo_collection_receiver(receiver);
+    let is_mongoose_model = parsed.source.contains("mongoose")
+        && !receiver.contains('.')
+        && receiver.starts_with(|c: char| c.is_ascii_uppercase());
+    (is_collection || is_mongoose_model).then_some((DbLibrary::Mongo, operation_type))
+}
+
+/// Extract the model delegate from a Prisma call like `prisma.user.findMany`.
+///
+/// Client-level methods such as `prisma.$queryRaw` have no model.
+fn extract_prisma_model(callee: &str) -> Option<String> {
+    let mut parts = callee.split('.');
+    parts.next()?;
+    let model = parts.next()?;
+    parts.next()?;
+    if model.is_empty() || model.starts_with('$') {
+        return None;
+    }
+    Some(model.to_string())
+}
+
+/// Check whether a database call passes SQL built from non-literal values,
+/// via template literal substitutions or `+` concatenation with a non-literal operand.
+///
+/// Tagged templates (e.g. Prisma's `$queryRaw\`...\``) are parameterized and not flagged.
+fn is_interpolated_sql(parsed: &ParsedFile, node: &tree_sitter::Node) -> bool {
+    fn is_dynamic_string(parsed: &ParsedFile, node: tree_sitter::Node) -> bool {
+        match node.kind() {
+            "template_string" => {
+                let mut cursor = node.walk();
+                node.named_children(&mut cursor)
+                    .any(|child| child.kind() == "template_substitution")
+            }
+            "binary_expression" => {
+                let is_concat = node
+                    .child_by_field_name("operator")
+                    .is_some_and(|op| parsed.text_for_node(&op) == "+");
+                is_concat
+                    && ["left", "right"]
+                        .into_iter()
+                        .filter_map(|field| node.child_by_field_name(field))
+                        .any(|operand| {
+                            operand.kind() != "string" || is_dynamic_string(parsed, operand)
+                        })
+            }
+            _ => false,
+        }
+    }
+
+    let Some(args) = node.child_by_field_name("arguments") else {
+        return false;
+    };
+    if args.kind() != "arguments" {
+        return false;
+    }
+    let mut cursor = args.walk();
+    args.named_children(&mut cursor)
+        .any(|arg| is_dynamic_string(parsed, arg))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1811,6 +2464,120 @@ mod tests {
         assert!(sem.functions[0].is_async);
     }
 
+    #[test]
+    fn collects_interface_fields_and_extends() {
+        let src = r#"
+export interface User extends Entity, Timestamped<Date> {
+    readonly id: string;
+    email?: string;
+    roles: Array<Role>;
+    can(action: string): boolean;
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.interfaces.len(), 1);
+        let user = sem.interface("User").unwrap();
+        assert!(user.is_exported);
+        assert_eq!(user.extends, vec!["Entity", "Timestamped<Date>"]);
+        assert_eq!(user.methods, vec!["can"]);
+
+        let names: Vec<&str> = user.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "email", "roles"]);
+        assert!(user.fields[0].is_readonly);
+        assert!(user.fields[1].is_optional);
+        assert!(!user.fields[2].is_optional);
+        assert_eq!(
+            user.fields[2].type_annotation.as_deref(),
+            Some("Array<Role>")
+        );
+    }
+
+    #[test]
+    fn collects_union_type_alias() {
+        let src = r#"
+type Status = 'active' | 'disabled' | 'pending';
+type Id = string;
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.type_aliases.len(), 2);
+
+        let status = &sem.type_aliases[0];
+        assert_eq!(status.name, "Status");
+        assert!(!status.is_exported);
+        assert_eq!(
+            status.union_members,
+            vec!["'active'", "'disabled'", "'pending'"]
+        );
+
+        let id = &sem.type_aliases[1];
+        assert_eq!(id.value, "string");
+        assert!(id.union_members.is_empty());
+    }
+
+    #[test]
+    fn timer_delays_populate_timeout_value_in_seconds() {
+        use crate::semantics::common::CommonSemantics;
+
+        let src = r#"
+function schedule(cb: () => void) {
+    setTimeout(cb, 5000);
+    setTimeout(cb, delayMs);
+    const signal = AbortSignal.timeout(3_000);
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let ops = sem.async_operations();
+        let timeout_of = |prefix: &str| {
+            ops.iter()
+                .find(|op| op.operation_text.starts_with(prefix))
+                .map(|op| op.timeout_value)
+                .expect("operation is detected")
+        };
+        assert_eq!(timeout_of("setTimeout(cb, 5000)"), Some(5.0));
+        assert_eq!(timeout_of("setTimeout(cb, delayMs)"), None);
+        assert_eq!(timeout_of("AbortSignal.timeout"), Some(3.0));
+    }
+
+    #[test]
+    fn flags_bare_call_to_async_function_as_floating_promise() {
+        let src = r#"
+async function fetchData() {
+    return 1;
+}
+
+async function main() {
+    fetchData();
+    await fetchData();
+    const pending = fetchData();
+    fetchData().catch(console.error);
+    fetchData().then(render);
+    return fetchData();
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let floating = sem.floating_promises();
+        let texts: Vec<&str> = floating
+            .iter()
+            .map(|op| op.operation_text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["fetchData()", "fetchData().then(render)"]);
+        assert_eq!(floating[0].enclosing_function.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn awaited_async_call_is_not_floating() {
+        let src = r#"
+async function fetchData() {}
+
+async function main() {
+    await fetchData();
+    syncWork();
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert!(sem.floating_promises().is_empty());
+    }
+
     #[test]
     fn collects_class() {
         let src = r#"
@@ -1910,6 +2677,7 @@ async function manageUser(prisma: any) {
         assert_eq!(sem.db_operations[1].library.as_str(), "Prisma");
         assert_eq!(sem.db_operations[1].operation_type.as_str(), "UPDATE");
         assert_eq!(sem.db_operations[2].library.as_str(), "Prisma");
+        assert_eq!(sem.db_operations[0].model_name.as_deref(), Some("user"));
         assert_eq!(sem.db_operations[2].operation_type.as_str(), "DELETE");
     }
 
@@ -1963,6 +2731,85 @@ async function manageUsers(User: any) {
         assert_eq!(sem.db_operations[0].operation_type.as_str(), "SELECT");
     }
 
+    #[test]
+    fn extracts_prisma_model_name() {
+        let src = r#"
+async function getOrders(prisma: any) {
+    await prisma.order.findMany({ where: { paid: true } });
+    await prisma.$queryRaw`SELECT 1`;
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.db_operations.len(), 2);
+        assert_eq!(sem.db_operations[0].model_name.as_deref(), Some("order"));
+        assert_eq!(sem.db_operations[1].model_name, None);
+    }
+
+    #[test]
+    fn flags_template_literal_raw_sql_as_interpolated() {
+        let src = r#"
+async function getUser(sequelize: any, id: string) {
+    await sequelize.query(`SELECT * FROM users WHERE id = ${id}`);
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.db_operations.len(), 1);
+        assert_eq!(sem.db_operations[0].operation_type.as_str(), "RAW_SQL");
+        assert!(sem.db_operations[0].has_string_interpolation);
+    }
+
+    #[test]
+    fn prisma_transaction_marks_operations_in_transaction() {
+        let src = r#"
+async function transfer(prisma: any) {
+    await prisma.$transaction([
+        prisma.account.update({ where: { id: 1 }, data: { balance: 0 } }),
+        prisma.ledger.create({ data: { amount: 10 } }),
+    ]);
+    await prisma.audit.create({ data: { action: "transfer" } });
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.db_operations.len(), 3);
+        let in_tx: Vec<bool> = sem
+            .db_operations
+            .iter()
+            .map(|op| op.in_transaction)
+            .collect();
+        assert_eq!(in_tx, vec![true, true, false]);
+    }
+
+    #[test]
+    fn typeorm_transaction_callback_marks_operations_in_transaction() {
+        let src = r#"
+async function save(dataSource: any, userRepository: any) {
+    await dataSource.manager.transaction(async (manager) => {
+        await userRepository.save({ name: "a" });
+    });
+    await userRepository.delete({ name: "b" });
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        let in_tx: Vec<bool> = sem
+            .db_operations
+            .iter()
+            .map(|op| op.in_transaction)
+            .collect();
+        assert_eq!(in_tx, vec![true, false]);
+    }
+
+    #[test]
+    fn parameterized_raw_sql_not_interpolated() {
+        let src = r#"
+async function getUser(sequelize: any, id: string) {
+    await sequelize.query("SELECT * FROM users WHERE id = ?", { replacements: [id] });
+}
+"#;
+        let sem = parse_and_build_semantics(src);
+        assert_eq!(sem.db_operations.len(), 1);
+        assert!(!sem.db_operations[0].has_string_interpolation);
+    }
+
     #[test]
     fn detects_drizzle_orm_operations() {
         let src = r#"
@@ -1,7 +1,13 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
 
+use crate::semantics::common::CommonLocation;
+
 /// Supported programming languages.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
     Python,
@@ -12,6 +18,33 @@ pub enum Language {
     Javascript,
 }
 
+impl Language {
+    /// Map a file extension (with or without the leading dot) to a language.
+    ///
+    /// `.js`/`.jsx`/`.mjs`/`.cjs` map to `Javascript`, `.ts`/`.tsx`/`.mts`/`.cts`
+    /// to `Typescript`.
+    pub fn from_extension(ext: &str) -> Option<Language> {
+        let ext = ext.trim_start_matches('.').to_ascii_lowercase();
+        match ext.as_str() {
+            "py" | "pyi" => Some(Language::Python),
+            "rs" => Some(Language::Rust),
+            "go" => Some(Language::Go),
+            "java" => Some(Language::Java),
+            "ts" | "tsx" | "mts" | "cts" => Some(Language::Typescript),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Language::Javascript),
+            _ => None,
+        }
+    }
+
+    /// Detect the language of a file from its path's extension.
+    pub fn from_path(path: &str) -> Option<Language> {
+        Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Language::from_extension)
+    }
+}
+
 /// Frameworks we can detect and reason about.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrameworkGuess {
@@ -90,6 +123,39 @@ pub struct SourceFile {
     pub content: String,
 }
 
+impl SourceFile {
+    /// Read a file from disk, detecting its language from the extension.
+    ///
+    /// Fails with `ErrorKind::InvalidInput` if the extension is not a known language.
+    pub fn from_path(path: impl AsRef<Path>) -> io::Result<SourceFile> {
+        let path = path.as_ref();
+        let path_str = path.to_string_lossy().into_owned();
+        let language = Language::from_path(&path_str).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unrecognized source file extension: {}", path_str),
+            )
+        })?;
+        let content = fs::read_to_string(path)?;
+
+        Ok(SourceFile {
+            path: path_str,
+            language,
+            content,
+        })
+    }
+
+    /// Exact source text covered by `loc`.
+    ///
+    /// Returns an empty string if the byte range runs past the end of the
+    /// content or does not fall on character boundaries.
+    pub fn slice(&self, loc: &CommonLocation) -> &str {
+        self.content
+            .get(loc.start_byte..loc.end_byte)
+            .unwrap_or_default()
+    }
+}
+
 /// Input for one context in a review session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionContextInput {
@@ -98,3 +164,91 @@ pub struct SessionContextInput {
     pub dimension: Dimension,
     pub files: Vec<SourceFile>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_from_extension_common_extensions() {
+        assert_eq!(Language::from_extension("py"), Some(Language::Python));
+        assert_eq!(Language::from_extension(".go"), Some(Language::Go));
+        assert_eq!(Language::from_extension("rs"), Some(Language::Rust));
+        assert_eq!(Language::from_extension("java"), Some(Language::Java));
+        assert_eq!(Language::from_extension("ts"), Some(Language::Typescript));
+        assert_eq!(Language::from_extension("tsx"), Some(Language::Typescript));
+        assert_eq!(Language::from_extension("js"), Some(Language::Javascript));
+        assert_eq!(Language::from_extension("jsx"), Some(Language::Javascript));
+        assert_eq!(Language::from_extension("mjs"), Some(Language::Javascript));
+        assert_eq!(Language::from_extension("PY"), Some(Language::Python));
+    }
+
+    #[test]
+    fn language_from_extension_unknown_returns_none() {
+        assert_eq!(Language::from_extension("txt"), None);
+        assert_eq!(Language::from_extension(""), None);
+    }
+
+    #[test]
+    fn language_from_path() {
+        assert_eq!(Language::from_path("src/main.rs"), Some(Language::Rust));
+        assert_eq!(
+            Language::from_path("app/models/user.py"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            Language::from_path("types/index.d.ts"),
+            Some(Language::Typescript)
+        );
+        assert_eq!(Language::from_path("Makefile"), None);
+        assert_eq!(Language::from_path("README.md"), None);
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("unfault-core-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn source_file_from_path_reads_and_detects_language() {
+        let path = temp_path("example.py");
+        fs::write(&path, "def hello():\n    pass\n").unwrap();
+
+        let sf = SourceFile::from_path(&path).expect("reading should succeed");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(sf.language, Language::Python);
+        assert_eq!(sf.path, path.to_string_lossy());
+        assert_eq!(sf.content, "def hello():\n    pass\n");
+    }
+
+    #[test]
+    fn source_file_from_path_unknown_extension_is_invalid_input() {
+        let err = SourceFile::from_path(temp_path("notes.txt")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn source_file_from_path_missing_file_is_not_found() {
+        let err = SourceFile::from_path(temp_path("missing.rs")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn source_file_slice_is_bounds_checked() {
+        let sf = SourceFile {
+            path: "a.py".to_string(),
+            language: Language::Python,
+            content: "from app import db\nname = \"café\"\n".to_string(),
+        };
+        let loc = |start_byte, end_byte| CommonLocation {
+            file_id: crate::parse::ast::FileId(1),
+            line: 1,
+            column: 1,
+            start_byte,
+            end_byte,
+        };
+        assert_eq!(sf.slice(&loc(0, 18)), "from app import db");
+        assert_eq!(sf.slice(&loc(27, 31)), "");
+        assert_eq!(sf.slice(&loc(10, 100)), "");
+    }
+}